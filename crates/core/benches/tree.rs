@@ -0,0 +1,75 @@
+//! Tree generation benchmarks.
+//!
+//! Runs against a generated vault by default. Set `VAULT_TREE_BENCH_VAULT` to
+//! benchmark a real vault instead, e.g. one on a network drive:
+//!
+//! ```sh
+//! VAULT_TREE_BENCH_VAULT=/mnt/share/vault cargo bench -p vault-tree-core
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use vault_tree_core::{generate_tree, search_vault, SearchOptions, TreeOptions};
+
+const DIRS: usize = 40;
+const SUBDIRS: usize = 5;
+const NOTES_PER_DIR: usize = 20;
+
+/// Build a vault of `DIRS * SUBDIRS` folders with linked, tagged notes.
+fn generate_vault() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for d in 0..DIRS {
+        for s in 0..SUBDIRS {
+            let folder = dir.path().join(format!("area-{d}/topic-{s}"));
+            fs::create_dir_all(&folder).unwrap();
+            for n in 0..NOTES_PER_DIR {
+                let body = format!(
+                    "---\ntags: [area{d}, topic{s}]\ndate: 2025-01-{:02}\n---\n\n\
+                     # Note {d}-{s}-{n}\n\nSee [[note-{d}-{s}-{}]] and [[note-{}-0-0]].\n",
+                    n % 28 + 1,
+                    (n + 1) % NOTES_PER_DIR,
+                    (d + 1) % DIRS,
+                );
+                fs::write(folder.join(format!("note-{d}-{s}-{n}.md")), body).unwrap();
+            }
+        }
+    }
+    dir
+}
+
+fn bench_vault(c: &mut Criterion, name: &str, path: &Path) {
+    let mut group = c.benchmark_group(name);
+    group.sample_size(20);
+
+    group.bench_function("generate_tree", |b| {
+        b.iter(|| generate_tree(black_box(path), &TreeOptions::default()).unwrap())
+    });
+    group.bench_function("generate_tree_depth_2", |b| {
+        let options = TreeOptions {
+            depth: Some(2),
+            ..Default::default()
+        };
+        b.iter(|| generate_tree(black_box(path), &options).unwrap())
+    });
+    group.bench_function("search_vault", |b| {
+        b.iter(|| search_vault(black_box(path), r"\[\[note-1", &SearchOptions::default()).unwrap())
+    });
+
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    match std::env::var_os("VAULT_TREE_BENCH_VAULT") {
+        Some(path) => bench_vault(c, "external", &PathBuf::from(path)),
+        None => {
+            let vault = generate_vault();
+            bench_vault(c, "generated", vault.path());
+        }
+    }
+}
+
+criterion_group!(tree, benches);
+criterion_main!(tree);
@@ -0,0 +1,293 @@
+use crate::fingerprint::hash_file;
+use crate::scan::{scan_note, NoteScan};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CacheError {
+    #[error("io error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("invalid cache file: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// What was last read from a file, valid while its size and mtime match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    size: u64,
+    modified: SystemTime,
+    #[serde(default)]
+    scan: Option<NoteScan>,
+    #[serde(default)]
+    hash: Option<String>,
+    /// The file's text, for search. Kept in memory only.
+    #[serde(skip)]
+    text: Option<Arc<str>>,
+}
+
+impl CacheEntry {
+    fn text_len(&self) -> usize {
+        self.text.as_ref().map_or(0, |text| text.len())
+    }
+}
+
+/// Most note text [`MetadataCache::text`] keeps in memory, in bytes.
+const MAX_CACHED_TEXT: usize = 64 * 1024 * 1024;
+
+/// Note scans, content hashes, and text keyed by path, size, and mtime, so
+/// unchanged notes aren't read again across tree builds, searches, and index
+/// updates. Entries are keyed by absolute path, so one cache can serve
+/// several vaults; each build or update drops entries under its vault whose
+/// files are gone. Safe to share between threads; [`save`](Self::save) and
+/// [`load`](Self::load) persist it between runs, without the text.
+#[derive(Debug, Default)]
+pub struct MetadataCache {
+    entries: RwLock<HashMap<PathBuf, CacheEntry>>,
+    /// Bytes of text held across all entries.
+    text_bytes: AtomicUsize,
+}
+
+impl MetadataCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a cache written by [`save`](Self::save). A missing file gives an
+    /// empty cache.
+    pub fn load(path: &Path) -> Result<Self, CacheError> {
+        let entries = match fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            entries: RwLock::new(entries),
+            text_bytes: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), CacheError> {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        fs::write(path, serde_json::to_vec(&*entries)?)?;
+        Ok(())
+    }
+
+    /// The note's frontmatter, links, and tags, scanning it only if it
+    /// changed since it was last seen.
+    pub fn scan(&self, path: &Path) -> io::Result<NoteScan> {
+        self.get_or_insert(
+            path,
+            |entry| entry.scan.clone(),
+            |entry| {
+                let scan = scan_note(path)?;
+                entry.scan = Some(scan.clone());
+                Ok(scan)
+            },
+        )
+    }
+
+    /// The file's BLAKE3 hash, reading it only if it changed since it was
+    /// last seen.
+    pub fn hash(&self, path: &Path) -> io::Result<String> {
+        self.get_or_insert(
+            path,
+            |entry| entry.hash.clone(),
+            |entry| {
+                let hash = hash_file(path)?;
+                entry.hash = Some(hash.clone());
+                Ok(hash)
+            },
+        )
+    }
+
+    /// The file's text, reading it only if it changed since it was last
+    /// seen. Up to 64 MiB of text is kept; past that, files are read anew.
+    pub fn text(&self, path: &Path) -> io::Result<Arc<str>> {
+        self.get_or_insert(
+            path,
+            |entry| entry.text.clone(),
+            |entry| {
+                let text: Arc<str> = fs::read_to_string(path)?.into();
+                let held = self.text_bytes.load(Ordering::Relaxed);
+                if held + text.len() <= MAX_CACHED_TEXT {
+                    entry.text = Some(text.clone());
+                }
+                Ok(text)
+            },
+        )
+    }
+
+    /// Drop entries for files that no longer exist.
+    pub fn prune(&self) {
+        self.retain(|path| path.exists());
+    }
+
+    /// Drop entries under `root` for files that no longer exist. Entries of
+    /// other vaults, and of notes a filtered walk skipped, are kept.
+    pub fn prune_under(&self, root: &Path) {
+        self.retain(|path| !path.starts_with(root) || path.exists());
+    }
+
+    fn retain(&self, mut keep: impl FnMut(&Path) -> bool) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|path, entry| {
+            let kept = keep(path);
+            if !kept {
+                self.text_bytes
+                    .fetch_sub(entry.text_len(), Ordering::Relaxed);
+            }
+            kept
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get_or_insert<T>(
+        &self,
+        path: &Path,
+        get: impl FnOnce(&CacheEntry) -> Option<T>,
+        fill: impl FnOnce(&mut CacheEntry) -> io::Result<T>,
+    ) -> io::Result<T> {
+        let metadata = fs::metadata(path)?;
+        let (size, modified) = (metadata.len(), metadata.modified()?);
+        let fresh = |entry: &CacheEntry| entry.size == size && entry.modified == modified;
+
+        let cached = {
+            let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+            match entries.get(path).filter(|e| fresh(e)) {
+                Some(entry) => match get(entry) {
+                    Some(value) => return Ok(value),
+                    // Keep whatever else was already read for this version.
+                    None => Some(entry.clone()),
+                },
+                None => None,
+            }
+        };
+
+        let mut entry = cached.unwrap_or(CacheEntry {
+            size,
+            modified,
+            scan: None,
+            hash: None,
+            text: None,
+        });
+        let value = fill(&mut entry)?;
+        // The text counter only changes under the write lock, so it never
+        // dips below the bytes actually held.
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        self.text_bytes
+            .fetch_add(entry.text_len(), Ordering::Relaxed);
+        if let Some(old) = entries.insert(path.to_path_buf(), entry) {
+            self.text_bytes.fetch_sub(old.text_len(), Ordering::Relaxed);
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn reuses_entries_until_file_changes() {
+        let dir = TempDir::new().unwrap();
+        let note = dir.path().join("note.md");
+        fs::write(&note, "See [[a]]\n").unwrap();
+
+        let cache = MetadataCache::new();
+        assert_eq!(cache.scan(&note).unwrap().links.len(), 1);
+        let hash = cache.hash(&note).unwrap();
+        assert_eq!(cache.len(), 1);
+
+        // Same size and mtime: the cached scan is returned without reading.
+        let modified = fs::metadata(&note).unwrap().modified().unwrap();
+        fs::write(&note, "See [[b]]\n").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&note)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert_eq!(cache.scan(&note).unwrap().links[0].target, "a");
+
+        fs::write(&note, "See [[b]] and [[c]]\n").unwrap();
+        assert_eq!(cache.scan(&note).unwrap().links.len(), 2);
+        assert_ne!(cache.hash(&note).unwrap(), hash);
+    }
+
+    #[test]
+    fn persists_between_runs() {
+        let dir = TempDir::new().unwrap();
+        let note = dir.path().join("note.md");
+        let file = dir.path().join("cache.json");
+        fs::write(&note, "---\ntags: [x]\n---\nBody\n").unwrap();
+
+        assert!(MetadataCache::load(&file).unwrap().is_empty());
+        let cache = MetadataCache::new();
+        cache.scan(&note).unwrap();
+        cache.save(&file).unwrap();
+
+        let loaded = MetadataCache::load(&file).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.scan(&note).unwrap().tags(), vec!["x"]);
+
+        fs::remove_file(&note).unwrap();
+        loaded.prune();
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn prunes_only_missing_files_under_the_root() {
+        let (one, two) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let (a, b) = (one.path().join("a.md"), one.path().join("b.md"));
+        let c = two.path().join("c.md");
+        for path in [&a, &b, &c] {
+            fs::write(path, "Text\n").unwrap();
+        }
+
+        let cache = MetadataCache::new();
+        cache.scan(&a).unwrap();
+        cache.hash(&b).unwrap();
+        cache.text(&c).unwrap();
+        fs::remove_file(&b).unwrap();
+        fs::remove_file(&c).unwrap();
+        cache.prune_under(one.path());
+
+        let entries = cache.entries.read().unwrap();
+        assert!(entries.contains_key(&a) && entries.contains_key(&c));
+        assert!(!entries.contains_key(&b));
+    }
+
+    #[test]
+    fn keeps_text_until_the_file_changes() {
+        let dir = TempDir::new().unwrap();
+        let note = dir.path().join("note.md");
+        fs::write(&note, "first\n").unwrap();
+
+        let cache = MetadataCache::new();
+        assert_eq!(&*cache.text(&note).unwrap(), "first\n");
+        assert_eq!(cache.text_bytes.load(Ordering::Relaxed), 6);
+
+        fs::write(&note, "second line\n").unwrap();
+        assert_eq!(&*cache.text(&note).unwrap(), "second line\n");
+        assert_eq!(cache.text_bytes.load(Ordering::Relaxed), 12);
+
+        fs::remove_file(&note).unwrap();
+        cache.prune();
+        assert_eq!(cache.text_bytes.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::exclude::ExcludeSet;
+use crate::links::{extract_links, normalize_link_target};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use walkdir::WalkDir;
+
+#[derive(Debug, Error)]
+pub enum CanvasError {
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("invalid canvas json: {0}")]
+    JsonError(#[from] serde_json::Error),
+}
+
+/// An Obsidian `.canvas` document (JSON Canvas format). Only the fields
+/// needed to follow references are kept.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Canvas {
+    #[serde(default)]
+    pub nodes: Vec<CanvasNode>,
+    #[serde(default)]
+    pub edges: Vec<CanvasEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanvasNode {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub node_type: String,
+    /// Vault-relative path for `file` nodes.
+    pub file: Option<String>,
+    /// Markdown body for `text` nodes.
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanvasEdge {
+    pub id: String,
+    #[serde(rename = "fromNode")]
+    pub from_node: String,
+    #[serde(rename = "toNode")]
+    pub to_node: String,
+}
+
+/// Notes a canvas points at.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CanvasRefs {
+    /// Markdown files placed on the canvas or wikilinked from text cards.
+    pub files: Vec<String>,
+    /// Canvas edges joining two markdown file cards, as `(from, to)` paths.
+    pub connections: Vec<(String, String)>,
+}
+
+pub fn parse_canvas(content: &str) -> Result<Canvas, CanvasError> {
+    Ok(serde_json::from_str(content)?)
+}
+
+impl Canvas {
+    pub fn references(&self) -> CanvasRefs {
+        let mut refs = CanvasRefs::default();
+        let mut note_files: HashMap<&str, &str> = HashMap::new();
+
+        for node in &self.nodes {
+            match (node.node_type.as_str(), &node.file, &node.text) {
+                ("file", Some(file), _) if file.ends_with(".md") => {
+                    note_files.insert(node.id.as_str(), file.as_str());
+                    push_unique(&mut refs.files, file);
+                }
+                ("text", _, Some(text)) => {
+                    for link in extract_links(text) {
+                        push_unique(&mut refs.files, &link.target);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for edge in &self.edges {
+            if let (Some(from), Some(to)) = (
+                note_files.get(edge.from_node.as_str()),
+                note_files.get(edge.to_node.as_str()),
+            ) {
+                refs.connections.push((from.to_string(), to.to_string()));
+            }
+        }
+
+        refs
+    }
+}
+
+/// Add `file` unless a reference to the same note is already present.
+fn push_unique(files: &mut Vec<String>, file: &str) {
+    let key = |f: &str| {
+        let stem = Path::new(f.trim()).file_stem().unwrap_or_default();
+        normalize_link_target(&stem.to_string_lossy())
+    };
+    if !files.iter().any(|f| key(f) == key(file)) {
+        files.push(file.to_string());
+    }
+}
+
+/// Read and parse a canvas file, returning the notes it references.
+pub fn scan_canvas(path: &Path) -> Result<CanvasRefs, CanvasError> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(parse_canvas(&content)?.references())
+}
+
+/// Every parseable canvas in the vault, keyed by vault-relative path and sorted.
+pub(crate) fn scan_vault_canvases(
+    vault_path: &Path,
+    exclude: &ExcludeSet,
+) -> Vec<(String, CanvasRefs)> {
+    let files: Vec<PathBuf> = WalkDir::new(vault_path)
+        .into_iter()
+        .filter_entry(|e| !exclude.is_excluded(vault_path, e.path()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|x| x == "canvas"))
+        .map(|e| e.path().to_path_buf())
+        .collect();
+
+    let mut canvases: Vec<(String, CanvasRefs)> = files
+        .par_iter()
+        .filter_map(|path| {
+            let relative = path.strip_prefix(vault_path).ok()?;
+            Some((
+                relative.to_string_lossy().to_string(),
+                scan_canvas(path).ok()?,
+            ))
+        })
+        .collect();
+    canvases.sort_by(|a, b| a.0.cmp(&b.0));
+    canvases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = r#"{
+        "nodes": [
+            {"id": "a", "type": "file", "file": "notes/Alpha.md", "x": 0, "y": 0, "width": 400, "height": 400},
+            {"id": "b", "type": "file", "file": "Beta.md", "x": 500, "y": 0, "width": 400, "height": 400},
+            {"id": "c", "type": "text", "text": "See [[Gamma]] and [[Beta]]", "x": 0, "y": 500, "width": 200, "height": 100},
+            {"id": "d", "type": "file", "file": "img/photo.png", "x": 0, "y": 0, "width": 1, "height": 1},
+            {"id": "e", "type": "link", "url": "https://example.com", "x": 0, "y": 0, "width": 1, "height": 1}
+        ],
+        "edges": [
+            {"id": "e1", "fromNode": "a", "toNode": "b", "fromSide": "right", "toSide": "left"},
+            {"id": "e2", "fromNode": "a", "toNode": "d"}
+        ]
+    }"#;
+
+    #[test]
+    fn extracts_canvas_references() {
+        let refs = parse_canvas(BOARD).unwrap().references();
+
+        assert_eq!(refs.files, vec!["notes/Alpha.md", "Beta.md", "Gamma"]);
+        assert_eq!(
+            refs.connections,
+            vec![("notes/Alpha.md".to_string(), "Beta.md".to_string())]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(matches!(
+            parse_canvas("not json"),
+            Err(CanvasError::JsonError(_))
+        ));
+        assert!(parse_canvas("{}").unwrap().nodes.is_empty());
+    }
+}
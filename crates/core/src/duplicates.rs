@@ -0,0 +1,199 @@
+use crate::exclude::ExcludeSet;
+use crate::fingerprint::hash_content;
+use crate::frontmatter::strip_frontmatter;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Minimum Jaccard similarity of word shingles for two notes to count as
+/// near-duplicates.
+pub const NEAR_DUPLICATE_THRESHOLD: f64 = 0.8;
+
+/// Words per shingle when comparing note bodies.
+const SHINGLE_WORDS: usize = 3;
+
+#[derive(Debug, Error)]
+pub enum DuplicateError {
+    #[error("vault path does not exist: {0}")]
+    VaultNotFound(String),
+    #[error("{0}")]
+    Exclude(#[from] crate::exclude::ExcludeError),
+}
+
+/// Notes whose bodies (ignoring frontmatter and surrounding whitespace) are
+/// byte-for-byte identical.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    /// blake3 hash of the shared body.
+    pub hash: String,
+    pub paths: Vec<String>,
+}
+
+/// Two notes with mostly the same wording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarNotes {
+    pub first: String,
+    pub second: String,
+    /// Jaccard similarity of word shingles, from 0.0 to 1.0.
+    pub similarity: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DuplicateReport {
+    pub identical: Vec<DuplicateGroup>,
+    /// Near-identical pairs, most similar first. Notes already reported as
+    /// identical appear here only through one representative.
+    pub similar: Vec<SimilarNotes>,
+}
+
+struct NoteBody {
+    path: String,
+    hash: String,
+    shingles: HashSet<u64>,
+}
+
+/// Find notes with identical or near-identical bodies.
+pub fn find_duplicate_notes(vault_path: &Path) -> Result<DuplicateReport, DuplicateError> {
+    if !vault_path.exists() {
+        return Err(DuplicateError::VaultNotFound(
+            vault_path.display().to_string(),
+        ));
+    }
+
+    let exclude = ExcludeSet::load(vault_path, &[])?;
+    let files: Vec<PathBuf> = exclude
+        .walk_markdown(vault_path, vault_path)
+        .map(|e| e.path().to_path_buf())
+        .collect();
+
+    let mut bodies: Vec<NoteBody> = files
+        .par_iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(path).ok()?;
+            let body = strip_frontmatter(&content).trim();
+            if body.is_empty() {
+                return None;
+            }
+            let relative = path.strip_prefix(vault_path).ok()?;
+            Some(NoteBody {
+                path: relative.to_string_lossy().to_string(),
+                hash: hash_content(body.as_bytes()),
+                shingles: shingles(body),
+            })
+        })
+        .collect();
+    bodies.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut groups: BTreeMap<&str, Vec<&NoteBody>> = BTreeMap::new();
+    for body in &bodies {
+        groups.entry(body.hash.as_str()).or_default().push(body);
+    }
+
+    let mut report = DuplicateReport::default();
+    let mut representatives = Vec::new();
+    for (hash, notes) in &groups {
+        representatives.push(notes[0]);
+        if notes.len() > 1 {
+            report.identical.push(DuplicateGroup {
+                hash: hash.to_string(),
+                paths: notes.iter().map(|n| n.path.clone()).collect(),
+            });
+        }
+    }
+    report.identical.sort_by(|a, b| a.paths[0].cmp(&b.paths[0]));
+
+    for (i, a) in representatives.iter().enumerate() {
+        for b in &representatives[i + 1..] {
+            let similarity = jaccard(&a.shingles, &b.shingles);
+            if similarity >= NEAR_DUPLICATE_THRESHOLD {
+                let (first, second) = if a.path <= b.path { (a, b) } else { (b, a) };
+                report.similar.push(SimilarNotes {
+                    first: first.path.clone(),
+                    second: second.path.clone(),
+                    similarity,
+                });
+            }
+        }
+    }
+    report.similar.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then(a.first.cmp(&b.first))
+            .then(a.second.cmp(&b.second))
+    });
+
+    Ok(report)
+}
+
+/// Hashed runs of consecutive lowercase words.
+fn shingles(body: &str) -> HashSet<u64> {
+    let words: Vec<String> = body
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    words
+        .windows(SHINGLE_WORDS.min(words.len()).max(1))
+        .map(|window| {
+            let mut hasher = DefaultHasher::new();
+            window.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
+
+fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let shared = a.intersection(b).count();
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils::create_test_vault;
+    use std::fs;
+
+    const BODY: &str = "The quick brown fox jumps over the lazy dog while the \
+        farmer watches from the porch and sips his morning coffee slowly.";
+
+    #[test]
+    fn finds_identical_and_similar_notes() {
+        let vault = create_test_vault();
+        fs::write(vault.path().join("a.md"), BODY).unwrap();
+        fs::write(
+            vault.path().join("subdir/b.md"),
+            format!("---\ntitle: Copy\n---\n\n{}\n", BODY),
+        )
+        .unwrap();
+        fs::write(
+            vault.path().join("c.md"),
+            format!("{} Then it rains.", BODY),
+        )
+        .unwrap();
+
+        let report = find_duplicate_notes(vault.path()).unwrap();
+
+        assert_eq!(report.identical.len(), 1);
+        assert_eq!(report.identical[0].paths, vec!["a.md", "subdir/b.md"]);
+        assert_eq!(report.similar.len(), 1);
+        assert_eq!(report.similar[0].first, "a.md");
+        assert_eq!(report.similar[0].second, "c.md");
+        assert!(report.similar[0].similarity < 1.0);
+    }
+
+    #[test]
+    fn distinct_notes_are_not_duplicates() {
+        let vault = create_test_vault();
+        let report = find_duplicate_notes(vault.path()).unwrap();
+
+        assert!(report.identical.is_empty());
+        assert!(report.similar.is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::utils::{is_excluded, is_markdown_file};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::path::Path;
+use thiserror::Error;
+use walkdir::{DirEntry, WalkDir};
+
+/// Per-vault ignore file, one glob per line.
+pub const VAULTIGNORE: &str = ".vaultignore";
+
+#[derive(Debug, Error)]
+pub enum ExcludeError {
+    #[error("invalid exclude pattern: {0}")]
+    InvalidGlob(#[from] globset::Error),
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// User-configured exclusions from `.vaultignore` and option globs, applied on
+/// top of the built-in `.obsidian`/`.git`/`node_modules` skips.
+///
+/// Patterns follow a small gitignore subset: blank lines and `#` comments are
+/// ignored, a pattern without `/` matches a file or directory name at any
+/// depth (`templates`, `*.excalidraw.md`), and a pattern containing `/` is
+/// anchored at the vault root (`archive/2023/**`).
+#[derive(Debug, Clone, Default)]
+pub struct ExcludeSet {
+    globs: Option<GlobSet>,
+}
+
+impl ExcludeSet {
+    pub fn new(patterns: &[String]) -> Result<Self, ExcludeError> {
+        let mut builder = GlobSetBuilder::new();
+        let mut any = false;
+        for pattern in patterns {
+            let pattern = pattern.trim();
+            if pattern.is_empty() || pattern.starts_with('#') {
+                continue;
+            }
+            let pattern = pattern.trim_end_matches('/');
+            let base = match pattern.strip_prefix('/') {
+                Some(anchored) => anchored.to_string(),
+                None if pattern.contains('/') => pattern.to_string(),
+                None => format!("**/{}", pattern),
+            };
+            for glob in [base.clone(), format!("{}/**", base)] {
+                builder.add(GlobBuilder::new(&glob).literal_separator(true).build()?);
+            }
+            any = true;
+        }
+        Ok(Self {
+            globs: any.then(|| builder.build()).transpose()?,
+        })
+    }
+
+    /// Combine the vault's `.vaultignore` (if present) with `extra` patterns.
+    pub fn load(vault_path: &Path, extra: &[String]) -> Result<Self, ExcludeError> {
+        let ignore_file = vault_path.join(VAULTIGNORE);
+        let mut patterns: Vec<String> = if ignore_file.is_file() {
+            std::fs::read_to_string(ignore_file)?
+                .lines()
+                .map(str::to_string)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        patterns.extend_from_slice(extra);
+        Self::new(&patterns)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.globs.is_none()
+    }
+
+    /// Whether `path` (inside `vault_path`) is excluded, either by the built-in
+    /// rules or by a user pattern.
+    pub fn is_excluded(&self, vault_path: &Path, path: &Path) -> bool {
+        if is_excluded(path) {
+            return true;
+        }
+        let Some(globs) = &self.globs else {
+            return false;
+        };
+        path.strip_prefix(vault_path)
+            .is_ok_and(|relative| !relative.as_os_str().is_empty() && globs.is_match(relative))
+    }
+
+    /// Walk markdown files under `root` (which lies inside `vault_path`),
+    /// pruning excluded directories.
+    pub fn walk_markdown<'a>(
+        &'a self,
+        vault_path: &'a Path,
+        root: &Path,
+    ) -> impl Iterator<Item = DirEntry> + 'a {
+        WalkDir::new(root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(move |e| !self.is_excluded(vault_path, e.path()))
+            .filter_map(|e| e.ok())
+            .filter(is_markdown_file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils::create_test_vault;
+    use std::fs;
+
+    #[test]
+    fn matches_names_and_anchored_paths() {
+        let set = ExcludeSet::new(&[
+            "# comment".to_string(),
+            "templates/".to_string(),
+            "*.excalidraw.md".to_string(),
+            "archive/2023".to_string(),
+        ])
+        .unwrap();
+        let vault = Path::new("/vault");
+        let excluded = |p: &str| set.is_excluded(vault, &vault.join(p));
+
+        assert!(excluded("templates"));
+        assert!(excluded("work/templates/daily.md"));
+        assert!(excluded("drawing.excalidraw.md"));
+        assert!(excluded("archive/2023/old.md"));
+        assert!(!excluded("archive/2024/new.md"));
+        assert!(!excluded("notes/templates.md"));
+        assert!(excluded(".obsidian"));
+    }
+
+    #[test]
+    fn loads_vaultignore() {
+        let vault = create_test_vault();
+        fs::write(vault.path().join(VAULTIGNORE), "subdir\n").unwrap();
+
+        let set = ExcludeSet::load(vault.path(), &["note2.md".to_string()]).unwrap();
+        let notes: Vec<_> = set.walk_markdown(vault.path(), vault.path()).collect();
+
+        assert_eq!(notes.len(), 1);
+        assert!(notes[0].path().ends_with("note1.md"));
+    }
+
+    #[test]
+    fn rejects_invalid_glob() {
+        assert!(ExcludeSet::new(&["a[".to_string()]).is_err());
+        assert!(ExcludeSet::new(&[]).unwrap().is_empty());
+    }
+}
@@ -6,6 +6,16 @@ pub fn hash_content(content: &[u8]) -> String {
     hasher.finalize().to_hex().to_string()
 }
 
+/// BLAKE3 of a file's contents; the same digest as [`hash_content`]. Large
+/// files are memory-mapped and hashed on multiple threads.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn hash_file(path: &std::path::Path) -> std::io::Result<String> {
+    let mut hasher = Hasher::new();
+    hasher.update_mmap_rayon(path)?;
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
 pub fn hash_file(path: &std::path::Path) -> std::io::Result<String> {
     let content = std::fs::read(path)?;
     Ok(hash_content(&content))
@@ -30,6 +40,19 @@ mod tests {
         assert_ne!(hash1, hash2);
     }
 
+    #[test]
+    fn file_hash_matches_content_hash() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let small = dir.path().join("small.md");
+        let large = dir.path().join("large.pdf");
+        let content: Vec<u8> = (0..4 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&small, b"# Note").unwrap();
+        std::fs::write(&large, &content).unwrap();
+
+        assert_eq!(hash_file(&small).unwrap(), hash_content(b"# Note"));
+        assert_eq!(hash_file(&large).unwrap(), hash_content(&content));
+    }
+
     #[test]
     fn hash_is_64_chars() {
         let hash = hash_content(b"test");
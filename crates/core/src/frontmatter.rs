@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -17,27 +18,83 @@ pub struct Frontmatter {
     pub tags: Vec<String>,
     pub slug: Option<String>,
     pub description: Option<String>,
+    /// Alternative names that wikilinks may use to reach this note.
+    #[serde(default, deserialize_with = "string_or_list")]
+    pub aliases: Vec<String>,
+    /// Every other key, as written in the YAML.
+    #[serde(flatten)]
+    pub custom: HashMap<String, serde_yaml::Value>,
 }
 
-pub fn extract_frontmatter(content: &str) -> Result<Frontmatter, FrontmatterError> {
-    let content = content.trim_start();
+/// Accept both `aliases: Name` and `aliases: [A, B]`.
+fn string_or_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+        None(()),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(s) => vec![s],
+        OneOrMany::Many(v) => v,
+        OneOrMany::None(()) => Vec::new(),
+    })
+}
 
-    if !content.starts_with("---") {
-        return Err(FrontmatterError::NoDelimiters);
+/// Frontmatter longer than this is taken to be unterminated.
+pub(crate) const MAX_FRONTMATTER_BYTES: usize = 64 * 1024;
+
+/// A `---` line, allowing trailing whitespace.
+pub(crate) fn is_delimiter(line: &str) -> bool {
+    line.trim_end() == "---"
+}
+
+/// Split `content` into its frontmatter YAML and the body after it. The
+/// first non-blank line must be a `---` delimiter, closed by another within
+/// [`MAX_FRONTMATTER_BYTES`]; the streaming scanner applies the same rules.
+fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let mut lines = content.split_inclusive('\n');
+    let mut offset = 0;
+    let opening = lines.find(|line| {
+        offset += line.len();
+        !line.trim().is_empty()
+    })?;
+    if !is_delimiter(opening) {
+        return None;
     }
 
-    let after_first = &content[3..];
-    let end_pos = after_first
-        .find("\n---")
-        .or_else(|| after_first.find("\r\n---"))
-        .ok_or(FrontmatterError::NoDelimiters)?;
+    let yaml_start = offset;
+    for line in lines {
+        let start = offset;
+        offset += line.len();
+        if is_delimiter(line) {
+            return Some((&content[yaml_start..start], &content[offset..]));
+        }
+        if offset - yaml_start > MAX_FRONTMATTER_BYTES {
+            return None;
+        }
+    }
+    None
+}
 
-    let yaml_content = &after_first[..end_pos].trim();
-    let fm: Frontmatter = serde_yaml::from_str(yaml_content)?;
+pub fn extract_frontmatter(content: &str) -> Result<Frontmatter, FrontmatterError> {
+    let (yaml, _) = split_frontmatter(content).ok_or(FrontmatterError::NoDelimiters)?;
+    let fm: Frontmatter = serde_yaml::from_str(yaml.trim())?;
 
     Ok(fm)
 }
 
+/// The note body after the frontmatter block, or the whole content when
+/// there is none.
+pub fn strip_frontmatter(content: &str) -> &str {
+    split_frontmatter(content).map_or(content, |(_, body)| body)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,6 +140,59 @@ Content
         assert!(fm.tags.is_empty());
     }
 
+    #[test]
+    fn agrees_with_the_scanner_on_delimiters() {
+        let cases = [
+            "---\ntitle: Yes\n---\nbody\n",
+            "\n---  \r\ntitle: Yes\r\n---\t\r\nbody\r\n",
+            "----\ntitle: No\n----\nbody\n",
+            "---\ntitle: No\n---more\nbody\n",
+            "---foo\ntitle: No\n---\nbody\n",
+            "# Heading\n---\ntitle: No\n---\n",
+            "---\ntitle: Never closed\n",
+        ];
+        for content in cases {
+            let scanned = crate::scan_content(content).frontmatter;
+            let extracted = extract_frontmatter(content).ok();
+            assert_eq!(
+                scanned.map(|fm| fm.title),
+                extracted.map(|fm| fm.title),
+                "{:?}",
+                content
+            );
+        }
+
+        assert_eq!(strip_frontmatter("---\ntitle: Yes\n---\nbody\n"), "body\n");
+        let unclosed = "---\ntitle: No\n---more\nbody\n";
+        assert_eq!(strip_frontmatter(unclosed), unclosed);
+        let huge = format!("---\n{}---\nbody\n", "a: b\n".repeat(MAX_FRONTMATTER_BYTES));
+        assert_eq!(strip_frontmatter(&huge), huge);
+    }
+
+    #[test]
+    fn parses_aliases_as_string_or_list() {
+        let fm = extract_frontmatter("---\naliases: [One, Two]\n---\n").unwrap();
+        assert_eq!(fm.aliases, vec!["One", "Two"]);
+
+        let fm = extract_frontmatter("---\naliases: Solo\n---\n").unwrap();
+        assert_eq!(fm.aliases, vec!["Solo"]);
+
+        let fm = extract_frontmatter("---\naliases:\n---\n").unwrap();
+        assert!(fm.aliases.is_empty());
+    }
+
+    #[test]
+    fn keeps_custom_fields() {
+        let content = "---\ntitle: Custom\nstatus: draft\nrating: 4\nauthors: [a, b]\n---\n";
+        let fm = extract_frontmatter(content).unwrap();
+
+        assert_eq!(fm.custom.len(), 3);
+        assert!(!fm.custom.contains_key("title"));
+        assert_eq!(fm.custom["status"].as_str(), Some("draft"));
+        assert_eq!(fm.custom["rating"].as_u64(), Some(4));
+        assert!(fm.custom["authors"].is_sequence());
+    }
+
     #[test]
     fn handles_inline_tags() {
         let content = r#"---
@@ -93,4 +203,16 @@ tags: [one, two, three]
         let fm = extract_frontmatter(content).unwrap();
         assert_eq!(fm.tags, vec!["one", "two", "three"]);
     }
+
+    #[test]
+    fn strips_frontmatter() {
+        assert_eq!(
+            strip_frontmatter("---\ntitle: A\n---\n\n# Body\n"),
+            "\n# Body\n"
+        );
+        assert_eq!(
+            strip_frontmatter("# No frontmatter\n"),
+            "# No frontmatter\n"
+        );
+    }
 }
@@ -0,0 +1,142 @@
+use crate::links::{GraphNode, LinkGraph, LinkType};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Render the link graph as a Graphviz `digraph`. Nodes are keyed by their
+/// vault path and labelled with the frontmatter title when present; embeds
+/// are drawn dashed.
+pub fn render_graph_dot(graph: &LinkGraph) -> String {
+    let mut out = String::from("digraph vault {\n    rankdir=LR;\n    node [shape=box];\n");
+
+    for node in &graph.nodes {
+        let _ = writeln!(
+            out,
+            "    \"{}\" [label=\"{}\"];",
+            escape_dot(&node.id),
+            escape_dot(&node_label(node))
+        );
+    }
+    for edge in &graph.edges {
+        let style = if edge.link_type == LinkType::Embed {
+            " [style=dashed]"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "    \"{}\" -> \"{}\"{};",
+            escape_dot(&edge.source),
+            escape_dot(&edge.target),
+            style
+        );
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Render the link graph as a Mermaid `graph LR` block (without the code
+/// fence). Embeds are drawn as dotted arrows.
+pub fn render_graph_mermaid(graph: &LinkGraph) -> String {
+    let mut out = String::from("graph LR\n");
+    let ids: HashMap<&str, usize> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id.as_str(), i))
+        .collect();
+
+    for (i, node) in graph.nodes.iter().enumerate() {
+        let _ = writeln!(out, "    n{}[\"{}\"]", i, escape_mermaid(&node_label(node)));
+    }
+    for edge in &graph.edges {
+        let (Some(source), Some(target)) =
+            (ids.get(edge.source.as_str()), ids.get(edge.target.as_str()))
+        else {
+            continue;
+        };
+        let arrow = if edge.link_type == LinkType::Embed {
+            "-.->"
+        } else {
+            "-->"
+        };
+        let _ = writeln!(out, "    n{} {} n{}", source, arrow, target);
+    }
+
+    out
+}
+
+fn node_label(node: &GraphNode) -> String {
+    node.title.clone().unwrap_or_else(|| {
+        node.id
+            .rsplit('/')
+            .next()
+            .unwrap_or(&node.id)
+            .trim_end_matches(".md")
+            .to_string()
+    })
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::links::GraphEdge;
+
+    fn sample_graph() -> LinkGraph {
+        LinkGraph {
+            nodes: vec![
+                GraphNode {
+                    id: "a.md".to_string(),
+                    title: Some("Say \"hi\"".to_string()),
+                    tags: vec![],
+                },
+                GraphNode {
+                    id: "dir/b.md".to_string(),
+                    title: None,
+                    tags: vec![],
+                },
+            ],
+            edges: vec![
+                GraphEdge {
+                    source: "a.md".to_string(),
+                    target: "dir/b.md".to_string(),
+                    link_type: LinkType::Wikilink,
+                },
+                GraphEdge {
+                    source: "dir/b.md".to_string(),
+                    target: "a.md".to_string(),
+                    link_type: LinkType::Embed,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_dot() {
+        let dot = render_graph_dot(&sample_graph());
+
+        assert!(dot.starts_with("digraph vault {"));
+        assert!(dot.contains("\"a.md\" [label=\"Say \\\"hi\\\"\"];"));
+        assert!(dot.contains("\"dir/b.md\" [label=\"b\"];"));
+        assert!(dot.contains("\"a.md\" -> \"dir/b.md\";"));
+        assert!(dot.contains("\"dir/b.md\" -> \"a.md\" [style=dashed];"));
+    }
+
+    #[test]
+    fn renders_mermaid() {
+        let mermaid = render_graph_mermaid(&sample_graph());
+
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("n0[\"Say #quot;hi#quot;\"]"));
+        assert!(mermaid.contains("n0 --> n1"));
+        assert!(mermaid.contains("n1 -.-> n0"));
+    }
+}
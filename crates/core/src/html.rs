@@ -0,0 +1,382 @@
+use crate::frontmatter::strip_frontmatter;
+use crate::links::scan_vault_notes;
+use crate::resolver::LinkResolver;
+use crate::tree::{generate_tree, TreeError, TreeOptions, VaultNode};
+use pulldown_cmark::html::push_html;
+use pulldown_cmark::{CowStr, Event, LinkType, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum HtmlError {
+    #[error("vault path does not exist: {0}")]
+    VaultNotFound(String),
+    #[error(transparent)]
+    Tree(#[from] TreeError),
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HtmlSummary {
+    pub pages: usize,
+    /// Links rewritten to point at another page.
+    pub links: usize,
+    /// Links to notes that don't exist, rendered as plain text.
+    pub unresolved_links: usize,
+}
+
+const STYLESHEET: &str = "\
+body { margin: 0; display: flex; font-family: system-ui, sans-serif; line-height: 1.6; }
+.sidebar { width: 16rem; flex-shrink: 0; height: 100vh; overflow-y: auto; position: sticky; top: 0;
+  padding: 1rem; box-sizing: border-box; border-right: 1px solid #ddd; font-size: 0.9rem; }
+.sidebar ul { list-style: none; padding-left: 1rem; margin: 0; }
+.sidebar > ul { padding-left: 0; }
+.sidebar summary { cursor: pointer; font-weight: 600; }
+main { max-width: 48rem; padding: 1rem 2rem; }
+a { color: #5b4bb7; }
+.unresolved { color: #999; text-decoration: underline dotted; }
+.backlinks { margin-top: 3rem; border-top: 1px solid #ddd; font-size: 0.9rem; }
+";
+
+/// Publish the vault as a static site in `out_dir`: one page per note at the
+/// same relative path, with wikilinks and markdown links pointing at other
+/// pages, a sidebar built from the vault tree, and a backlinks list. Links to
+/// missing notes are rendered as plain text. Attachments are not copied.
+pub fn export_html(vault_path: &Path, out_dir: &Path) -> Result<HtmlSummary, HtmlError> {
+    if !vault_path.exists() {
+        return Err(HtmlError::VaultNotFound(vault_path.display().to_string()));
+    }
+
+    let notes = scan_vault_notes(vault_path);
+    let resolver = LinkResolver::from_notes(&notes);
+    let tree = generate_tree(vault_path, &TreeOptions::default())?;
+    let mut sidebar = String::new();
+    write_sidebar(&tree.root.children, &mut sidebar);
+
+    let mut backlinks: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+    for (source, scan) in &notes {
+        for link in &scan.links {
+            match resolver.lookup(&link.target, Some(source)) {
+                Some(target) if target != source => {
+                    backlinks.entry(target).or_default().insert(source);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fs::create_dir_all(out_dir)?;
+    fs::write(out_dir.join("style.css"), STYLESHEET)?;
+
+    let mut summary = HtmlSummary::default();
+    for (path, scan) in &notes {
+        let Ok(content) = fs::read_to_string(vault_path.join(path)) else {
+            continue;
+        };
+        let url_path = path.replace('\\', "/");
+        let page = Page {
+            source: path,
+            root: "../".repeat(url_path.matches('/').count()),
+            resolver: &resolver,
+        };
+
+        let title = scan
+            .frontmatter
+            .as_ref()
+            .and_then(|fm| fm.title.clone())
+            .unwrap_or_else(|| note_name(&url_path).to_string());
+        let mut main = format!(
+            "<article>\n<h1>{}</h1>\n{}</article>\n",
+            escape(&title),
+            page.render(strip_frontmatter(&content), &mut summary)
+        );
+        if let Some(sources) = backlinks.get(path.as_str()) {
+            main.push_str("<section class=\"backlinks\">\n<h2>Backlinks</h2>\n<ul>\n");
+            for source in sources {
+                main.push_str(&format!(
+                    "<li><a href=\"{}{}\">{}</a></li>\n",
+                    page.root,
+                    href(&page_path(source)),
+                    escape(note_name(source))
+                ));
+            }
+            main.push_str("</ul>\n</section>\n");
+        }
+
+        let out = out_dir.join(page_path(&url_path));
+        if let Some(parent) = out.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(out, layout(&title, &page.root, &sidebar, &main))?;
+        summary.pages += 1;
+    }
+
+    if !notes.iter().any(|(path, _)| path == "index.md") {
+        let main = format!("<h1>{}</h1>\n{}\n", escape(&tree.root.name), sidebar);
+        fs::write(
+            out_dir.join("index.html"),
+            layout(&tree.root.name, "", &sidebar, &main),
+        )?;
+    }
+
+    Ok(summary)
+}
+
+/// Where a link in the rendered page should point.
+enum Target {
+    Page(String),
+    Missing,
+    /// External URLs, anchors, and attachments, left as written.
+    Unchanged,
+}
+
+struct Page<'a> {
+    /// Vault-relative path of the note being rendered.
+    source: &'a str,
+    /// `../` repeated once per folder, leading back to the site root.
+    root: String,
+    resolver: &'a LinkResolver,
+}
+
+impl Page<'_> {
+    fn render(&self, markdown: &str, summary: &mut HtmlSummary) -> String {
+        let options = Options::ENABLE_TABLES
+            | Options::ENABLE_FOOTNOTES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_WIKILINKS;
+        // What to emit for each open link or image when it closes.
+        let mut closing: Vec<Event> = Vec::new();
+
+        let events = Parser::new_ext(markdown, options).map(|event| match event {
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => match self.target(link_type, &dest_url) {
+                Target::Page(url) => {
+                    summary.links += 1;
+                    closing.push(Event::End(TagEnd::Link));
+                    Event::Start(Tag::Link {
+                        link_type,
+                        dest_url: url.into(),
+                        title,
+                        id,
+                    })
+                }
+                Target::Missing => {
+                    summary.unresolved_links += 1;
+                    closing.push(Event::InlineHtml("</span>".into()));
+                    Event::InlineHtml("<span class=\"unresolved\">".into())
+                }
+                Target::Unchanged => {
+                    closing.push(Event::End(TagEnd::Link));
+                    Event::Start(Tag::Link {
+                        link_type,
+                        dest_url,
+                        title,
+                        id,
+                    })
+                }
+            },
+            // `![[note]]` embeds become links to the embedded note's page.
+            Event::Start(Tag::Image {
+                link_type: link_type @ LinkType::WikiLink { .. },
+                dest_url,
+                title,
+                id,
+            }) => match self.target(link_type, &dest_url) {
+                Target::Page(url) => {
+                    summary.links += 1;
+                    closing.push(Event::End(TagEnd::Link));
+                    Event::Start(Tag::Link {
+                        link_type,
+                        dest_url: url.into(),
+                        title,
+                        id,
+                    })
+                }
+                Target::Missing => {
+                    summary.unresolved_links += 1;
+                    closing.push(Event::InlineHtml("</span>".into()));
+                    Event::InlineHtml("<span class=\"unresolved\">".into())
+                }
+                Target::Unchanged => {
+                    closing.push(Event::End(TagEnd::Image));
+                    Event::Start(Tag::Image {
+                        link_type,
+                        dest_url,
+                        title,
+                        id,
+                    })
+                }
+            },
+            Event::Start(image @ Tag::Image { .. }) => {
+                closing.push(Event::End(TagEnd::Image));
+                Event::Start(image)
+            }
+            Event::End(TagEnd::Link | TagEnd::Image) => {
+                closing.pop().unwrap_or(Event::End(TagEnd::Link))
+            }
+            other => other,
+        });
+
+        let mut html = String::new();
+        push_html(&mut html, events);
+        html
+    }
+
+    fn target(&self, link_type: LinkType, dest: &CowStr) -> Target {
+        let is_wikilink = matches!(link_type, LinkType::WikiLink { .. });
+        if !is_wikilink && (dest.contains("://") || dest.starts_with("mailto:")) {
+            return Target::Unchanged;
+        }
+        let note = dest
+            .split(['#', '^'])
+            .next()
+            .unwrap_or_default()
+            .replace("%20", " ");
+        if note.is_empty() {
+            return Target::Unchanged;
+        }
+        let is_attachment = Path::new(&note).extension().is_some_and(|ext| ext != "md");
+        if is_attachment {
+            return Target::Unchanged;
+        }
+        match self.resolver.lookup(&note, Some(self.source)) {
+            Some(path) => Target::Page(format!("{}{}", self.root, href(&page_path(path)))),
+            None => Target::Missing,
+        }
+    }
+}
+
+/// Nested lists of folders and note links, with hrefs relative to the site
+/// root.
+fn write_sidebar(nodes: &[VaultNode], out: &mut String) {
+    out.push_str("<ul>\n");
+    for node in nodes {
+        if node.is_dir {
+            out.push_str(&format!(
+                "<li><details open><summary>{}</summary>\n",
+                escape(&node.name)
+            ));
+            write_sidebar(&node.children, out);
+            out.push_str("</details></li>\n");
+        } else if !node.is_attachment {
+            let path = node.path.replace('\\', "/");
+            out.push_str(&format!(
+                "<li><a href=\"{}\">{}</a></li>\n",
+                href(&page_path(&path)),
+                escape(note_name(&path))
+            ));
+        }
+    }
+    out.push_str("</ul>\n");
+}
+
+fn layout(title: &str, root: &str, sidebar: &str, main: &str) -> String {
+    let sidebar = sidebar.replace("href=\"", &format!("href=\"{}", root));
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<title>{}</title>
+<link rel=\"stylesheet\" href=\"{root}style.css\">
+</head>
+<body>
+<nav class=\"sidebar\">
+<a href=\"{root}index.html\">Home</a>
+{}</nav>
+<main>
+{}</main>
+</body>
+</html>
+",
+        escape(title),
+        sidebar,
+        main,
+        root = root
+    )
+}
+
+/// `folder/note.md` -> `folder/note.html`.
+fn page_path(path: &str) -> String {
+    format!("{}.html", path.strip_suffix(".md").unwrap_or(path))
+}
+
+fn note_name(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.strip_suffix(".md").unwrap_or(name)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Percent-encode the characters that would break a relative URL.
+fn href(path: &str) -> String {
+    let mut url = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            ' ' => url.push_str("%20"),
+            '"' => url.push_str("%22"),
+            '#' => url.push_str("%23"),
+            '%' => url.push_str("%25"),
+            '?' => url.push_str("%3F"),
+            '<' => url.push_str("%3C"),
+            '>' => url.push_str("%3E"),
+            c => url.push(c),
+        }
+    }
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils::create_test_vault;
+    use tempfile::TempDir;
+
+    #[test]
+    fn exports_linked_pages() {
+        let vault = create_test_vault();
+        fs::write(
+            vault.path().join("subdir/more.md"),
+            "Up to [[note2|the second note]], [back](../note1.md), and [[Ghost]].\n\n`[[code]]`\n",
+        )
+        .unwrap();
+        let out = TempDir::new().unwrap();
+
+        let summary = export_html(vault.path(), out.path()).unwrap();
+
+        assert_eq!(summary.pages, 4);
+        assert_eq!(summary.unresolved_links, 1);
+        assert!(out.path().join("style.css").exists());
+        assert!(out.path().join("index.html").exists());
+
+        let note1 = fs::read_to_string(out.path().join("note1.html")).unwrap();
+        assert!(note1.contains("<h1>Note 1</h1>"));
+        assert!(note1.contains("<a href=\"note2.html\">note2</a>"));
+        assert!(note1.contains("<h2>Backlinks</h2>"));
+        assert!(note1.contains("<a href=\"subdir/nested.html\">nested</a>"));
+        assert!(!note1.contains("title: Note 1"));
+
+        let more = fs::read_to_string(out.path().join("subdir/more.html")).unwrap();
+        assert!(more.contains("<a href=\"../note2.html\">the second note</a>"));
+        assert!(more.contains("<a href=\"../note1.html\">back</a>"));
+        assert!(more.contains("<span class=\"unresolved\">Ghost</span>"));
+        assert!(more.contains("<code>[[code]]</code>"));
+        assert!(more.contains("href=\"../style.css\""));
+        assert!(more.contains("<a href=\"../subdir/nested.html\">nested</a>"));
+    }
+}
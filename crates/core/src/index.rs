@@ -0,0 +1,310 @@
+use crate::cache::MetadataCache;
+use crate::exclude::{ExcludeError, ExcludeSet};
+use crate::frontmatter::{extract_frontmatter, strip_frontmatter};
+use crate::tags::{extract_inline_tags, merge_tags};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tantivy::collector::{DocSetCollector, TopDocs};
+use tantivy::directory::MmapDirectory;
+use tantivy::query::{AllQuery, QueryParser};
+use tantivy::schema::{Field, Schema, Value, STORED, STRING, TEXT};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term};
+use thiserror::Error;
+
+/// Memory budget for the tantivy writer.
+const WRITER_HEAP_BYTES: usize = 50_000_000;
+
+#[derive(Debug, Error)]
+pub enum IndexError {
+    #[error("vault path does not exist: {0}")]
+    VaultNotFound(String),
+    #[error("index error: {0}")]
+    Tantivy(#[from] tantivy::TantivyError),
+    #[error("index directory error: {0}")]
+    Directory(#[from] tantivy::directory::error::OpenDirectoryError),
+    #[error("invalid query: {0}")]
+    InvalidQuery(#[from] tantivy::query::QueryParserError),
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    Exclude(#[from] ExcludeError),
+}
+
+/// What an [`VaultIndex::update`] pass changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexUpdate {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexHit {
+    /// Vault-relative path.
+    pub path: String,
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+    pub score: f32,
+    /// Body excerpt around the matched terms, with matches wrapped in `**`.
+    pub snippet: String,
+}
+
+struct Fields {
+    path: Field,
+    hash: Field,
+    title: Field,
+    tags: Field,
+    body: Field,
+}
+
+/// Persistent tantivy index over note titles, tags, and bodies.
+///
+/// [`update`](Self::update) re-indexes only notes whose content hash changed,
+/// so repeated searches over a large vault stay cheap.
+pub struct VaultIndex {
+    vault_path: PathBuf,
+    index: Index,
+    reader: IndexReader,
+    fields: Fields,
+}
+
+impl VaultIndex {
+    /// Open the index stored in `index_dir`, creating it if needed.
+    pub fn open(vault_path: &Path, index_dir: &Path) -> Result<Self, IndexError> {
+        if !vault_path.exists() {
+            return Err(IndexError::VaultNotFound(vault_path.display().to_string()));
+        }
+        std::fs::create_dir_all(index_dir)?;
+
+        let mut builder = Schema::builder();
+        let fields = Fields {
+            path: builder.add_text_field("path", STRING | STORED),
+            hash: builder.add_text_field("hash", STRING | STORED),
+            title: builder.add_text_field("title", TEXT | STORED),
+            tags: builder.add_text_field("tags", TEXT | STORED),
+            body: builder.add_text_field("body", TEXT | STORED),
+        };
+        let index = Index::open_or_create(MmapDirectory::open(index_dir)?, builder.build())?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+
+        Ok(Self {
+            vault_path: vault_path.to_path_buf(),
+            index,
+            reader,
+            fields,
+        })
+    }
+
+    /// Bring the index in line with the vault: add new notes, re-index
+    /// changed ones, and drop deleted ones.
+    pub fn update(&mut self) -> Result<IndexUpdate, IndexError> {
+        self.update_with_cache(&MetadataCache::new())
+    }
+
+    /// [`update`](Self::update), skipping the read of any note whose size and
+    /// mtime `cache` already has a hash for.
+    pub fn update_with_cache(&mut self, cache: &MetadataCache) -> Result<IndexUpdate, IndexError> {
+        let mut indexed = self.indexed_hashes()?;
+        let mut writer: IndexWriter = self.index.writer(WRITER_HEAP_BYTES)?;
+        let mut update = IndexUpdate::default();
+
+        let exclude = ExcludeSet::load(&self.vault_path, &[])?;
+        for entry in exclude.walk_markdown(&self.vault_path, &self.vault_path) {
+            let Ok(hash) = cache.hash(entry.path()) else {
+                continue;
+            };
+            let Ok(relative) = entry.path().strip_prefix(&self.vault_path) else {
+                continue;
+            };
+            let path = relative.to_string_lossy().to_string();
+
+            if indexed.get(&path) == Some(&hash) {
+                indexed.remove(&path);
+                update.unchanged += 1;
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            match indexed.remove(&path) {
+                Some(_) => {
+                    writer.delete_term(Term::from_field_text(self.fields.path, &path));
+                    update.updated += 1;
+                }
+                None => update.added += 1,
+            }
+            writer.add_document(self.note_document(&path, &hash, &content))?;
+        }
+
+        cache.prune_under(&self.vault_path);
+
+        for path in indexed.keys() {
+            writer.delete_term(Term::from_field_text(self.fields.path, path));
+            update.removed += 1;
+        }
+
+        writer.commit()?;
+        self.reader.reload()?;
+        Ok(update)
+    }
+
+    /// Rank notes against a tantivy query (`rust AND tags:async`,
+    /// `"exact phrase"`, `title:meeting`), searching titles, tags, and
+    /// bodies by default.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<IndexHit>, IndexError> {
+        let searcher = self.reader.searcher();
+        let parser = QueryParser::for_index(
+            &self.index,
+            vec![self.fields.title, self.fields.tags, self.fields.body],
+        );
+        let query = parser.parse_query(query)?;
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(limit).order_by_score())?;
+        let snippets = SnippetGenerator::create(&searcher, &*query, self.fields.body)?;
+
+        top_docs
+            .into_iter()
+            .map(|(score, address)| {
+                let doc: TantivyDocument = searcher.doc(address)?;
+                let snippet = snippets.snippet_from_doc(&doc);
+                let mut excerpt = String::new();
+                let mut last = 0;
+                for range in snippet.highlighted() {
+                    excerpt.push_str(&snippet.fragment()[last..range.start]);
+                    excerpt.push_str("**");
+                    excerpt.push_str(&snippet.fragment()[range.clone()]);
+                    excerpt.push_str("**");
+                    last = range.end;
+                }
+                excerpt.push_str(&snippet.fragment()[last..]);
+
+                Ok(IndexHit {
+                    path: first_text(&doc, self.fields.path).unwrap_or_default(),
+                    title: first_text(&doc, self.fields.title),
+                    tags: doc
+                        .get_all(self.fields.tags)
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect(),
+                    score,
+                    snippet: excerpt.split_whitespace().collect::<Vec<_>>().join(" "),
+                })
+            })
+            .collect()
+    }
+
+    /// Number of notes currently indexed.
+    pub fn len(&self) -> u64 {
+        self.reader.searcher().num_docs()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn indexed_hashes(&self) -> Result<HashMap<String, String>, IndexError> {
+        let searcher = self.reader.searcher();
+        let mut hashes = HashMap::new();
+        for address in searcher.search(&AllQuery, &DocSetCollector)? {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            if let (Some(path), Some(hash)) = (
+                first_text(&doc, self.fields.path),
+                first_text(&doc, self.fields.hash),
+            ) {
+                hashes.insert(path, hash);
+            }
+        }
+        Ok(hashes)
+    }
+
+    fn note_document(&self, path: &str, hash: &str, content: &str) -> TantivyDocument {
+        let frontmatter = extract_frontmatter(content).ok();
+        let body = strip_frontmatter(content);
+        let tags = merge_tags(
+            frontmatter.as_ref().map_or(&[][..], |fm| &fm.tags),
+            &extract_inline_tags(body),
+        );
+        let title = frontmatter.and_then(|fm| fm.title).unwrap_or_else(|| {
+            path.rsplit('/')
+                .next()
+                .unwrap_or(path)
+                .trim_end_matches(".md")
+                .to_string()
+        });
+
+        let mut document = doc!(
+            self.fields.path => path,
+            self.fields.hash => hash,
+            self.fields.title => title,
+            self.fields.body => body,
+        );
+        for tag in tags {
+            document.add_text(self.fields.tags, tag);
+        }
+        document
+    }
+}
+
+fn first_text(doc: &TantivyDocument, field: Field) -> Option<String> {
+    doc.get_first(field)
+        .and_then(|v| v.as_str().map(str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils::create_test_vault;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn indexes_and_searches_notes() {
+        let vault = create_test_vault();
+        let index_dir = TempDir::new().unwrap();
+        let mut index = VaultIndex::open(vault.path(), index_dir.path()).unwrap();
+
+        let update = index.update().unwrap();
+        assert_eq!(update.added, 3);
+        assert_eq!(index.len(), 3);
+
+        let hits = index.search("world", 10).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().any(|h| h.path == "note1.md"));
+
+        let hits = index.search("tags:mcp", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].title.as_deref(), Some("Note 2"));
+        assert!(hits[0].tags.contains(&"mcp".to_string()));
+    }
+
+    #[test]
+    fn updates_incrementally() {
+        let vault = create_test_vault();
+        let index_dir = TempDir::new().unwrap();
+        let mut index = VaultIndex::open(vault.path(), index_dir.path()).unwrap();
+        index.update().unwrap();
+
+        fs::write(vault.path().join("note2.md"), "Rewritten about tantivy\n").unwrap();
+        fs::remove_file(vault.path().join("subdir/nested.md")).unwrap();
+        fs::write(vault.path().join("new.md"), "Fresh note\n").unwrap();
+
+        let mut reopened = VaultIndex::open(vault.path(), index_dir.path()).unwrap();
+        let update = reopened.update().unwrap();
+
+        assert_eq!(
+            update,
+            IndexUpdate {
+                added: 1,
+                updated: 1,
+                removed: 1,
+                unchanged: 1,
+            }
+        );
+        assert_eq!(reopened.search("tantivy", 10).unwrap()[0].path, "note2.md");
+        assert!(reopened.search("nested", 10).unwrap().is_empty());
+    }
+}
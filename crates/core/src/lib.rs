@@ -1,19 +1,82 @@
+pub mod cache;
+pub mod canvas;
+pub mod duplicates;
+pub mod exclude;
 pub mod fingerprint;
 pub mod frontmatter;
+pub mod graph;
+#[cfg(feature = "html")]
+pub mod html;
+#[cfg(feature = "index")]
+pub mod index;
 pub mod links;
+pub mod note;
+pub mod query;
+pub mod rename;
+pub mod resolver;
+pub mod scan;
 pub mod search;
+pub mod secrets;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod stats;
+pub mod tags;
 #[cfg(test)]
 mod testutils;
+pub mod timeline;
 pub mod tree;
 pub mod utils;
+#[cfg(feature = "watch")]
+pub mod watch;
 
+pub use cache::{CacheError, MetadataCache};
+pub use canvas::{
+    parse_canvas, scan_canvas, Canvas, CanvasEdge, CanvasError, CanvasNode, CanvasRefs,
+};
+pub use duplicates::{
+    find_duplicate_notes, DuplicateError, DuplicateGroup, DuplicateReport, SimilarNotes,
+    NEAR_DUPLICATE_THRESHOLD,
+};
+pub use exclude::{ExcludeError, ExcludeSet, VAULTIGNORE};
 pub use fingerprint::{hash_content, hash_file};
-pub use frontmatter::{extract_frontmatter, Frontmatter};
-pub use links::{extract_links, normalize_link_target, Link, LinkIndex, LinkType};
-pub use search::{search_vault, SearchMatch, SearchOptions, SearchResult};
-pub use tree::{generate_tree, render_tree, TreeOptions, VaultNode, VaultTree};
+pub use frontmatter::{extract_frontmatter, strip_frontmatter, Frontmatter};
+pub use graph::{render_graph_dot, render_graph_mermaid};
+#[cfg(feature = "html")]
+pub use html::{export_html, HtmlError, HtmlSummary};
+#[cfg(feature = "index")]
+pub use index::{IndexError, IndexHit, IndexUpdate, VaultIndex};
+pub use links::{
+    backlinks_for, extract_links, find_ambiguous_links, find_broken_links, generate_link_graph,
+    normalize_link_target, AmbiguousLink, Backlink, BrokenLink, GraphEdge, GraphNode, Link,
+    LinkError, LinkGraph, LinkIndex, LinkNormalization, LinkType,
+};
+pub use note::{create_note, note_file_name, NoteError};
+pub use query::{query_vault, QueryError, QueryMatch};
+pub use rename::{rename_note, FileChange, RenameError, RenameReport};
+pub use resolver::{LinkResolver, Resolution};
+pub use scan::{scan_content, scan_note, NoteScan};
+pub use search::{
+    search_contents, search_vault, search_vault_with_cache, SearchMatch, SearchOptions,
+    SearchResult,
+};
+pub use secrets::{scan_secrets, SecretFinding};
+#[cfg(feature = "sqlite")]
+pub use sqlite::{export_sqlite, ExportError, ExportSummary};
+pub use stats::{
+    analyze_notes, analyze_vault, vault_stats, DirectoryCount, MonthCount, NoteSize, StatsError,
+    TagCount, VaultAnalysis, VaultStats,
+};
+pub use tags::{extract_inline_tags, merge_tags};
+pub use timeline::{generate_timeline, generate_timeline_with_cache};
+pub use tree::{
+    generate_tree, generate_tree_with_cache, render_tree, render_tree_as, render_tree_as_streaming,
+    render_tree_markdown, render_tree_markdown_streaming, render_tree_streaming, TreeFormat,
+    TreeOptions, VaultNode, VaultTree,
+};
 pub use utils::{
-    compare_dir_entries, compare_tree_entries, count_totals, format_file_annotation, is_excluded,
-    is_markdown_file, node_annotation, render_tree_ascii, sum_child_notes, walk_markdown_files,
-    TreeRenderable,
+    compare_dir_entries, compare_tree_entries, count_totals, for_each_line, format_file_annotation,
+    format_size, is_excluded, is_markdown_file, node_annotation, parse_date, render_tree_ascii,
+    sum_child_notes, walk_markdown_files, write_tree_ascii, TreeRenderable,
 };
+#[cfg(feature = "watch")]
+pub use watch::{watch_vault, ChangeKind, VaultChange, VaultSnapshot, VaultWatcher, WatchError};
@@ -1,25 +1,68 @@
+use crate::canvas::scan_vault_canvases;
+use crate::exclude::ExcludeSet;
+use crate::resolver::{LinkResolver, Resolution};
+use crate::scan::{scan_note, NoteScan};
+use crate::utils::for_each_line;
+use rayon::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
 
-static WIKILINK_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\[\[([^\]|#]+)(?:#[^\]|]*)?(?:\|[^\]]+)?\]\]").unwrap());
+pub(crate) static WIKILINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?P<embed>!)?\[\[(?P<target>[^\]|#^]+)(?:#\^(?P<block>[^\]|]*)|#(?P<heading>[^\]|]*)|\^(?P<block_alt>[^\]|]*))?(?:\|(?P<alias>[^\]]+))?\]\]",
+    )
+    .unwrap()
+});
 
-static MDLINK_RE: LazyLock<Regex> =
+pub(crate) static MDLINK_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(([^)]+)\)").unwrap());
 
+#[derive(Debug, Error)]
+pub enum LinkError {
+    #[error("vault path does not exist: {0}")]
+    VaultNotFound(String),
+    #[error("note not found: {0}")]
+    NoteNotFound(String),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Link {
     pub target: String,
     pub link_type: LinkType,
     pub display_text: Option<String>,
+    /// Heading or block id after `#`/`^`, without the marker.
+    #[serde(default)]
+    pub subpath: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LinkType {
     Wikilink,
     Markdown,
+    /// `![[note]]`
+    Embed,
+    /// `[[note#Heading]]`
+    Heading,
+    /// `[[note#^block]]` or `[[note^block]]`
+    Block,
+    /// A note placed on or connected within an Obsidian `.canvas`.
+    Canvas,
+}
+
+impl LinkType {
+    /// Whether the link uses `[[...]]` syntax rather than a markdown link.
+    pub fn is_wikilink(self) -> bool {
+        matches!(
+            self,
+            LinkType::Wikilink | LinkType::Embed | LinkType::Heading | LinkType::Block
+        )
+    }
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -54,18 +97,345 @@ impl LinkIndex {
     }
 }
 
+/// A note in the link graph, identified by its vault-relative path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    pub id: String,
+    pub title: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A resolved link between two notes in the graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub link_type: LinkType,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinkGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Build the full note graph for a vault. Links that don't resolve to an
+/// existing note are left out. Canvases appear as nodes linking to the notes
+/// placed on them, and canvas edges between two notes become note edges.
+pub fn generate_link_graph(vault_path: &Path) -> Result<LinkGraph, LinkError> {
+    if !vault_path.exists() {
+        return Err(LinkError::VaultNotFound(vault_path.display().to_string()));
+    }
+
+    let notes = scan_vault_notes(vault_path);
+    let resolver = LinkResolver::from_notes(&notes);
+
+    let exclude = ExcludeSet::load(vault_path, &[]).unwrap_or_default();
+    let canvases = scan_vault_canvases(vault_path, &exclude);
+
+    let LinkGraph {
+        mut nodes,
+        mut edges,
+    } = note_graph(&notes, &resolver);
+    nodes.extend(canvases.iter().map(|(id, _)| GraphNode {
+        id: id.clone(),
+        title: None,
+        tags: Vec::new(),
+    }));
+
+    let canvas_edge = |source: &str, target: &str| {
+        Some(GraphEdge {
+            source: source.to_string(),
+            target: resolver.lookup(target, Some(source))?.to_string(),
+            link_type: LinkType::Canvas,
+        })
+    };
+    for (id, refs) in &canvases {
+        edges.extend(refs.files.iter().filter_map(|file| canvas_edge(id, file)));
+        edges.extend(
+            refs.connections
+                .iter()
+                .filter_map(|(from, to)| canvas_edge(resolver.lookup(from, Some(id))?, to)),
+        );
+    }
+
+    Ok(LinkGraph { nodes, edges })
+}
+
+impl LinkGraph {
+    /// Build the graph from notes already scanned, given as vault-relative
+    /// paths, e.g. with [`scan_content`](crate::scan_content).
+    pub fn from_notes(notes: &[(String, NoteScan)]) -> Self {
+        note_graph(notes, &LinkResolver::from_notes(notes))
+    }
+
+    /// Incoming and outgoing edges per node id.
+    pub fn link_index(&self) -> LinkIndex {
+        let mut index = LinkIndex::new();
+        for edge in &self.edges {
+            index.add_link(&edge.source, &edge.target);
+        }
+        index
+    }
+}
+
+fn note_graph(notes: &[(String, NoteScan)], resolver: &LinkResolver) -> LinkGraph {
+    let nodes = notes
+        .iter()
+        .map(|(id, scan)| GraphNode {
+            id: id.clone(),
+            title: scan.frontmatter.as_ref().and_then(|fm| fm.title.clone()),
+            tags: scan.tags(),
+        })
+        .collect();
+
+    let edges = notes
+        .iter()
+        .flat_map(|(id, scan)| {
+            scan.links.iter().filter_map(move |link| {
+                Some(GraphEdge {
+                    source: id.clone(),
+                    target: resolver.lookup(&link.target, Some(id))?.to_string(),
+                    link_type: link.link_type,
+                })
+            })
+        })
+        .collect();
+
+    LinkGraph { nodes, edges }
+}
+
+/// A wikilink whose target note doesn't exist in the vault.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokenLink {
+    pub source: String,
+    pub target: String,
+    pub line_number: usize,
+    pub line_content: String,
+}
+
+/// List every wikilink whose target note doesn't exist, with its source file
+/// and line. Links to non-markdown attachments (`[[diagram.png]]`) are skipped.
+pub fn find_broken_links(vault_path: &Path) -> Result<Vec<BrokenLink>, LinkError> {
+    if !vault_path.exists() {
+        return Err(LinkError::VaultNotFound(vault_path.display().to_string()));
+    }
+
+    let notes = scan_vault_notes(vault_path);
+    let resolver = LinkResolver::from_notes(&notes);
+
+    let mut broken: Vec<BrokenLink> = notes
+        .par_iter()
+        .filter(|(_, scan)| scan.links.iter().any(|l| is_broken_wikilink(l, &resolver)))
+        .flat_map_iter(|(source, _)| {
+            let mut found = Vec::new();
+            let _ = for_each_line(&vault_path.join(source), |line_number, line| {
+                for link in extract_links(line) {
+                    if is_broken_wikilink(&link, &resolver) {
+                        found.push(BrokenLink {
+                            source: source.clone(),
+                            target: link.target,
+                            line_number,
+                            line_content: line.to_string(),
+                        });
+                    }
+                }
+                ControlFlow::Continue(())
+            });
+            found
+        })
+        .collect();
+
+    broken.sort_by(|a, b| {
+        a.source
+            .cmp(&b.source)
+            .then(a.line_number.cmp(&b.line_number))
+    });
+    Ok(broken)
+}
+
+/// A link whose target matches several notes equally well, such as
+/// `[[todo]]` when both `work/todo.md` and `home/todo.md` exist.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmbiguousLink {
+    pub source: String,
+    pub target: String,
+    pub line_number: usize,
+    pub line_content: String,
+    /// Every matching note, sorted by path.
+    pub candidates: Vec<String>,
+    /// The candidate Obsidian opens.
+    pub resolved: String,
+}
+
+/// List every link that matches more than one note by name or partial path,
+/// with the note it currently resolves to. Qualify these links with enough
+/// of the path (`[[work/todo]]`) to make them unique.
+pub fn find_ambiguous_links(vault_path: &Path) -> Result<Vec<AmbiguousLink>, LinkError> {
+    if !vault_path.exists() {
+        return Err(LinkError::VaultNotFound(vault_path.display().to_string()));
+    }
+
+    let notes = scan_vault_notes(vault_path);
+    let resolver = LinkResolver::from_notes(&notes);
+    let is_ambiguous = |link: &Link, source: &str| {
+        matches!(
+            resolver.resolve(&link.target, Some(source)),
+            Resolution::Ambiguous(_)
+        )
+    };
+
+    let mut ambiguous: Vec<AmbiguousLink> = notes
+        .par_iter()
+        .filter(|(source, scan)| scan.links.iter().any(|l| is_ambiguous(l, source)))
+        .flat_map_iter(|(source, _)| {
+            let mut found = Vec::new();
+            let _ = for_each_line(&vault_path.join(source), |line_number, line| {
+                for link in extract_links(line) {
+                    let Resolution::Ambiguous(candidates) =
+                        resolver.resolve(&link.target, Some(source))
+                    else {
+                        continue;
+                    };
+                    found.push(AmbiguousLink {
+                        source: source.clone(),
+                        resolved: resolver
+                            .lookup(&link.target, Some(source))
+                            .unwrap_or_default()
+                            .to_string(),
+                        target: link.target,
+                        line_number,
+                        line_content: line.to_string(),
+                        candidates: candidates.into_iter().map(str::to_string).collect(),
+                    });
+                }
+                ControlFlow::Continue(())
+            });
+            found
+        })
+        .collect();
+
+    ambiguous.sort_by(|a, b| {
+        a.source
+            .cmp(&b.source)
+            .then(a.line_number.cmp(&b.line_number))
+    });
+    Ok(ambiguous)
+}
+
+/// A note linking to another, with the line containing the link.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backlink {
+    pub source: String,
+    pub line_number: usize,
+    pub line_content: String,
+}
+
+/// List every line in the vault that links to `note`, given either as a
+/// vault-relative path or as a link target (`My Note`).
+pub fn backlinks_for(vault_path: &Path, note: &str) -> Result<Vec<Backlink>, LinkError> {
+    if !vault_path.exists() {
+        return Err(LinkError::VaultNotFound(vault_path.display().to_string()));
+    }
+
+    let notes = scan_vault_notes(vault_path);
+    let resolver = LinkResolver::from_notes(&notes);
+    let target = resolver
+        .resolve_note(note)
+        .ok_or_else(|| LinkError::NoteNotFound(note.to_string()))?;
+
+    let mut index = LinkIndex::new();
+    for (source, scan) in &notes {
+        for link in &scan.links {
+            if let Some(resolved) = resolver.lookup(&link.target, Some(source)) {
+                index.add_link(source, resolved);
+            }
+        }
+    }
+
+    let mut sources = index.incoming.remove(target).unwrap_or_default();
+    sources.dedup();
+
+    let mut backlinks: Vec<Backlink> = sources
+        .par_iter()
+        .flat_map_iter(|source| {
+            let mut found = Vec::new();
+            let _ = for_each_line(&vault_path.join(source), |line_number, line| {
+                let links_here = extract_links(line)
+                    .iter()
+                    .any(|l| resolver.lookup(&l.target, Some(source)) == Some(target));
+                if links_here {
+                    found.push(Backlink {
+                        source: source.clone(),
+                        line_number,
+                        line_content: line.to_string(),
+                    });
+                }
+                ControlFlow::Continue(())
+            });
+            found
+        })
+        .collect();
+
+    backlinks.sort_by(|a, b| {
+        a.source
+            .cmp(&b.source)
+            .then(a.line_number.cmp(&b.line_number))
+    });
+    Ok(backlinks)
+}
+
+fn is_broken_wikilink(link: &Link, resolver: &LinkResolver) -> bool {
+    let is_attachment = Path::new(link.target.trim())
+        .extension()
+        .is_some_and(|ext| ext != "md");
+    link.link_type.is_wikilink() && !is_attachment && resolver.lookup(&link.target, None).is_none()
+}
+
+/// Scan every note in the vault, keyed by vault-relative path and sorted.
+/// Honours `.vaultignore`; an unreadable or invalid ignore file is skipped.
+pub(crate) fn scan_vault_notes(vault_path: &Path) -> Vec<(String, NoteScan)> {
+    let exclude = ExcludeSet::load(vault_path, &[]).unwrap_or_default();
+    let files: Vec<PathBuf> = exclude
+        .walk_markdown(vault_path, vault_path)
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    let mut notes: Vec<(String, NoteScan)> = files
+        .par_iter()
+        .filter_map(|path| {
+            let relative = path.strip_prefix(vault_path).ok()?;
+            Some((
+                relative.to_string_lossy().to_string(),
+                scan_note(path).ok()?,
+            ))
+        })
+        .collect();
+    notes.sort_by(|a, b| a.0.cmp(&b.0));
+    notes
+}
+
 pub fn extract_links(content: &str) -> Vec<Link> {
     let mut links = Vec::new();
 
     for cap in WIKILINK_RE.captures_iter(content) {
-        let target = cap
-            .get(1)
-            .map(|m| m.as_str().to_string())
-            .unwrap_or_default();
+        let block = cap.name("block").or_else(|| cap.name("block_alt"));
+        let heading = cap.name("heading");
+        let link_type = if cap.name("embed").is_some() {
+            LinkType::Embed
+        } else if block.is_some() {
+            LinkType::Block
+        } else if heading.is_some() {
+            LinkType::Heading
+        } else {
+            LinkType::Wikilink
+        };
+
         links.push(Link {
-            target,
-            link_type: LinkType::Wikilink,
-            display_text: None,
+            target: cap["target"].to_string(),
+            link_type,
+            display_text: cap.name("alias").map(|m| m.as_str().to_string()),
+            subpath: block.or(heading).map(|m| m.as_str().to_string()),
         });
     }
 
@@ -81,6 +451,7 @@ pub fn extract_links(content: &str) -> Vec<Link> {
                 target,
                 link_type: LinkType::Markdown,
                 display_text: display,
+                subpath: None,
             });
         }
     }
@@ -88,16 +459,70 @@ pub fn extract_links(content: &str) -> Vec<Link> {
     links
 }
 
+/// How link targets and note names are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LinkNormalization {
+    /// Compose to Unicode NFC, so decomposed (NFD) file names, common on
+    /// macOS, match links typed in composed form.
+    pub unicode: bool,
+    /// Ignore case, as Obsidian does.
+    pub case_insensitive: bool,
+}
+
+impl Default for LinkNormalization {
+    fn default() -> Self {
+        Self {
+            unicode: true,
+            case_insensitive: true,
+        }
+    }
+}
+
+impl LinkNormalization {
+    pub fn normalize(&self, target: &str) -> String {
+        let target = target.trim();
+        let target = target.strip_suffix(".md").unwrap_or(target);
+        let target: String = if self.unicode {
+            target.nfc().collect()
+        } else {
+            target.to_string()
+        };
+        let target = if self.case_insensitive {
+            target.to_lowercase()
+        } else {
+            target
+        };
+        target.replace(' ', "-")
+    }
+}
+
+/// Normalize a link target with the default (NFC, case-insensitive) rules.
 pub fn normalize_link_target(target: &str) -> String {
-    let target = target.trim();
-    let target = target.strip_suffix(".md").unwrap_or(target);
-    target.to_lowercase().replace(' ', "-")
+    LinkNormalization::default().normalize(target)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn normalizes_unicode_and_case() {
+        let composed = "Caf\u{e9}";
+        let decomposed = "Cafe\u{301}";
+        assert_eq!(
+            normalize_link_target(composed),
+            normalize_link_target(decomposed)
+        );
+
+        let strict = LinkNormalization {
+            unicode: false,
+            case_insensitive: false,
+        };
+        assert_ne!(strict.normalize(composed), strict.normalize(decomposed));
+        assert_eq!(strict.normalize("My Note.md"), "My-Note");
+    }
+
     #[test]
     fn extracts_wikilinks() {
         let content = "Check out [[My Note]] and [[Another Note|alias]] for more.";
@@ -116,6 +541,23 @@ mod tests {
 
         assert_eq!(links.len(), 1);
         assert_eq!(links[0].target, "Note");
+        assert_eq!(links[0].link_type, LinkType::Heading);
+        assert_eq!(links[0].subpath.as_deref(), Some("Section"));
+    }
+
+    #[test]
+    fn extracts_embeds_and_block_refs() {
+        let content = "![[Diagram]] see [[Note#^abc123]] and [[Other^def|alias]]";
+        let links = extract_links(content);
+
+        assert_eq!(links.len(), 3);
+        assert_eq!(links[0].link_type, LinkType::Embed);
+        assert_eq!(links[0].target, "Diagram");
+        assert_eq!(links[1].link_type, LinkType::Block);
+        assert_eq!(links[1].subpath.as_deref(), Some("abc123"));
+        assert_eq!(links[2].link_type, LinkType::Block);
+        assert_eq!(links[2].target, "Other");
+        assert_eq!(links[2].display_text.as_deref(), Some("alias"));
     }
 
     #[test]
@@ -141,6 +583,149 @@ mod tests {
         assert_eq!(index.incoming_count("note-a"), 0);
     }
 
+    #[test]
+    fn generates_link_graph() {
+        let vault = crate::testutils::create_test_vault();
+        let graph = generate_link_graph(vault.path()).unwrap();
+
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.edges.len(), 2);
+        assert!(graph
+            .edges
+            .iter()
+            .any(|e| e.source == "note1.md" && e.target == "note2.md"));
+        assert!(graph
+            .edges
+            .iter()
+            .any(|e| e.source == "subdir/nested.md" && e.target == "note1.md"));
+    }
+
+    #[test]
+    fn builds_graph_from_scanned_notes() {
+        let notes: Vec<(String, NoteScan)> = [
+            ("a.md", "---\ntitle: A\n---\n[[b]] [[work/c]] [[ghost]]"),
+            ("b.md", "[[a]]"),
+            ("work/c.md", "#todo [[b|back]]"),
+        ]
+        .into_iter()
+        .map(|(path, content)| (path.to_string(), crate::scan_content(content)))
+        .collect();
+
+        let graph = LinkGraph::from_notes(&notes);
+        let index = graph.link_index();
+
+        assert_eq!(graph.nodes[0].title.as_deref(), Some("A"));
+        assert_eq!(graph.nodes[2].tags, vec!["todo"]);
+        assert_eq!(graph.edges.len(), 4);
+        assert_eq!(index.outgoing_count("a.md"), 2);
+        assert_eq!(index.incoming_count("b.md"), 2);
+    }
+
+    #[test]
+    fn includes_canvas_edges_in_graph() {
+        let vault = crate::testutils::create_test_vault();
+        std::fs::write(
+            vault.path().join("board.canvas"),
+            r#"{"nodes": [
+                {"id": "1", "type": "file", "file": "note2.md"},
+                {"id": "2", "type": "file", "file": "subdir/nested.md"}
+            ], "edges": [{"id": "e", "fromNode": "2", "toNode": "1"}]}"#,
+        )
+        .unwrap();
+
+        let graph = generate_link_graph(vault.path()).unwrap();
+
+        assert!(graph.nodes.iter().any(|n| n.id == "board.canvas"));
+        let canvas_edges: Vec<(&str, &str)> = graph
+            .edges
+            .iter()
+            .filter(|e| e.link_type == LinkType::Canvas)
+            .map(|e| (e.source.as_str(), e.target.as_str()))
+            .collect();
+        assert_eq!(
+            canvas_edges,
+            vec![
+                ("board.canvas", "note2.md"),
+                ("board.canvas", "subdir/nested.md"),
+                ("subdir/nested.md", "note2.md"),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_broken_links() {
+        let vault = crate::testutils::create_test_vault();
+        std::fs::write(
+            vault.path().join("broken.md"),
+            "Fine: [[note1]]\nMissing: [[Ghost Note]] and ![[image.png]]\n",
+        )
+        .unwrap();
+
+        let broken = find_broken_links(vault.path()).unwrap();
+
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].source, "broken.md");
+        assert_eq!(broken[0].target, "Ghost Note");
+        assert_eq!(broken[0].line_number, 2);
+    }
+
+    #[test]
+    fn finds_ambiguous_links() {
+        let vault = crate::testutils::create_test_vault();
+        let dir = vault.path();
+        for folder in ["work", "home"] {
+            std::fs::create_dir(dir.join(folder)).unwrap();
+            std::fs::write(dir.join(folder).join("todo.md"), "Tasks\n").unwrap();
+        }
+        std::fs::write(
+            dir.join("home/plan.md"),
+            "Vague [[todo]]\nExact [[work/todo]]\n",
+        )
+        .unwrap();
+
+        let ambiguous = find_ambiguous_links(dir).unwrap();
+
+        assert_eq!(ambiguous.len(), 1);
+        assert_eq!(ambiguous[0].source, "home/plan.md");
+        assert_eq!(ambiguous[0].line_number, 1);
+        assert_eq!(
+            ambiguous[0].candidates,
+            vec!["home/todo.md", "work/todo.md"]
+        );
+        assert_eq!(ambiguous[0].resolved, "home/todo.md");
+
+        let backlinks = backlinks_for(dir, "work/todo.md").unwrap();
+        assert_eq!(backlinks.len(), 1);
+        assert_eq!(backlinks[0].line_number, 2);
+    }
+
+    #[test]
+    fn finds_backlinks_with_context() {
+        let vault = crate::testutils::create_test_vault();
+        std::fs::write(
+            vault.path().join("extra.md"),
+            "First [[Note1]]\nunrelated\nAgain [[note1|alias]]\n",
+        )
+        .unwrap();
+
+        let backlinks = backlinks_for(vault.path(), "note1").unwrap();
+
+        assert_eq!(backlinks.len(), 3);
+        assert_eq!(backlinks[0].source, "extra.md");
+        assert_eq!(backlinks[0].line_number, 1);
+        assert_eq!(backlinks[1].line_number, 3);
+        assert_eq!(backlinks[2].source, "subdir/nested.md");
+        assert_eq!(backlinks[2].line_content, "Links to [[note1]]");
+
+        let by_path = backlinks_for(vault.path(), "note2.md").unwrap();
+        assert_eq!(by_path.len(), 1);
+
+        assert!(matches!(
+            backlinks_for(vault.path(), "ghost"),
+            Err(LinkError::NoteNotFound(_))
+        ));
+    }
+
     #[test]
     fn normalizes_link_targets() {
         assert_eq!(normalize_link_target("My Note.md"), "my-note");
@@ -0,0 +1,116 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Component, Path};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum NoteError {
+    #[error("vault path does not exist: {0}")]
+    VaultNotFound(String),
+    #[error("path must stay inside the vault: {0}")]
+    OutsideVault(String),
+    #[error("note already exists: {0}")]
+    AlreadyExists(String),
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// `title` with the characters Obsidian doesn't allow in note names
+/// (`\ / : * ? " < > | # ^ [ ]`) replaced by spaces.
+pub fn note_file_name(title: &str) -> String {
+    let cleaned: String = title
+        .chars()
+        .map(|c| match c {
+            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let name = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    name.trim_matches('.').trim().to_string()
+}
+
+/// Write a new note at `path` (vault-relative, `.md` optional), creating
+/// directories as needed. Never overwrites an existing file. Returns the
+/// vault-relative path written.
+pub fn create_note(vault_path: &Path, path: &str, content: &str) -> Result<String, NoteError> {
+    if !vault_path.is_dir() {
+        return Err(NoteError::VaultNotFound(vault_path.display().to_string()));
+    }
+
+    let path = if path.ends_with(".md") {
+        path.to_string()
+    } else {
+        format!("{}.md", path)
+    };
+    let inside = Path::new(&path)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)));
+    if !inside {
+        return Err(NoteError::OutsideVault(path));
+    }
+
+    let destination = vault_path.join(&path);
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&destination)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => NoteError::AlreadyExists(path.clone()),
+            _ => NoteError::IoError(e),
+        })?;
+    file.write_all(content.as_bytes())?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils::create_test_vault;
+
+    #[test]
+    fn creates_notes_without_overwriting() {
+        let vault = create_test_vault();
+
+        let path = create_note(vault.path(), "Knowledge/Rust", "# Rust\n").unwrap();
+        assert_eq!(path, "Knowledge/Rust.md");
+        assert_eq!(
+            fs::read_to_string(vault.path().join("Knowledge/Rust.md")).unwrap(),
+            "# Rust\n"
+        );
+
+        assert!(matches!(
+            create_note(vault.path(), "note1.md", "replaced"),
+            Err(NoteError::AlreadyExists(_))
+        ));
+        assert!(fs::read_to_string(vault.path().join("note1.md"))
+            .unwrap()
+            .contains("Hello World"));
+    }
+
+    #[test]
+    fn refuses_paths_outside_the_vault() {
+        let vault = create_test_vault();
+        for path in ["../escape", "/tmp/escape", "subdir/../../escape"] {
+            assert!(
+                matches!(
+                    create_note(vault.path(), path, ""),
+                    Err(NoteError::OutsideVault(_))
+                ),
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn cleans_note_names() {
+        assert_eq!(note_file_name("C/C++: a history"), "C C++ a history");
+        assert_eq!(note_file_name("What is [[this]]?"), "What is this");
+        assert_eq!(note_file_name("..hidden"), "hidden");
+    }
+}
@@ -0,0 +1,564 @@
+use crate::links::scan_vault_notes;
+use crate::resolver::LinkResolver;
+use crate::scan::NoteScan;
+use crate::tree::tag_matches;
+use crate::utils::parse_date;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum QueryError {
+    #[error("vault path does not exist: {0}")]
+    VaultNotFound(String),
+    #[error("invalid query: {0}")]
+    Parse(String),
+}
+
+/// A note selected by [`query_vault`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryMatch {
+    /// Vault-relative path.
+    pub path: String,
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+    pub date: Option<String>,
+    /// Last modified time, as seconds since the Unix epoch.
+    pub modified: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Predicate {
+    Tag(String),
+    LinksTo(String),
+    LinkedFrom(String),
+    Modified(Op, String),
+    Date(Op, String),
+    Words(Op, usize),
+    Title(String),
+    Path(String),
+    Property(String, Op, String),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Term(Predicate),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// Select notes with a Dataview-style filter such as
+/// `tag:#rust AND links-to:note2 AND modified:>2024-01-01`.
+///
+/// Terms are `field:value`; `AND` (or juxtaposition), `OR`, `NOT` / `-`, and
+/// parentheses combine them. Fields:
+///
+/// - `tag:` frontmatter or inline tag; nested tags match their parent
+/// - `links-to:` / `linked-from:` a note name or vault-relative path
+/// - `modified:` file mtime and `date:` frontmatter date, `YYYY-MM-DD`
+/// - `words:` body word count
+/// - `title:` / `path:` case-insensitive substring
+/// - any other field compares a frontmatter property, e.g. `status:draft`
+///
+/// Date and number fields accept `>`, `>=`, `<`, and `<=` prefixes. A bare
+/// word matches the title or path. Quote values containing spaces.
+pub fn query_vault(vault_path: &Path, query: &str) -> Result<Vec<QueryMatch>, QueryError> {
+    if !vault_path.exists() {
+        return Err(QueryError::VaultNotFound(vault_path.display().to_string()));
+    }
+    let expr = parse_query(query)?;
+
+    let notes = scan_vault_notes(vault_path);
+    let resolver = LinkResolver::from_notes(&notes);
+    let outgoing: HashMap<&str, HashSet<&str>> = notes
+        .iter()
+        .map(|(path, scan)| {
+            let resolved = scan
+                .links
+                .iter()
+                .filter_map(|link| resolver.lookup(&link.target, Some(path)))
+                .collect();
+            (path.as_str(), resolved)
+        })
+        .collect();
+
+    let ctx = QueryContext {
+        resolver: &resolver,
+        outgoing: &outgoing,
+    };
+
+    Ok(notes
+        .iter()
+        .filter_map(|(path, scan)| {
+            let modified = std::fs::metadata(vault_path.join(path))
+                .and_then(|m| m.modified())
+                .ok();
+            let note = NoteView {
+                path,
+                scan,
+                modified,
+            };
+            ctx.eval(&expr, &note).then(|| note.to_match())
+        })
+        .collect())
+}
+
+struct QueryContext<'a> {
+    resolver: &'a LinkResolver,
+    outgoing: &'a HashMap<&'a str, HashSet<&'a str>>,
+}
+
+struct NoteView<'a> {
+    path: &'a str,
+    scan: &'a NoteScan,
+    modified: Option<SystemTime>,
+}
+
+impl NoteView<'_> {
+    fn title(&self) -> Option<&str> {
+        self.scan.frontmatter.as_ref()?.title.as_deref()
+    }
+
+    fn date(&self) -> Option<&str> {
+        let date = self.scan.frontmatter.as_ref()?.date.as_deref()?;
+        Some(date.get(..10).unwrap_or(date))
+    }
+
+    fn to_match(&self) -> QueryMatch {
+        QueryMatch {
+            path: self.path.to_string(),
+            title: self.title().map(str::to_string),
+            tags: self.scan.tags(),
+            date: self.date().map(str::to_string),
+            modified: self
+                .modified
+                .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs()),
+        }
+    }
+}
+
+impl QueryContext<'_> {
+    fn eval(&self, expr: &Expr, note: &NoteView) -> bool {
+        match expr {
+            Expr::Term(predicate) => self.matches(predicate, note),
+            Expr::Not(inner) => !self.eval(inner, note),
+            Expr::And(a, b) => self.eval(a, note) && self.eval(b, note),
+            Expr::Or(a, b) => self.eval(a, note) || self.eval(b, note),
+        }
+    }
+
+    fn matches(&self, predicate: &Predicate, note: &NoteView) -> bool {
+        match predicate {
+            Predicate::Tag(wanted) => note.scan.tags().iter().any(|t| tag_matches(t, wanted)),
+            Predicate::LinksTo(target) => self
+                .resolve(target)
+                .is_some_and(|target| self.outgoing[note.path].contains(target)),
+            Predicate::LinkedFrom(source) => self
+                .resolve(source)
+                .is_some_and(|source| self.outgoing[source].contains(note.path)),
+            Predicate::Modified(op, date) => note
+                .modified
+                .zip(parse_date(date))
+                .is_some_and(|(modified, day)| compare_day(modified, day, *op)),
+            Predicate::Date(op, date) => note
+                .date()
+                .is_some_and(|d| compare(d.cmp(date.as_str()), *op)),
+            Predicate::Words(op, count) => compare(note.scan.word_count.cmp(count), *op),
+            Predicate::Title(text) => contains(note.title().unwrap_or_default(), text),
+            Predicate::Path(text) => contains(note.path, text),
+            Predicate::Property(key, op, value) => note
+                .scan
+                .frontmatter
+                .as_ref()
+                .and_then(|fm| fm.custom.get(key))
+                .is_some_and(|property| property_matches(property, *op, value)),
+            Predicate::Text(text) => {
+                contains(note.title().unwrap_or_default(), text) || contains(note.path, text)
+            }
+        }
+    }
+
+    fn resolve(&self, note: &str) -> Option<&str> {
+        self.resolver.resolve_note(note)
+    }
+}
+
+fn compare(ordering: std::cmp::Ordering, op: Op) -> bool {
+    use std::cmp::Ordering::*;
+    match op {
+        Op::Eq => ordering == Equal,
+        Op::Gt => ordering == Greater,
+        Op::Ge => ordering != Less,
+        Op::Lt => ordering == Less,
+        Op::Le => ordering != Greater,
+    }
+}
+
+/// Compare a timestamp with the UTC day starting at `day`.
+fn compare_day(time: SystemTime, day: SystemTime, op: Op) -> bool {
+    let next_day = day + Duration::from_secs(86_400);
+    match op {
+        Op::Eq => time >= day && time < next_day,
+        Op::Gt => time >= next_day,
+        Op::Ge => time >= day,
+        Op::Lt => time < day,
+        Op::Le => time < next_day,
+    }
+}
+
+fn contains(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+fn property_matches(property: &serde_yaml::Value, op: Op, value: &str) -> bool {
+    use serde_yaml::Value;
+    match property {
+        Value::Sequence(items) => items.iter().any(|v| property_matches(v, op, value)),
+        Value::String(s) => match op {
+            Op::Eq => s.eq_ignore_ascii_case(value),
+            _ => compare(s.as_str().cmp(value), op),
+        },
+        Value::Number(n) => match (n.as_f64(), value.parse::<f64>()) {
+            (Some(n), Ok(v)) => n.partial_cmp(&v).is_some_and(|o| compare(o, op)),
+            _ => false,
+        },
+        Value::Bool(b) => op == Op::Eq && value.parse::<bool>() == Ok(*b),
+        _ => false,
+    }
+}
+
+/// Deepest nesting of parentheses and `NOT` a query may use.
+const MAX_NESTING: usize = 64;
+/// Most terms and operators in one query. Long `AND`/`OR` chains nest too,
+/// and matching and dropping the expression recurse through them.
+const MAX_TOKENS: usize = 1024;
+
+fn parse_query(query: &str) -> Result<Expr, QueryError> {
+    let tokens = tokenize(query)?;
+    if tokens.is_empty() {
+        return Err(QueryError::Parse("empty query".to_string()));
+    }
+    if tokens.len() > MAX_TOKENS {
+        return Err(QueryError::Parse(format!(
+            "query too long (more than {} terms)",
+            MAX_TOKENS
+        )));
+    }
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        depth: 0,
+    };
+    let expr = parser.or()?;
+    match parser.tokens.get(parser.pos) {
+        None => Ok(expr),
+        Some(token) => Err(QueryError::Parse(format!("unexpected {:?}", token))),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Open,
+    Close,
+}
+
+/// Split on whitespace and parentheses, keeping quoted runs (which may
+/// follow a `field:` prefix) together.
+fn tokenize(query: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens = Vec::new();
+    let chars = query.chars();
+    let mut word = String::new();
+    let mut quoted = false;
+    // Parentheses opened inside a word, as in `foo(bar)`, are part of it.
+    let mut nested = 0usize;
+
+    for c in chars {
+        match c {
+            '"' => quoted = !quoted,
+            c if quoted => word.push(c),
+            '(' | ')' if word.is_empty() => {
+                tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            }
+            '(' => {
+                nested += 1;
+                word.push(c);
+            }
+            ')' if nested > 0 => {
+                nested -= 1;
+                word.push(c);
+            }
+            ')' => {
+                tokens.push(Token::Word(std::mem::take(&mut word)));
+                tokens.push(Token::Close);
+            }
+            c if c.is_whitespace() => {
+                if !word.is_empty() {
+                    tokens.push(Token::Word(std::mem::take(&mut word)));
+                }
+                nested = 0;
+            }
+            c => word.push(c),
+        }
+    }
+    if quoted {
+        return Err(QueryError::Parse("unterminated quote".to_string()));
+    }
+    if !word.is_empty() {
+        tokens.push(Token::Word(word));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Parentheses and `NOT`s currently open.
+    depth: usize,
+}
+
+impl Parser {
+    fn peek_word(&self) -> Option<&str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Word(w)) => Some(w),
+            _ => None,
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.and()?;
+        while self.peek_word() == Some("OR") {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.unary()?;
+        loop {
+            match self.tokens.get(self.pos) {
+                Some(Token::Word(w)) if w == "AND" => self.pos += 1,
+                Some(Token::Word(w)) if w == "OR" => break,
+                Some(Token::Word(_)) | Some(Token::Open) => {}
+                _ => break,
+            }
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    /// Run `f` one nesting level deeper, failing past [`MAX_NESTING`].
+    fn nested(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<Expr, QueryError>,
+    ) -> Result<Expr, QueryError> {
+        if self.depth >= MAX_NESTING {
+            return Err(QueryError::Parse(format!(
+                "query nested too deeply (more than {} levels)",
+                MAX_NESTING
+            )));
+        }
+        self.depth += 1;
+        let expr = f(self);
+        self.depth -= 1;
+        expr
+    }
+
+    fn unary(&mut self) -> Result<Expr, QueryError> {
+        match self.tokens.get(self.pos).cloned() {
+            Some(Token::Word(w)) if w == "NOT" => {
+                self.pos += 1;
+                self.nested(|p| Ok(Expr::Not(Box::new(p.unary()?))))
+            }
+            Some(Token::Word(w)) if w.len() > 1 && w.starts_with('-') => {
+                self.pos += 1;
+                Ok(Expr::Not(Box::new(Expr::Term(parse_term(&w[1..])?))))
+            }
+            Some(Token::Word(w)) => {
+                self.pos += 1;
+                Ok(Expr::Term(parse_term(&w)?))
+            }
+            Some(Token::Open) => {
+                self.pos += 1;
+                let expr = self.nested(Self::or)?;
+                if self.tokens.get(self.pos) != Some(&Token::Close) {
+                    return Err(QueryError::Parse("missing closing parenthesis".to_string()));
+                }
+                self.pos += 1;
+                Ok(expr)
+            }
+            Some(Token::Close) => Err(QueryError::Parse("unexpected ')'".to_string())),
+            None => Err(QueryError::Parse("query ends early".to_string())),
+        }
+    }
+}
+
+fn parse_term(term: &str) -> Result<Predicate, QueryError> {
+    let Some((field, value)) = term.split_once(':') else {
+        return Ok(Predicate::Text(term.to_string()));
+    };
+    if value.is_empty() {
+        return Err(QueryError::Parse(format!("missing value for '{}'", field)));
+    }
+
+    Ok(match field.to_lowercase().as_str() {
+        "tag" => Predicate::Tag(value.trim_start_matches('#').to_string()),
+        "links-to" => Predicate::LinksTo(value.to_string()),
+        "linked-from" => Predicate::LinkedFrom(value.to_string()),
+        "modified" => {
+            let (op, date) = split_op(value);
+            parse_date(date)
+                .ok_or_else(|| QueryError::Parse(format!("invalid date '{}'", date)))?;
+            Predicate::Modified(op, date.to_string())
+        }
+        "date" => {
+            let (op, date) = split_op(value);
+            Predicate::Date(op, date.to_string())
+        }
+        "words" => {
+            let (op, count) = split_op(value);
+            let count = count
+                .parse()
+                .map_err(|_| QueryError::Parse(format!("invalid word count '{}'", count)))?;
+            Predicate::Words(op, count)
+        }
+        "title" => Predicate::Title(value.to_string()),
+        "path" => Predicate::Path(value.to_string()),
+        _ => {
+            let (op, value) = split_op(value);
+            Predicate::Property(field.to_string(), op, value.to_string())
+        }
+    })
+}
+
+fn split_op(value: &str) -> (Op, &str) {
+    for (prefix, op) in [(">=", Op::Ge), ("<=", Op::Le), (">", Op::Gt), ("<", Op::Lt)] {
+        if let Some(rest) = value.strip_prefix(prefix) {
+            return (op, rest);
+        }
+    }
+    (Op::Eq, value.strip_prefix('=').unwrap_or(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils::create_test_vault;
+
+    fn paths(vault: &Path, query: &str) -> Vec<String> {
+        query_vault(vault, query)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.path)
+            .collect()
+    }
+
+    #[test]
+    fn parentheses_inside_words_are_literal() {
+        let text = |t: &str| Box::new(Expr::Term(Predicate::Text(t.to_string())));
+
+        assert_eq!(parse_query("foo(bar)").unwrap(), *text("foo(bar)"));
+        assert_eq!(
+            parse_query("(a OR foo(bar))").unwrap(),
+            Expr::Or(text("a"), text("foo(bar)"))
+        );
+        assert_eq!(
+            parse_query("(a b)").unwrap(),
+            Expr::And(text("a"), text("b"))
+        );
+    }
+
+    #[test]
+    fn rejects_deep_nesting_and_long_queries() {
+        let deep = format!("{}a{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert!(matches!(parse_query(&deep), Err(QueryError::Parse(_))));
+        let nots = "NOT ".repeat(100_000) + "a";
+        assert!(matches!(parse_query(&nots), Err(QueryError::Parse(_))));
+        let chain = "a OR ".repeat(100_000) + "a";
+        assert!(matches!(parse_query(&chain), Err(QueryError::Parse(_))));
+
+        let nested = format!("{}a{}", "(".repeat(MAX_NESTING), ")".repeat(MAX_NESTING));
+        assert!(parse_query(&nested).is_ok());
+        let nested = format!("({})", nested);
+        assert!(matches!(parse_query(&nested), Err(QueryError::Parse(_))));
+    }
+
+    #[test]
+    fn parses_precedence() {
+        let expr = parse_query("tag:a b OR NOT (c)").unwrap();
+        let term = |p: Predicate| Box::new(Expr::Term(p));
+
+        assert_eq!(
+            expr,
+            Expr::Or(
+                Box::new(Expr::And(
+                    term(Predicate::Tag("a".to_string())),
+                    term(Predicate::Text("b".to_string())),
+                )),
+                Box::new(Expr::Not(term(Predicate::Text("c".to_string())))),
+            )
+        );
+    }
+
+    #[test]
+    fn filters_by_tags_links_and_dates() {
+        let vault = create_test_vault();
+        let path = vault.path();
+
+        assert_eq!(
+            paths(path, "tag:#rust AND links-to:note2"),
+            vec!["note1.md"]
+        );
+        assert_eq!(paths(path, "linked-from:nested"), vec!["note1.md"]);
+        assert_eq!(paths(path, "date:>=2025-01-01"), vec!["note1.md"]);
+        assert_eq!(
+            paths(path, "modified:>2000-01-01 -tag:rust"),
+            vec!["note2.md", "subdir/nested.md"]
+        );
+        assert_eq!(
+            paths(path, "tag:mcp OR path:subdir/"),
+            vec!["note2.md", "subdir/nested.md"]
+        );
+        assert!(paths(path, "modified:<2000-01-01").is_empty());
+    }
+
+    #[test]
+    fn matches_frontmatter_properties() {
+        let vault = create_test_vault();
+        std::fs::write(
+            vault.path().join("task.md"),
+            "---\nstatus: Draft\npriority: 3\n---\nWork\n",
+        )
+        .unwrap();
+
+        assert_eq!(paths(vault.path(), "status:draft"), vec!["task.md"]);
+        assert_eq!(paths(vault.path(), "priority:>=2"), vec!["task.md"]);
+        assert!(paths(vault.path(), "priority:>5").is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        let vault = create_test_vault();
+        for query in ["", "(tag:a", "modified:>soon", "\"open", "tag:"] {
+            assert!(
+                matches!(query_vault(vault.path(), query), Err(QueryError::Parse(_))),
+                "{query}"
+            );
+        }
+    }
+}
@@ -0,0 +1,360 @@
+use crate::links::{normalize_link_target, scan_vault_notes, MDLINK_RE, WIKILINK_RE};
+use crate::resolver::LinkResolver;
+use regex::{Captures, Match};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RenameError {
+    #[error("vault path does not exist: {0}")]
+    VaultNotFound(String),
+    #[error("note not found: {0}")]
+    NoteNotFound(String),
+    #[error("path must stay inside the vault: {0}")]
+    OutsideVault(String),
+    #[error("destination already exists: {0}")]
+    DestinationExists(String),
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// A note whose links were rewritten by [`rename_note`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: String,
+    pub links_rewritten: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameReport {
+    pub old_path: String,
+    pub new_path: String,
+    pub files_changed: Vec<FileChange>,
+    pub links_rewritten: usize,
+}
+
+/// Move a note to `new` (vault-relative, `.md` optional) and rewrite every
+/// wikilink and markdown link pointing at it. `old` may be a vault-relative
+/// path or a link target. Links that reach the note through a frontmatter
+/// alias are left alone.
+pub fn rename_note(vault_path: &Path, old: &str, new: &str) -> Result<RenameReport, RenameError> {
+    if !vault_path.exists() {
+        return Err(RenameError::VaultNotFound(vault_path.display().to_string()));
+    }
+
+    let notes = scan_vault_notes(vault_path);
+    let resolver = LinkResolver::from_notes(&notes);
+    let old_path = resolver
+        .resolve_note(old)
+        .ok_or_else(|| RenameError::NoteNotFound(old.to_string()))?
+        .to_string();
+
+    let new_path = if new.ends_with(".md") {
+        new.to_string()
+    } else {
+        format!("{}.md", new)
+    };
+    let inside = Path::new(&new_path)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)));
+    if !inside {
+        return Err(RenameError::OutsideVault(new_path));
+    }
+    let destination = vault_path.join(&new_path);
+    if destination.exists() {
+        return Err(RenameError::DestinationExists(new_path));
+    }
+
+    // Bare links to the note get the shortest target that stays unique once
+    // it sits at its new path.
+    let renamed = LinkResolver::new(notes.iter().map(|(path, _)| {
+        if *path == old_path {
+            new_path.clone()
+        } else {
+            path.clone()
+        }
+    }));
+    let rewriter = LinkRewriter {
+        resolver: &resolver,
+        old_path: &old_path,
+        old_stem: normalize_link_target(&file_stem(&old_path)),
+        new_path: &new_path,
+        new_link: renamed
+            .shortest_link(&new_path)
+            .unwrap_or_else(|| file_stem(&new_path)),
+    };
+
+    let mut rewritten: HashMap<String, Rewrite> = HashMap::new();
+    for (source, scan) in &notes {
+        if !scan
+            .links
+            .iter()
+            .any(|l| rewriter.points_at_old(source, &l.target))
+        {
+            continue;
+        }
+        let original = fs::read_to_string(vault_path.join(source))?;
+        let (updated, count) = rewriter.rewrite(source, &original);
+        if count > 0 {
+            let rewrite = Rewrite {
+                original,
+                updated,
+                count,
+            };
+            rewritten.insert(source.clone(), rewrite);
+        }
+    }
+
+    // Move the note first so a failed move leaves every file untouched, then
+    // rewrite links, putting everything back if a write fails.
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let source_file = vault_path.join(&old_path);
+    fs::rename(&source_file, &destination)?;
+    let on_disk = |source: &str| {
+        if source == old_path {
+            destination.clone()
+        } else {
+            vault_path.join(source)
+        }
+    };
+    let mut written = Vec::new();
+    for (source, rewrite) in &rewritten {
+        if let Err(e) = fs::write(on_disk(source), &rewrite.updated) {
+            for done in written {
+                let _ = fs::write(on_disk(done), &rewritten[done].original);
+            }
+            let _ = fs::rename(&destination, &source_file);
+            return Err(e.into());
+        }
+        written.push(source.as_str());
+    }
+
+    let mut files_changed: Vec<FileChange> = rewritten
+        .into_iter()
+        .map(|(path, Rewrite { count, .. })| FileChange {
+            path: if path == old_path {
+                new_path.clone()
+            } else {
+                path
+            },
+            links_rewritten: count,
+        })
+        .collect();
+    files_changed.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(RenameReport {
+        links_rewritten: files_changed.iter().map(|c| c.links_rewritten).sum(),
+        old_path,
+        new_path,
+        files_changed,
+    })
+}
+
+/// A linking note's contents before and after [`LinkRewriter::rewrite`].
+struct Rewrite {
+    original: String,
+    updated: String,
+    count: usize,
+}
+
+struct LinkRewriter<'a> {
+    resolver: &'a LinkResolver,
+    old_path: &'a str,
+    old_stem: String,
+    new_path: &'a str,
+    new_link: String,
+}
+
+impl LinkRewriter<'_> {
+    fn points_at_old(&self, source: &str, target: &str) -> bool {
+        normalize_link_target(&file_stem(target)) == self.old_stem
+            && self.resolver.lookup(target, Some(source)) == Some(self.old_path)
+    }
+
+    /// Rewrite links in `content` (the body of `source`), returning the new
+    /// text and the number of links changed.
+    fn rewrite(&self, source: &str, content: &str) -> (String, usize) {
+        let mut count = 0;
+
+        let content = WIKILINK_RE.replace_all(content, |caps: &Captures| {
+            let target = &caps["target"];
+            if !self.points_at_old(source, target) {
+                return caps[0].to_string();
+            }
+            count += 1;
+            let new_target = if target.contains('/') {
+                self.new_path.trim_end_matches(".md").to_string()
+            } else {
+                self.new_link.clone()
+            };
+            splice(caps, caps.name("target").unwrap(), &new_target)
+        });
+
+        let content = MDLINK_RE.replace_all(&content, |caps: &Captures| {
+            let target = &caps[2];
+            let is_note_link = target.ends_with(".md") || !target.contains('.');
+            if !is_note_link || !self.points_at_old(source, target) {
+                return caps[0].to_string();
+            }
+            count += 1;
+            let from_dir = Path::new(source).parent().unwrap_or(Path::new(""));
+            let mut new_target = relative_path(from_dir, Path::new(self.new_path));
+            if !target.ends_with(".md") {
+                new_target = new_target.trim_end_matches(".md").to_string();
+            }
+            if target.starts_with("./") && !new_target.starts_with("../") {
+                new_target = format!("./{}", new_target);
+            }
+            splice(caps, caps.get(2).unwrap(), &new_target)
+        });
+
+        (content.into_owned(), count)
+    }
+}
+
+/// Replace one capture group within the full match, keeping the rest intact.
+fn splice(caps: &Captures, part: Match, replacement: &str) -> String {
+    let whole = caps.get(0).unwrap();
+    let text = whole.as_str();
+    let start = part.start() - whole.start();
+    let end = part.end() - whole.start();
+    format!("{}{}{}", &text[..start], replacement, &text[end..])
+}
+
+fn file_stem(path: &str) -> String {
+    Path::new(path.trim())
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Path to `to` from directory `from`, both relative to the vault root.
+fn relative_path(from: &Path, to: &Path) -> String {
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut path = PathBuf::new();
+    for _ in common..from.len() {
+        path.push("..");
+    }
+    for component in &to[common..] {
+        path.push(component);
+    }
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils::create_test_vault;
+
+    #[test]
+    fn renames_and_rewrites_links() {
+        let vault = create_test_vault();
+        fs::write(
+            vault.path().join("refs.md"),
+            "![[note1#Hello World|hi]] and [md](note1.md) and [[note2]]\n",
+        )
+        .unwrap();
+
+        let report = rename_note(vault.path(), "note1", "archive/first").unwrap();
+
+        assert_eq!(report.old_path, "note1.md");
+        assert_eq!(report.new_path, "archive/first.md");
+        assert_eq!(report.links_rewritten, 3);
+        assert!(!vault.path().join("note1.md").exists());
+        assert!(vault.path().join("archive/first.md").exists());
+
+        let refs = fs::read_to_string(vault.path().join("refs.md")).unwrap();
+        assert_eq!(
+            refs,
+            "![[first#Hello World|hi]] and [md](archive/first.md) and [[note2]]\n"
+        );
+        let nested = fs::read_to_string(vault.path().join("subdir/nested.md")).unwrap();
+        assert!(nested.contains("[[first]]"));
+    }
+
+    #[test]
+    fn leaves_alias_links_alone() {
+        let vault = create_test_vault();
+        fs::write(
+            vault.path().join("aliased.md"),
+            "---\naliases: [Nickname]\n---\n",
+        )
+        .unwrap();
+        fs::write(vault.path().join("linker.md"), "[[Nickname]] [[aliased]]\n").unwrap();
+
+        let report = rename_note(vault.path(), "aliased.md", "renamed").unwrap();
+
+        assert_eq!(report.links_rewritten, 1);
+        let linker = fs::read_to_string(vault.path().join("linker.md")).unwrap();
+        assert_eq!(linker, "[[Nickname]] [[renamed]]\n");
+    }
+
+    #[test]
+    fn qualifies_links_when_new_name_is_taken() {
+        let vault = create_test_vault();
+        fs::create_dir(vault.path().join("work")).unwrap();
+        fs::write(vault.path().join("work/todo.md"), "Tasks\n").unwrap();
+
+        rename_note(vault.path(), "note2", "home/todo").unwrap();
+
+        let note1 = fs::read_to_string(vault.path().join("note1.md")).unwrap();
+        assert!(note1.contains("[[home/todo]]"));
+    }
+
+    #[test]
+    fn refuses_to_overwrite() {
+        let vault = create_test_vault();
+        assert!(matches!(
+            rename_note(vault.path(), "note1", "note2"),
+            Err(RenameError::DestinationExists(_))
+        ));
+        assert!(matches!(
+            rename_note(vault.path(), "ghost", "other"),
+            Err(RenameError::NoteNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn refuses_destinations_outside_the_vault() {
+        let vault = create_test_vault();
+        for new in ["../escape", "/tmp/escape", "subdir/../../escape"] {
+            assert!(
+                matches!(
+                    rename_note(vault.path(), "note1", new),
+                    Err(RenameError::OutsideVault(_))
+                ),
+                "{}",
+                new
+            );
+        }
+        assert!(vault.path().join("note1.md").exists());
+        assert!(!vault.path().parent().unwrap().join("escape.md").exists());
+    }
+
+    #[test]
+    fn failed_move_leaves_links_unchanged() {
+        let vault = create_test_vault();
+        fs::write(vault.path().join("refs.md"), "[[note1]]\n").unwrap();
+        // A plain file where the destination folder should go.
+        fs::write(vault.path().join("blocked"), "").unwrap();
+
+        assert!(matches!(
+            rename_note(vault.path(), "note1", "blocked/note1"),
+            Err(RenameError::IoError(_))
+        ));
+
+        assert!(vault.path().join("note1.md").exists());
+        let refs = fs::read_to_string(vault.path().join("refs.md")).unwrap();
+        assert_eq!(refs, "[[note1]]\n");
+        let nested = fs::read_to_string(vault.path().join("subdir/nested.md")).unwrap();
+        assert!(nested.contains("[[note1]]"));
+    }
+}
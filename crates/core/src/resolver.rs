@@ -0,0 +1,339 @@
+use crate::links::{scan_vault_notes, LinkError, LinkNormalization};
+use crate::scan::NoteScan;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// What a link target points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution<'a> {
+    Note(&'a str),
+    /// Several notes match the target equally well, sorted by path.
+    Ambiguous(Vec<&'a str>),
+    Unresolved,
+}
+
+/// Maps link targets to note paths following Obsidian's rules: a target
+/// matches a note by file name, by any trailing part of its path
+/// (`work/todo` for `projects/work/todo.md`), or by frontmatter alias.
+/// Names and paths are compared after [`LinkNormalization`].
+#[derive(Debug, Clone, Default)]
+pub struct LinkResolver {
+    notes: Vec<NotePath>,
+    by_path: HashMap<String, usize>,
+    by_name: HashMap<String, Vec<usize>>,
+    aliases: HashMap<String, usize>,
+    norm: LinkNormalization,
+}
+
+#[derive(Debug, Clone)]
+struct NotePath {
+    path: String,
+    /// Normalized path components, without the `.md` extension.
+    key: Vec<String>,
+}
+
+impl LinkResolver {
+    /// Build a resolver over vault-relative note paths.
+    pub fn new<I, S>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::with_normalization(paths, LinkNormalization::default())
+    }
+
+    pub fn with_normalization<I, S>(paths: I, norm: LinkNormalization) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut paths: Vec<String> = paths.into_iter().map(Into::into).collect();
+        paths.sort();
+        paths.dedup();
+
+        let mut resolver = Self {
+            norm,
+            ..Default::default()
+        };
+        for path in paths {
+            let key = resolver.key(&path);
+            let Some(name) = key.last() else {
+                continue;
+            };
+            let index = resolver.notes.len();
+            resolver
+                .by_name
+                .entry(name.clone())
+                .or_default()
+                .push(index);
+            resolver.by_path.insert(path.clone(), index);
+            resolver.notes.push(NotePath { path, key });
+        }
+        resolver
+    }
+
+    /// Scan a vault and index its notes and their aliases.
+    pub fn from_vault(vault_path: &Path) -> Result<Self, LinkError> {
+        if !vault_path.exists() {
+            return Err(LinkError::VaultNotFound(vault_path.display().to_string()));
+        }
+        Ok(Self::from_notes(&scan_vault_notes(vault_path)))
+    }
+
+    pub(crate) fn from_notes(notes: &[(String, NoteScan)]) -> Self {
+        let notes: Vec<(&str, &NoteScan)> = notes.iter().map(|(p, s)| (p.as_str(), s)).collect();
+        Self::from_scans(&notes, LinkNormalization::default())
+    }
+
+    /// Index vault-relative note paths and the aliases in their scans.
+    pub(crate) fn from_scans(notes: &[(&str, &NoteScan)], norm: LinkNormalization) -> Self {
+        let mut resolver = Self::with_normalization(notes.iter().map(|(path, _)| *path), norm);
+        for (path, scan) in notes {
+            for alias in scan.frontmatter.iter().flat_map(|fm| &fm.aliases) {
+                resolver.add_alias(alias, path);
+            }
+        }
+        resolver
+    }
+
+    /// Let `alias` resolve to the note at `path`. Note names take precedence
+    /// over aliases, and the first note to claim an alias keeps it.
+    pub fn add_alias(&mut self, alias: &str, path: &str) {
+        if let Some(&index) = self.by_path.get(path) {
+            self.aliases
+                .entry(self.norm.normalize(alias))
+                .or_insert(index);
+        }
+    }
+
+    /// Every note matching `target`. `source`, the linking note, is used for
+    /// `./` and `../` links, which are relative to its directory.
+    pub fn resolve(&self, target: &str, source: Option<&str>) -> Resolution<'_> {
+        let key = self.key(target);
+        // Components after the last `..`, matched against the end of note paths.
+        let suffix = &key[key.iter().rposition(|c| c == "..").map_or(0, |i| i + 1)..];
+        let Some(name) = suffix.last() else {
+            return Resolution::Unresolved;
+        };
+        let candidates = self.by_name.get(name).map_or(&[][..], Vec::as_slice);
+
+        if let Some(relative) = source.and_then(|s| self.relative_key(s, target, &key)) {
+            if let Some(&index) = candidates.iter().find(|&&i| self.notes[i].key == relative) {
+                return Resolution::Note(&self.notes[index].path);
+            }
+        }
+
+        let matching: Vec<usize> = candidates
+            .iter()
+            .copied()
+            .filter(|&i| self.notes[i].key.ends_with(suffix))
+            .collect();
+        // A path that no longer matches still resolves by file name, so
+        // links survive notes being moved between folders.
+        let matching = if matching.is_empty() {
+            candidates.to_vec()
+        } else {
+            matching
+        };
+
+        match matching.as_slice() {
+            [] => self
+                .aliases
+                .get(&suffix.join("/"))
+                .map_or(Resolution::Unresolved, |&i| {
+                    Resolution::Note(&self.notes[i].path)
+                }),
+            [index] => Resolution::Note(&self.notes[*index].path),
+            _ => Resolution::Ambiguous(
+                matching
+                    .iter()
+                    .map(|&i| self.notes[i].path.as_str())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// The note Obsidian opens for `target`. Ambiguous targets prefer notes
+    /// under the linking note's directory, then the shallowest path.
+    pub fn lookup(&self, target: &str, source: Option<&str>) -> Option<&str> {
+        match self.resolve(target, source) {
+            Resolution::Note(path) => Some(path),
+            Resolution::Ambiguous(paths) => Some(closest(&paths, source)),
+            Resolution::Unresolved => None,
+        }
+    }
+
+    /// Resolve a note given as a vault-relative path or as a link target.
+    pub fn resolve_note(&self, note: &str) -> Option<&str> {
+        match self.by_path.get(note) {
+            Some(&index) => Some(&self.notes[index].path),
+            None => self.lookup(note, None),
+        }
+    }
+
+    /// The shortest link target that resolves only to `path`: the file name
+    /// when it is unique, otherwise as many trailing folders as needed.
+    pub fn shortest_link(&self, path: &str) -> Option<String> {
+        let note = &self.notes[*self.by_path.get(path)?];
+        let parts: Vec<&str> = path
+            .strip_suffix(".md")
+            .unwrap_or(path)
+            .split(['/', '\\'])
+            .filter(|c| !c.is_empty() && *c != ".")
+            .collect();
+        let candidates = &self.by_name[note.key.last()?];
+
+        for len in 1..parts.len() {
+            let suffix = &note.key[note.key.len() - len..];
+            let matches = candidates
+                .iter()
+                .filter(|&&i| self.notes[i].key.ends_with(suffix))
+                .count();
+            if matches == 1 {
+                return Some(parts[parts.len() - len..].join("/"));
+            }
+        }
+        Some(parts.join("/"))
+    }
+
+    /// Groups of notes sharing a file name, each sorted by path.
+    pub fn shared_names(&self) -> Vec<Vec<&str>> {
+        let mut groups: Vec<Vec<&str>> = self
+            .by_name
+            .values()
+            .filter(|indices| indices.len() > 1)
+            .map(|indices| {
+                indices
+                    .iter()
+                    .map(|&i| self.notes[i].path.as_str())
+                    .collect()
+            })
+            .collect();
+        groups.sort();
+        groups
+    }
+
+    fn key(&self, path: &str) -> Vec<String> {
+        self.norm
+            .normalize(&path.replace('\\', "/"))
+            .split('/')
+            .filter(|c| !c.is_empty() && *c != ".")
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// The key of a `./` or `../` target joined onto `source`'s directory.
+    fn relative_key(&self, source: &str, target: &str, key: &[String]) -> Option<Vec<String>> {
+        let target = target.trim();
+        if !target.starts_with("./") && !target.starts_with("../") {
+            return None;
+        }
+        let mut joined = self.key(source);
+        joined.pop();
+        for component in key {
+            if component == ".." {
+                joined.pop()?;
+            } else {
+                joined.push(component.clone());
+            }
+        }
+        Some(joined)
+    }
+}
+
+fn closest<'a>(paths: &[&'a str], source: Option<&str>) -> &'a str {
+    let dir = source
+        .and_then(|s| Path::new(s).parent())
+        .unwrap_or(Path::new(""));
+    paths
+        .iter()
+        .copied()
+        .min_by_key(|p| {
+            let path = Path::new(p);
+            (!path.starts_with(dir), path.components().count())
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver() -> LinkResolver {
+        LinkResolver::new([
+            "todo.md",
+            "work/todo.md",
+            "home/todo.md",
+            "home/garden/Plants.md",
+            "archive/plants.md",
+            "readme.md",
+        ])
+    }
+
+    #[test]
+    fn resolves_by_path_suffix() {
+        let r = resolver();
+        assert_eq!(
+            r.resolve("work/todo", None),
+            Resolution::Note("work/todo.md")
+        );
+        assert_eq!(
+            r.resolve("Garden/plants", None),
+            Resolution::Note("home/garden/Plants.md")
+        );
+        assert_eq!(r.resolve("readme", None), Resolution::Note("readme.md"));
+        assert_eq!(r.resolve("ghost", None), Resolution::Unresolved);
+        assert_eq!(
+            r.resolve("plants", None),
+            Resolution::Ambiguous(vec!["archive/plants.md", "home/garden/Plants.md"])
+        );
+    }
+
+    #[test]
+    fn resolves_relative_markdown_links() {
+        let r = resolver();
+        assert_eq!(
+            r.resolve("../work/todo.md", Some("home/list.md")),
+            Resolution::Note("work/todo.md")
+        );
+        assert_eq!(
+            r.resolve("./todo.md", Some("home/list.md")),
+            Resolution::Note("home/todo.md")
+        );
+    }
+
+    #[test]
+    fn lookup_prefers_nearby_then_shallow_notes() {
+        let r = resolver();
+        assert_eq!(r.lookup("todo", Some("home/list.md")), Some("home/todo.md"));
+        assert_eq!(r.lookup("todo", Some("work/x/deep.md")), Some("todo.md"));
+        assert_eq!(r.lookup("todo", None), Some("todo.md"));
+        assert_eq!(r.lookup("plants", None), Some("archive/plants.md"));
+    }
+
+    #[test]
+    fn finds_shortest_unique_link() {
+        let r = resolver();
+        assert_eq!(r.shortest_link("readme.md").as_deref(), Some("readme"));
+        assert_eq!(
+            r.shortest_link("work/todo.md").as_deref(),
+            Some("work/todo")
+        );
+        assert_eq!(r.shortest_link("todo.md").as_deref(), Some("todo"));
+        assert_eq!(
+            r.shortest_link("home/garden/Plants.md").as_deref(),
+            Some("garden/Plants")
+        );
+        assert_eq!(r.shortest_link("missing.md"), None);
+        assert_eq!(r.shared_names().len(), 2);
+    }
+
+    #[test]
+    fn names_take_precedence_over_aliases() {
+        let mut r = resolver();
+        r.add_alias("Groceries", "home/todo.md");
+        r.add_alias("readme", "work/todo.md");
+        assert_eq!(r.lookup("groceries", None), Some("home/todo.md"));
+        assert_eq!(r.lookup("readme", None), Some("readme.md"));
+    }
+}
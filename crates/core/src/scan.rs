@@ -0,0 +1,242 @@
+use crate::frontmatter::{is_delimiter, Frontmatter, MAX_FRONTMATTER_BYTES};
+use crate::links::{extract_links, Link};
+use crate::tags::{collect_line_tags, is_code_fence, merge_tags};
+use crate::utils::for_each_line;
+use serde::{Deserialize, Serialize};
+use std::ops::ControlFlow;
+use std::path::Path;
+
+/// Everything the tree and link index need from a note, gathered in one read.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NoteScan {
+    pub frontmatter: Option<Frontmatter>,
+    pub links: Vec<Link>,
+    /// `#tags` found in the body, outside code blocks.
+    pub inline_tags: Vec<String>,
+    /// Whitespace-separated words in the body, excluding frontmatter.
+    pub word_count: usize,
+}
+
+impl NoteScan {
+    /// Frontmatter tags followed by inline tags, deduplicated.
+    pub fn tags(&self) -> Vec<String> {
+        let frontmatter = self.frontmatter.as_ref().map_or(&[][..], |fm| &fm.tags);
+        merge_tags(frontmatter, &self.inline_tags)
+    }
+}
+
+#[derive(PartialEq, Default)]
+enum FrontmatterState {
+    #[default]
+    Start,
+    Inside,
+    Done,
+}
+
+/// Scan a note line by line, extracting frontmatter and links without
+/// holding the whole file in memory.
+pub fn scan_note(path: &Path) -> std::io::Result<NoteScan> {
+    let mut scanner = Scanner::default();
+    for_each_line(path, |_, line| {
+        scanner.line(line);
+        ControlFlow::Continue(())
+    })?;
+    Ok(scanner.finish())
+}
+
+/// Scan a note already in memory, like [`scan_note`].
+pub fn scan_content(content: &str) -> NoteScan {
+    let mut scanner = Scanner::default();
+    for line in content.lines() {
+        scanner.line(line);
+    }
+    scanner.finish()
+}
+
+#[derive(Default)]
+struct Scanner {
+    scan: NoteScan,
+    state: FrontmatterState,
+    yaml: String,
+    in_code_block: bool,
+}
+
+impl Scanner {
+    fn line(&mut self, line: &str) {
+        match self.state {
+            FrontmatterState::Start if line.trim().is_empty() => {}
+            FrontmatterState::Start if is_delimiter(line) => {
+                self.state = FrontmatterState::Inside;
+            }
+            FrontmatterState::Start => {
+                self.state = FrontmatterState::Done;
+                self.body_line(line);
+            }
+            FrontmatterState::Inside if is_delimiter(line) => {
+                self.scan.frontmatter = serde_yaml::from_str(self.yaml.trim()).ok();
+                self.yaml = String::new();
+                self.state = FrontmatterState::Done;
+            }
+            FrontmatterState::Inside => {
+                self.yaml.push_str(line);
+                self.yaml.push('\n');
+                if self.yaml.len() > MAX_FRONTMATTER_BYTES {
+                    self.replay_as_body();
+                }
+            }
+            FrontmatterState::Done => self.body_line(line),
+        }
+
+        self.scan.links.extend(extract_links(line));
+    }
+
+    fn body_line(&mut self, line: &str) {
+        let scan = &mut self.scan;
+        scan.word_count += line.split_whitespace().count();
+        if is_code_fence(line) {
+            self.in_code_block = !self.in_code_block;
+        } else if !self.in_code_block {
+            collect_line_tags(line, &mut scan.inline_tags);
+        }
+    }
+
+    /// An opening `---` that never closed was a horizontal rule: count the
+    /// lines buffered since as body. Their links were already collected.
+    fn replay_as_body(&mut self) {
+        let yaml = std::mem::take(&mut self.yaml);
+        self.state = FrontmatterState::Done;
+        self.body_line("---");
+        for line in yaml.lines() {
+            self.body_line(line);
+        }
+    }
+
+    fn finish(mut self) -> NoteScan {
+        if self.state == FrontmatterState::Inside {
+            self.replay_as_body();
+        }
+        self.scan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn scans_frontmatter_and_links() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("note.md");
+        fs::write(
+            &path,
+            "---\ntitle: Scan\ntags: [a, b]\n---\n\nSee [[Other]] and [doc](doc.md)\n",
+        )
+        .unwrap();
+
+        let scan = scan_note(&path).unwrap();
+        let fm = scan.frontmatter.unwrap();
+
+        assert_eq!(fm.title, Some("Scan".to_string()));
+        assert_eq!(fm.tags, vec!["a", "b"]);
+        assert_eq!(scan.links.len(), 2);
+        assert_eq!(scan.word_count, 4);
+    }
+
+    #[test]
+    fn collects_inline_tags_after_frontmatter() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("note.md");
+        fs::write(
+            &path,
+            "---\ntags: [rust]\n---\n#draft notes on #rust\n```\n#comment\n```\n",
+        )
+        .unwrap();
+
+        let scan = scan_note(&path).unwrap();
+
+        assert_eq!(scan.inline_tags, vec!["draft", "rust"]);
+        assert_eq!(scan.tags(), vec!["rust", "draft"]);
+    }
+
+    #[test]
+    fn scans_content_like_files() {
+        let content = "---\ntitle: Scan\ntags: [a]\n---\r\nSee [[Other]] #b\n";
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("note.md");
+        fs::write(&path, content).unwrap();
+
+        let from_file = scan_note(&path).unwrap();
+        let scan = scan_content(content);
+
+        assert_eq!(scan.tags(), vec!["a", "b"]);
+        assert_eq!(scan.links.len(), 1);
+        assert_eq!(scan.word_count, from_file.word_count);
+        assert_eq!(scan.tags(), from_file.tags());
+    }
+
+    #[test]
+    fn ignores_unterminated_frontmatter() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("note.md");
+        fs::write(&path, "---\ntitle: Never closed\n[[Link]]\n").unwrap();
+
+        let scan = scan_note(&path).unwrap();
+
+        assert!(scan.frontmatter.is_none());
+        assert_eq!(scan.links.len(), 1);
+    }
+
+    #[test]
+    fn leading_rule_without_close_is_body() {
+        let scan = scan_content(
+            "---
+Some #idea in [[Link]]
+```
+#not-a-tag
+```
+",
+        );
+
+        assert!(scan.frontmatter.is_none());
+        assert_eq!(scan.inline_tags, vec!["idea"]);
+        assert_eq!(scan.links.len(), 1);
+        assert_eq!(scan.word_count, 8);
+    }
+
+    #[test]
+    fn huge_unterminated_frontmatter_is_body() {
+        let body = "word #tag\n".repeat(MAX_FRONTMATTER_BYTES / 8);
+        let scan = scan_content(&format!("---\n{}", body));
+
+        assert!(scan.frontmatter.is_none());
+        assert_eq!(scan.word_count, 1 + 2 * MAX_FRONTMATTER_BYTES / 8);
+        assert_eq!(scan.inline_tags, vec!["tag"]);
+    }
+
+    #[test]
+    fn delimiters_must_be_exact() {
+        let scan = scan_content("---foo\ntitle: Nope\n---\n");
+        assert!(scan.frontmatter.is_none());
+
+        let scan = scan_content("---\ntitle: Nope\n---bar\n");
+        assert!(scan.frontmatter.is_none());
+        assert_eq!(scan.word_count, 4);
+
+        let scan = scan_content("---  \ntitle: Yes\n---\t\nbody\n");
+        assert_eq!(scan.frontmatter.unwrap().title, Some("Yes".to_string()));
+        assert_eq!(scan.word_count, 1);
+    }
+
+    #[test]
+    fn no_frontmatter_after_body_text() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("note.md");
+        fs::write(&path, "# Heading\n---\ntitle: Not frontmatter\n---\n").unwrap();
+
+        let scan = scan_note(&path).unwrap();
+
+        assert!(scan.frontmatter.is_none());
+    }
+}
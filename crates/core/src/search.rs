@@ -1,7 +1,11 @@
-use crate::utils::walk_markdown_files;
+use crate::cache::MetadataCache;
+use crate::exclude::{ExcludeError, ExcludeSet};
+use crate::utils::for_each_line;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::collections::VecDeque;
+use std::io;
+use std::ops::ControlFlow;
 use std::path::Path;
 use thiserror::Error;
 
@@ -11,6 +15,8 @@ pub enum SearchError {
     InvalidPattern(#[from] regex::Error),
     #[error("io error: {0}")]
     IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    Exclude(#[from] ExcludeError),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +25,12 @@ pub struct SearchMatch {
     pub line_content: String,
     pub match_start: usize,
     pub match_end: usize,
+    /// Lines preceding the match, oldest first, when `context_before` is set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_before: Vec<String>,
+    /// Lines following the match when `context_after` is set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_after: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,7 +43,17 @@ pub struct SearchResult {
 pub struct SearchOptions {
     pub file_pattern: Option<String>,
     pub case_insensitive: bool,
+    /// Only match at word boundaries.
+    pub whole_word: bool,
+    /// Treat the pattern as plain text rather than a regex.
+    pub literal: bool,
     pub max_results: Option<usize>,
+    /// Extra exclude globs, applied along with the vault's `.vaultignore`.
+    pub exclude: Vec<String>,
+    /// Lines of context to capture before each match, like `grep -B`.
+    pub context_before: usize,
+    /// Lines of context to capture after each match, like `grep -A`.
+    pub context_after: usize,
 }
 
 pub fn search_vault(
@@ -39,61 +61,124 @@ pub fn search_vault(
     pattern: &str,
     options: &SearchOptions,
 ) -> Result<Vec<SearchResult>, SearchError> {
-    let regex = if options.case_insensitive {
-        Regex::new(&format!("(?i){}", pattern))?
-    } else {
-        Regex::new(pattern)?
-    };
+    search_walk(vault_path, pattern, options, |path, line| {
+        for_each_line(path, line)
+    })
+}
 
-    let file_regex = options
-        .file_pattern
-        .as_ref()
-        .map(|p| Regex::new(p))
-        .transpose()?;
-
-    let entries = walk_markdown_files(vault_path).filter(|entry| {
-        file_regex.as_ref().is_none_or(|re| {
-            entry
-                .path()
-                .file_name()
-                .and_then(|n| n.to_str())
-                .is_some_and(|name| re.is_match(name))
-        })
-    });
+/// [`search_vault`], reusing note text from `cache` for files whose size
+/// and mtime haven't changed.
+pub fn search_vault_with_cache(
+    vault_path: &Path,
+    pattern: &str,
+    options: &SearchOptions,
+    cache: &MetadataCache,
+) -> Result<Vec<SearchResult>, SearchError> {
+    search_walk(vault_path, pattern, options, |path, line| {
+        let text = cache.text(path)?;
+        for (index, content) in text.lines().enumerate() {
+            if line(index + 1, content).is_break() {
+                break;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Walk the vault's notes, feeding each one's lines through `read`.
+fn search_walk<R>(
+    vault_path: &Path,
+    pattern: &str,
+    options: &SearchOptions,
+    mut read: R,
+) -> Result<Vec<SearchResult>, SearchError>
+where
+    R: FnMut(&Path, &mut dyn FnMut(usize, &str) -> ControlFlow<()>) -> io::Result<()>,
+{
+    let regex = compile_pattern(pattern, options)?;
+    let file_regex = compile_file_pattern(options)?;
+
+    let exclude = ExcludeSet::load(vault_path, &options.exclude)?;
+    let entries = exclude
+        .walk_markdown(vault_path, vault_path)
+        .filter(|entry| {
+            file_regex.as_ref().is_none_or(|re| {
+                entry
+                    .path()
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|name| re.is_match(name))
+            })
+        });
 
     let mut results = Vec::new();
     let mut total_matches = 0;
 
     for entry in entries {
         let path = entry.path();
-        let Ok(content) = fs::read_to_string(path) else {
-            continue;
-        };
+        let matches_before = total_matches;
+        let mut file = FileSearch::new(&regex, options);
 
-        let file_matches: Vec<SearchMatch> = content
-            .lines()
-            .enumerate()
-            .filter_map(|(line_num, line)| {
-                regex.find(line).map(|mat| SearchMatch {
-                    line_number: line_num + 1,
-                    line_content: line.to_string(),
-                    match_start: mat.start(),
-                    match_end: mat.end(),
-                })
-            })
-            .take_while(|_| {
-                options
-                    .max_results
-                    .is_none_or(|max| total_matches < max)
-                    .then(|| total_matches += 1)
-                    .is_some()
-            })
-            .collect();
+        let read = read(path, &mut |line_number, line| {
+            file.line(line_number, line, &mut total_matches)
+        });
+
+        // Unreadable files (including invalid UTF-8) are skipped entirely.
+        if read.is_err() {
+            total_matches = matches_before;
+            continue;
+        }
 
-        if !file_matches.is_empty() {
+        if !file.matches.is_empty() {
             results.push(SearchResult {
                 file_path: path.to_string_lossy().to_string(),
-                matches: file_matches,
+                matches: file.matches,
+            });
+
+            if options.max_results.is_some_and(|max| total_matches >= max) {
+                break;
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Search notes already in memory, given as `(path, content)` pairs, with the
+/// same matching rules as [`search_vault`]. `file_pattern` is matched against
+/// the last path component; `exclude` is ignored since there is no vault
+/// directory to read a `.vaultignore` from.
+pub fn search_contents<'a, I>(
+    files: I,
+    pattern: &str,
+    options: &SearchOptions,
+) -> Result<Vec<SearchResult>, SearchError>
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let regex = compile_pattern(pattern, options)?;
+    let file_regex = compile_file_pattern(options)?;
+
+    let mut results = Vec::new();
+    let mut total_matches = 0;
+
+    for (path, content) in files {
+        let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+        if file_regex.as_ref().is_some_and(|re| !re.is_match(name)) {
+            continue;
+        }
+
+        let mut file = FileSearch::new(&regex, options);
+        for (index, line) in content.lines().enumerate() {
+            if file.line(index + 1, line, &mut total_matches).is_break() {
+                break;
+            }
+        }
+
+        if !file.matches.is_empty() {
+            results.push(SearchResult {
+                file_path: path.to_string(),
+                matches: file.matches,
             });
 
             if options.max_results.is_some_and(|max| total_matches >= max) {
@@ -105,6 +190,110 @@ pub fn search_vault(
     Ok(results)
 }
 
+fn compile_pattern(pattern: &str, options: &SearchOptions) -> Result<Regex, SearchError> {
+    let pattern = if options.literal {
+        regex::escape(pattern)
+    } else {
+        pattern.to_string()
+    };
+    if options.case_insensitive {
+        Ok(Regex::new(&format!("(?i){}", pattern))?)
+    } else {
+        Ok(Regex::new(&pattern)?)
+    }
+}
+
+fn compile_file_pattern(options: &SearchOptions) -> Result<Option<Regex>, SearchError> {
+    Ok(options
+        .file_pattern
+        .as_ref()
+        .map(|p| Regex::new(p))
+        .transpose()?)
+}
+
+/// Matches collected from one file, fed a line at a time.
+struct FileSearch<'a> {
+    regex: &'a Regex,
+    options: &'a SearchOptions,
+    matches: Vec<SearchMatch>,
+    recent: VecDeque<String>,
+}
+
+impl<'a> FileSearch<'a> {
+    fn new(regex: &'a Regex, options: &'a SearchOptions) -> Self {
+        Self {
+            regex,
+            options,
+            matches: Vec::new(),
+            recent: VecDeque::with_capacity(options.context_before),
+        }
+    }
+
+    /// Process one line, counting new matches in `total_matches`. Breaks once
+    /// `max_results` is reached and trailing context is complete.
+    fn line(
+        &mut self,
+        line_number: usize,
+        line: &str,
+        total_matches: &mut usize,
+    ) -> ControlFlow<()> {
+        let options = self.options;
+        // Matches from the last `context_after` lines still collecting context.
+        let pending = self
+            .matches
+            .iter_mut()
+            .rev()
+            .take_while(|m| m.line_number + options.context_after >= line_number);
+        for m in pending {
+            m.context_after.push(line.to_string());
+        }
+
+        let limit_reached = options.max_results.is_some_and(|max| *total_matches >= max);
+        if limit_reached {
+            let waiting = self
+                .matches
+                .last()
+                .is_some_and(|m| m.line_number + options.context_after > line_number);
+            return if waiting {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            };
+        }
+
+        if let Some(mat) = first_match(self.regex, line, options.whole_word) {
+            self.matches.push(SearchMatch {
+                line_number,
+                line_content: line.to_string(),
+                match_start: mat.start(),
+                match_end: mat.end(),
+                context_before: self.recent.iter().cloned().collect(),
+                context_after: Vec::new(),
+            });
+            *total_matches += 1;
+        }
+
+        if options.context_before > 0 {
+            if self.recent.len() == options.context_before {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(line.to_string());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// First match in `line`; with `whole_word`, one not touching a word
+/// character on either side.
+fn first_match<'a>(regex: &Regex, line: &'a str, whole_word: bool) -> Option<regex::Match<'a>> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    regex.find_iter(line).find(|mat| {
+        !whole_word
+            || (!line[..mat.start()].chars().next_back().is_some_and(is_word)
+                && !line[mat.end()..].chars().next().is_some_and(is_word))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,4 +330,124 @@ mod tests {
 
         assert!(results.is_empty());
     }
+
+    #[test]
+    fn respects_max_results() {
+        let vault = create_test_vault();
+        let results = search_vault(
+            vault.path(),
+            "o",
+            &SearchOptions {
+                max_results: Some(2),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let total: usize = results.iter().map(|r| r.matches.len()).sum();
+        assert_eq!(total, 2);
+    }
+
+    #[test]
+    fn skips_excluded_paths() {
+        let vault = create_test_vault();
+        std::fs::write(vault.path().join(".vaultignore"), "note2.md\n").unwrap();
+        let options = SearchOptions {
+            exclude: vec!["subdir".to_string()],
+            ..Default::default()
+        };
+
+        let results = search_vault(vault.path(), "(?i)hello|links", &options).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].file_path.ends_with("note1.md"));
+    }
+
+    #[test]
+    fn captures_context_lines() {
+        let vault = create_test_vault();
+        let options = SearchOptions {
+            context_before: 2,
+            context_after: 1,
+            ..Default::default()
+        };
+
+        let results = search_vault(vault.path(), "Content with", &options).unwrap();
+        let m = &results[0].matches[0];
+
+        assert_eq!(m.context_before, vec!["# Hello World", ""]);
+        assert_eq!(m.context_after, vec!["This is a test note."]);
+    }
+
+    #[test]
+    fn literal_and_whole_word_search() {
+        let vault = create_test_vault();
+        std::fs::write(
+            vault.path().join("prices.md"),
+            "Costs $5 (approx.)\nA carton of cartons\n",
+        )
+        .unwrap();
+        let count = |pattern: &str, options: SearchOptions| -> usize {
+            search_vault(vault.path(), pattern, &options)
+                .unwrap()
+                .iter()
+                .map(|r| r.matches.len())
+                .sum()
+        };
+
+        let literal = SearchOptions {
+            literal: true,
+            ..Default::default()
+        };
+        assert_eq!(count("$5 (approx.)", literal.clone()), 1);
+        assert!(search_vault(vault.path(), "$5 (approx.", &SearchOptions::default()).is_err());
+
+        let whole_word = SearchOptions {
+            whole_word: true,
+            ..Default::default()
+        };
+        assert_eq!(count("carton", SearchOptions::default()), 1);
+        assert_eq!(count("cartons", whole_word.clone()), 1);
+        assert_eq!(count("arton", whole_word.clone()), 0);
+        assert_eq!(count("carton", whole_word), 1);
+    }
+
+    #[test]
+    fn searches_in_memory_contents() {
+        let files = [
+            ("daily/2025-01-01.md", "Met Alice\r\nTODO call Bob\n"),
+            ("projects/plan.md", "TODO draft\nTODO review\n"),
+            ("projects/notes.txt", "TODO ignored by file pattern\n"),
+        ];
+        let options = SearchOptions {
+            file_pattern: Some(r"\.md$".to_string()),
+            context_before: 1,
+            max_results: Some(2),
+            ..Default::default()
+        };
+
+        let results = search_contents(files, "TODO", &options).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].file_path, "daily/2025-01-01.md");
+        assert_eq!(results[0].matches[0].line_number, 2);
+        assert_eq!(results[0].matches[0].context_before, vec!["Met Alice"]);
+        assert_eq!(results[1].matches.len(), 1);
+    }
+
+    #[test]
+    fn cached_search_sees_edits() {
+        let vault = create_test_vault();
+        let cache = MetadataCache::new();
+        let options = SearchOptions::default();
+        let uncached = search_vault(vault.path(), "Hello", &options).unwrap();
+        let cached = search_vault_with_cache(vault.path(), "Hello", &options, &cache).unwrap();
+        assert_eq!(cached.len(), uncached.len());
+
+        let note = vault.path().join("note1.md");
+        std::fs::write(&note, "Goodbye only\n").unwrap();
+        let cached = search_vault_with_cache(vault.path(), "Goodbye", &options, &cache).unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].file_path, note.to_string_lossy());
+    }
 }
@@ -0,0 +1,322 @@
+use crate::links::{normalize_link_target, scan_vault_notes};
+use crate::resolver::LinkResolver;
+use crate::scan::NoteScan;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("vault path does not exist: {0}")]
+    VaultNotFound(String),
+    #[error("refusing to replace {0}: not a SQLite database (pass overwrite to replace it)")]
+    NotADatabase(String),
+    #[error("sqlite error: {0}")]
+    SqliteError(#[from] rusqlite::Error),
+    #[error("json error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportSummary {
+    pub notes: usize,
+    pub tags: usize,
+    pub properties: usize,
+    pub links: usize,
+}
+
+const SCHEMA: &str = "
+CREATE TABLE notes (
+    id INTEGER PRIMARY KEY,
+    path TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    title TEXT,
+    date TEXT,
+    word_count INTEGER NOT NULL
+);
+CREATE TABLE tags (
+    note_id INTEGER NOT NULL REFERENCES notes(id),
+    tag TEXT NOT NULL
+);
+CREATE TABLE properties (
+    note_id INTEGER NOT NULL REFERENCES notes(id),
+    key TEXT NOT NULL,
+    value TEXT NOT NULL
+);
+CREATE TABLE links (
+    source_id INTEGER NOT NULL REFERENCES notes(id),
+    target TEXT NOT NULL,
+    target_id INTEGER REFERENCES notes(id),
+    link_type TEXT NOT NULL
+);
+CREATE INDEX tags_tag ON tags(tag);
+CREATE INDEX links_target ON links(target);
+";
+
+/// Write notes, frontmatter properties, tags, links, and word counts into a
+/// fresh SQLite database at `db_path`. An existing file is only replaced if
+/// it is empty or a SQLite database, or if `overwrite` is set. The database
+/// is built next to `db_path` and renamed into place once complete.
+pub fn export_sqlite(
+    vault_path: &Path,
+    db_path: &Path,
+    overwrite: bool,
+) -> Result<ExportSummary, ExportError> {
+    if !vault_path.exists() {
+        return Err(ExportError::VaultNotFound(vault_path.display().to_string()));
+    }
+    if !replaceable(db_path, overwrite)? {
+        return Err(ExportError::NotADatabase(db_path.display().to_string()));
+    }
+    let Some(file_name) = db_path.file_name() else {
+        return Err(ExportError::NotADatabase(db_path.display().to_string()));
+    };
+
+    let scans = scan_vault_notes(vault_path);
+
+    let temp = db_path.with_file_name(format!(
+        ".{}.tmp{}",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+    if temp.exists() {
+        fs::remove_file(&temp)?;
+    }
+    match write_database(&temp, &scans) {
+        Ok(summary) => {
+            fs::rename(&temp, db_path)?;
+            Ok(summary)
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+/// Whether `path` is missing, or a file that may be replaced: an empty file,
+/// a SQLite database, or anything at all with `overwrite`.
+fn replaceable(path: &Path, overwrite: bool) -> Result<bool, ExportError> {
+    let meta = match fs::metadata(path) {
+        Ok(meta) => meta,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(true),
+        Err(e) => return Err(e.into()),
+    };
+    if !meta.is_file() {
+        return Ok(false);
+    }
+    if overwrite || meta.len() == 0 {
+        return Ok(true);
+    }
+    let mut header = [0u8; 16];
+    let read = fs::File::open(path)?.read(&mut header)?;
+    Ok(read == header.len() && &header == b"SQLite format 3\0")
+}
+
+fn write_database(
+    db_path: &Path,
+    scans: &[(String, NoteScan)],
+) -> Result<ExportSummary, ExportError> {
+    let mut conn = Connection::open(db_path)?;
+    conn.execute_batch(SCHEMA)?;
+    let tx = conn.transaction()?;
+    let mut summary = ExportSummary::default();
+
+    let mut note_ids = Vec::with_capacity(scans.len());
+    let mut ids_by_path: HashMap<&str, i64> = HashMap::new();
+    {
+        let mut insert_note = tx.prepare(
+            "INSERT INTO notes (path, name, title, date, word_count) VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        let mut insert_tag = tx.prepare("INSERT INTO tags (note_id, tag) VALUES (?1, ?2)")?;
+        let mut insert_property =
+            tx.prepare("INSERT INTO properties (note_id, key, value) VALUES (?1, ?2, ?3)")?;
+
+        for (path, scan) in scans {
+            let name = Path::new(path)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let fm = scan.frontmatter.as_ref();
+
+            insert_note.execute(params![
+                path,
+                name,
+                fm.and_then(|f| f.title.as_deref()),
+                fm.and_then(|f| f.date.as_deref()),
+                scan.word_count as i64,
+            ])?;
+            let id = tx.last_insert_rowid();
+            note_ids.push(id);
+            ids_by_path.insert(path, id);
+            summary.notes += 1;
+
+            for tag in scan.tags() {
+                insert_tag.execute(params![id, tag])?;
+                summary.tags += 1;
+            }
+
+            let Some(fm) = fm else { continue };
+
+            if let serde_json::Value::Object(map) = serde_json::to_value(fm)? {
+                for (key, value) in map {
+                    if key == "tags" || value.is_null() {
+                        continue;
+                    }
+                    let value = match value {
+                        serde_json::Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    insert_property.execute(params![id, key, value])?;
+                    summary.properties += 1;
+                }
+            }
+        }
+
+        let resolver = LinkResolver::from_notes(scans);
+        let mut insert_link = tx.prepare(
+            "INSERT INTO links (source_id, target, target_id, link_type) VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for ((path, scan), source_id) in scans.iter().zip(&note_ids) {
+            for link in &scan.links {
+                let target_id = resolver
+                    .lookup(&link.target, Some(path))
+                    .and_then(|target| ids_by_path.get(target));
+                insert_link.execute(params![
+                    source_id,
+                    normalize_link_target(&link.target),
+                    target_id,
+                    format!("{:?}", link.link_type).to_lowercase(),
+                ])?;
+                summary.links += 1;
+            }
+        }
+    }
+    tx.commit()?;
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils::create_test_vault;
+    use tempfile::TempDir;
+
+    #[test]
+    fn resolves_link_targets_by_path_and_alias() {
+        let vault = create_test_vault();
+        fs::create_dir_all(vault.path().join("work")).unwrap();
+        fs::write(vault.path().join("work/note2.md"), "Other\n").unwrap();
+        fs::write(
+            vault.path().join("aliased.md"),
+            "---\naliases: [Nickname]\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            vault.path().join("refs.md"),
+            "[[work/note2]] [[Nickname]] [[missing]]\n",
+        )
+        .unwrap();
+        let out = TempDir::new().unwrap();
+        let db = out.path().join("vault.db");
+
+        export_sqlite(vault.path(), &db, false).unwrap();
+
+        let conn = Connection::open(&db).unwrap();
+        let targets: Vec<(String, Option<String>)> = conn
+            .prepare(
+                "SELECT links.target, notes.path FROM links
+                 LEFT JOIN notes ON notes.id = links.target_id
+                 WHERE links.source_id = (SELECT id FROM notes WHERE path = 'refs.md')
+                 ORDER BY links.rowid",
+            )
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            targets,
+            vec![
+                ("work/note2".to_string(), Some("work/note2.md".to_string())),
+                ("nickname".to_string(), Some("aliased.md".to_string())),
+                ("missing".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn exports_notes_tags_and_links() {
+        let vault = create_test_vault();
+        let out = TempDir::new().unwrap();
+        let db = out.path().join("vault.db");
+
+        let summary = export_sqlite(vault.path(), &db, false).unwrap();
+        assert_eq!(summary.notes, 3);
+        assert_eq!(summary.tags, 2);
+        assert_eq!(summary.links, 2);
+
+        let conn = Connection::open(&db).unwrap();
+        let resolved: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM links WHERE target_id IS NOT NULL",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(resolved, 2);
+
+        let title: String = conn
+            .query_row(
+                "SELECT value FROM properties WHERE key = 'title' AND note_id =
+                 (SELECT id FROM notes WHERE path = 'note1.md')",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(title, "Note 1");
+    }
+
+    #[test]
+    fn replaces_only_databases_without_overwrite() {
+        let vault = create_test_vault();
+        let out = TempDir::new().unwrap();
+        let db = out.path().join("vault.db");
+
+        export_sqlite(vault.path(), &db, false).unwrap();
+        export_sqlite(vault.path(), &db, false).unwrap();
+
+        let notes = out.path().join("notes.txt");
+        fs::write(&notes, "keep me").unwrap();
+        assert!(matches!(
+            export_sqlite(vault.path(), &notes, false),
+            Err(ExportError::NotADatabase(_))
+        ));
+        assert_eq!(fs::read_to_string(&notes).unwrap(), "keep me");
+        assert!(matches!(
+            export_sqlite(vault.path(), out.path(), true),
+            Err(ExportError::NotADatabase(_))
+        ));
+
+        export_sqlite(vault.path(), &notes, true).unwrap();
+        assert!(Connection::open(&notes).is_ok());
+        let leftovers = fs::read_dir(out.path())
+            .unwrap()
+            .filter(|e| {
+                e.as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with('.')
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::links::scan_vault_notes;
+use crate::resolver::LinkResolver;
+use crate::scan::NoteScan;
+use crate::utils::parse_date;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use thiserror::Error;
+
+/// How many entries `largest_notes` and `busiest_directories` keep.
+const TOP_N: usize = 10;
+
+#[derive(Debug, Error)]
+pub enum StatsError {
+    #[error("vault path does not exist: {0}")]
+    VaultNotFound(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteSize {
+    pub path: String,
+    pub words: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectoryCount {
+    /// Vault-relative directory; empty for the vault root.
+    pub path: String,
+    pub notes: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VaultStats {
+    pub notes: usize,
+    pub words: usize,
+    /// Distinct tags across frontmatter and note bodies.
+    pub tags: usize,
+    pub links: usize,
+    /// Links whose target note doesn't exist.
+    pub unresolved_links: usize,
+    /// Notes with no resolved links in either direction.
+    pub orphans: usize,
+    pub average_words: f64,
+    pub largest_notes: Vec<NoteSize>,
+    /// Directories holding the most notes directly.
+    pub busiest_directories: Vec<DirectoryCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub notes: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthCount {
+    /// `YYYY-MM`.
+    pub month: String,
+    pub notes: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VaultAnalysis {
+    /// Notes per tag, most used first.
+    pub tags: Vec<TagCount>,
+    /// Notes per month of their frontmatter `date`, oldest first.
+    pub dates: Vec<MonthCount>,
+    /// Notes without a valid frontmatter `date`.
+    pub undated: usize,
+    /// Notes with no resolved links in either direction, sorted by path.
+    pub orphans: Vec<String>,
+}
+
+/// Tag frequencies, a monthly histogram of note dates, and the orphaned
+/// notes in a vault.
+pub fn analyze_vault(vault_path: &Path) -> Result<VaultAnalysis, StatsError> {
+    if !vault_path.exists() {
+        return Err(StatsError::VaultNotFound(vault_path.display().to_string()));
+    }
+    Ok(analyze_notes(&scan_vault_notes(vault_path)))
+}
+
+/// [`analyze_vault`] over notes already scanned, given as vault-relative
+/// paths.
+pub fn analyze_notes(notes: &[(String, NoteScan)]) -> VaultAnalysis {
+    let resolver = LinkResolver::from_notes(notes);
+    let mut analysis = VaultAnalysis::default();
+    let mut tags: HashMap<String, usize> = HashMap::new();
+    let mut months: BTreeMap<String, usize> = BTreeMap::new();
+    let mut connected: HashSet<&str> = HashSet::new();
+
+    for (path, scan) in notes {
+        for tag in scan.tags() {
+            *tags.entry(tag).or_default() += 1;
+        }
+
+        let date = scan.frontmatter.as_ref().and_then(|fm| fm.date.as_deref());
+        match date.and_then(month_of) {
+            Some(month) => *months.entry(month).or_default() += 1,
+            None => analysis.undated += 1,
+        }
+
+        for link in &scan.links {
+            if let Some(target) = resolver.lookup(&link.target, Some(path)) {
+                if target != path {
+                    connected.insert(path);
+                    connected.insert(target);
+                }
+            }
+        }
+    }
+
+    analysis.tags = tags
+        .into_iter()
+        .map(|(tag, notes)| TagCount { tag, notes })
+        .collect();
+    analysis
+        .tags
+        .sort_by(|a, b| b.notes.cmp(&a.notes).then(a.tag.cmp(&b.tag)));
+    analysis.dates = months
+        .into_iter()
+        .map(|(month, notes)| MonthCount { month, notes })
+        .collect();
+    analysis.orphans = notes
+        .iter()
+        .map(|(path, _)| path)
+        .filter(|path| !connected.contains(path.as_str()))
+        .cloned()
+        .collect();
+    analysis.orphans.sort();
+    analysis
+}
+
+/// `YYYY-MM` of a `YYYY-MM-DD` date, ignoring any time that follows.
+fn month_of(date: &str) -> Option<String> {
+    let day = date.trim().get(..10)?;
+    parse_date(day)?;
+    Some(day[..7].to_string())
+}
+
+/// Summarize a vault: totals, orphan count, and the largest notes and
+/// busiest directories.
+pub fn vault_stats(vault_path: &Path) -> Result<VaultStats, StatsError> {
+    if !vault_path.exists() {
+        return Err(StatsError::VaultNotFound(vault_path.display().to_string()));
+    }
+
+    let notes = scan_vault_notes(vault_path);
+    let resolver = LinkResolver::from_notes(&notes);
+
+    let mut stats = VaultStats {
+        notes: notes.len(),
+        ..Default::default()
+    };
+    let mut tags = HashSet::new();
+    let mut connected: HashSet<&str> = HashSet::new();
+    let mut directories: HashMap<String, usize> = HashMap::new();
+
+    for (path, scan) in &notes {
+        stats.words += scan.word_count;
+        stats.links += scan.links.len();
+        tags.extend(scan.tags());
+
+        for link in &scan.links {
+            match resolver.lookup(&link.target, Some(path)) {
+                Some(target) if target != path => {
+                    connected.insert(path);
+                    connected.insert(target);
+                }
+                Some(_) => {}
+                None => stats.unresolved_links += 1,
+            }
+        }
+
+        let dir = Path::new(path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        *directories.entry(dir).or_default() += 1;
+    }
+
+    stats.tags = tags.len();
+    stats.orphans = notes.len() - connected.len();
+    if stats.notes > 0 {
+        stats.average_words = stats.words as f64 / stats.notes as f64;
+    }
+
+    let mut largest: Vec<NoteSize> = notes
+        .iter()
+        .map(|(path, scan)| NoteSize {
+            path: path.clone(),
+            words: scan.word_count,
+        })
+        .collect();
+    largest.sort_by(|a, b| b.words.cmp(&a.words).then(a.path.cmp(&b.path)));
+    largest.truncate(TOP_N);
+    stats.largest_notes = largest;
+
+    let mut busiest: Vec<DirectoryCount> = directories
+        .into_iter()
+        .map(|(path, notes)| DirectoryCount { path, notes })
+        .collect();
+    busiest.sort_by(|a, b| b.notes.cmp(&a.notes).then(a.path.cmp(&b.path)));
+    busiest.truncate(TOP_N);
+    stats.busiest_directories = busiest;
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils::create_test_vault;
+
+    #[test]
+    fn computes_vault_stats() {
+        let vault = create_test_vault();
+        std::fs::write(
+            vault.path().join("lonely.md"),
+            "No links here, just #solo words and [[Missing]]\n",
+        )
+        .unwrap();
+
+        let stats = vault_stats(vault.path()).unwrap();
+
+        assert_eq!(stats.notes, 4);
+        assert_eq!(stats.tags, 3);
+        assert_eq!(stats.links, 3);
+        assert_eq!(stats.unresolved_links, 1);
+        assert_eq!(stats.orphans, 1);
+        assert_eq!(stats.largest_notes[0].path, "note1.md");
+        assert_eq!(stats.largest_notes[0].words, 11);
+        assert_eq!(stats.busiest_directories[0].path, "");
+        assert_eq!(stats.busiest_directories[0].notes, 3);
+        assert!(stats.average_words > 0.0);
+    }
+
+    #[test]
+    fn analyzes_tags_dates_and_orphans() {
+        let vault = create_test_vault();
+        std::fs::write(
+            vault.path().join("lonely.md"),
+            "---\ndate: 2025-01-20T09:30\ntags: [rust]\n---\nNo links, just #solo\n",
+        )
+        .unwrap();
+
+        let analysis = analyze_vault(vault.path()).unwrap();
+
+        assert_eq!(analysis.tags[0].tag, "rust");
+        assert_eq!(analysis.tags[0].notes, 2);
+        assert!(analysis
+            .tags
+            .iter()
+            .any(|t| t.tag == "solo" && t.notes == 1));
+        assert_eq!(analysis.dates[0].month, "2025-01");
+        assert_eq!(analysis.dates[0].notes, 2);
+        assert_eq!(analysis.undated, 2);
+        assert_eq!(analysis.orphans, vec!["lonely.md"]);
+    }
+
+    #[test]
+    fn rejects_missing_vault() {
+        assert!(vault_stats(Path::new("/nonexistent/vault/xyz")).is_err());
+        assert!(analyze_vault(Path::new("/nonexistent/vault/xyz")).is_err());
+    }
+}
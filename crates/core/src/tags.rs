@@ -0,0 +1,85 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+static INLINE_TAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|\s)#([\p{L}\p{N}_/-]+)").unwrap());
+
+/// Extract Obsidian `#tags` from note content, skipping fenced code blocks.
+/// Tags are returned without the leading `#`, deduplicated in order of appearance.
+pub fn extract_inline_tags(content: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut in_code_block = false;
+    for line in content.lines() {
+        if is_code_fence(line) {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            collect_line_tags(line, &mut tags);
+        }
+    }
+    tags
+}
+
+/// Combine frontmatter and inline tags, keeping the first occurrence of each.
+pub fn merge_tags(frontmatter: &[String], inline: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::with_capacity(frontmatter.len() + inline.len());
+    for tag in frontmatter.iter().chain(inline) {
+        let tag = tag.trim_start_matches('#');
+        if !merged.iter().any(|t| t == tag) {
+            merged.push(tag.to_string());
+        }
+    }
+    merged
+}
+
+pub(crate) fn is_code_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Append tags found on a single line, skipping inline code spans and
+/// purely numeric tags like `#123`, which Obsidian doesn't treat as tags.
+pub(crate) fn collect_line_tags(line: &str, tags: &mut Vec<String>) {
+    if !line.contains('#') {
+        return;
+    }
+    for (i, segment) in line.split('`').enumerate() {
+        if i % 2 == 1 {
+            continue;
+        }
+        for cap in INLINE_TAG_RE.captures_iter(segment) {
+            let tag = cap[1].trim_end_matches('/');
+            if tag.chars().all(|c| c.is_ascii_digit()) || tags.iter().any(|t| t == tag) {
+                continue;
+            }
+            tags.push(tag.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_inline_tags() {
+        let content =
+            "Working on #rust and #project/vault-tree.\n# Heading\nIssue #42, again #rust";
+        assert_eq!(
+            extract_inline_tags(content),
+            vec!["rust", "project/vault-tree"]
+        );
+    }
+
+    #[test]
+    fn skips_code_and_anchors() {
+        let content = "See [link](page#anchor) and `#not-a-tag`\n```\n#also-not\n```\n#real";
+        assert_eq!(extract_inline_tags(content), vec!["real"]);
+    }
+
+    #[test]
+    fn merges_without_duplicates() {
+        let fm = vec!["rust".to_string(), "#mcp".to_string()];
+        let inline = vec!["mcp".to_string(), "notes".to_string()];
+        assert_eq!(merge_tags(&fm, &inline), vec!["rust", "mcp", "notes"]);
+    }
+}
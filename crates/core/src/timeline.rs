@@ -0,0 +1,225 @@
+use crate::cache::MetadataCache;
+use crate::exclude::ExcludeSet;
+use crate::tree::{
+    collect_metadata, matching_notes, FileMetadata, TreeError, TreeOptions, VaultNode, VaultTree,
+};
+use crate::utils::{count_totals, parse_date};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+static DAILY_NOTE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{4}-\d{2}-\d{2})").unwrap());
+
+/// Group notes chronologically into `YYYY/YYYY-MM/` directories, using the
+/// frontmatter `date` or a daily-note file name like `2024-05-01.md`.
+/// Undated notes are summarized in a trailing `undated` directory.
+pub fn generate_timeline(vault_path: &Path) -> Result<VaultTree, TreeError> {
+    generate_timeline_with_cache(vault_path, &TreeOptions::default(), &MetadataCache::new())
+}
+
+/// [`generate_timeline`] honouring the tree's `exclude`, filter, and `depth`
+/// options (years are at depth 1, months at depth 2), reusing note scans
+/// from `cache`.
+pub fn generate_timeline_with_cache(
+    vault_path: &Path,
+    options: &TreeOptions,
+    cache: &MetadataCache,
+) -> Result<VaultTree, TreeError> {
+    if !vault_path.exists() {
+        return Err(TreeError::VaultNotFound(vault_path.display().to_string()));
+    }
+
+    let exclude = ExcludeSet::load(vault_path, &options.exclude)?;
+    let (mut metadata_map, _) =
+        collect_metadata(vault_path, &exclude, options.link_normalization, cache);
+    if options.has_filters() {
+        let matching = matching_notes(options, &metadata_map)?;
+        metadata_map.retain(|path, _| matching.contains(path));
+    }
+
+    // year -> month -> (date, note)
+    let mut years: BTreeMap<String, BTreeMap<String, Vec<(String, VaultNode)>>> = BTreeMap::new();
+    let mut undated = 0;
+
+    for (path, metadata) in metadata_map {
+        let Some(date) = note_date(&path, &metadata) else {
+            undated += 1;
+            continue;
+        };
+        let relative = path.strip_prefix(vault_path).unwrap_or(&path);
+        let node = VaultNode {
+            path: relative.to_string_lossy().to_string(),
+            name: file_name(&path),
+            is_dir: false,
+            metadata: Some(metadata),
+            children: vec![],
+            note_count: 0,
+            is_attachment: false,
+            attachment_count: 0,
+            size: 0,
+        };
+        years
+            .entry(date[..4].to_string())
+            .or_default()
+            .entry(date[..7].to_string())
+            .or_default()
+            .push((date, node));
+    }
+
+    let mut children: Vec<VaultNode> = years
+        .into_iter()
+        .map(|(year, months)| {
+            let months = months
+                .into_iter()
+                .map(|(month, mut notes)| {
+                    notes.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.path.cmp(&b.1.path)));
+                    let notes: Vec<VaultNode> = notes.into_iter().map(|(_, n)| n).collect();
+                    let count = notes.len();
+                    dir_node(&format!("{}/{}", year, month), &month, notes, count)
+                })
+                .collect::<Vec<_>>();
+            let count = months.iter().map(|m| m.note_count).sum();
+            dir_node(&year, &year, months, count)
+        })
+        .collect();
+
+    if undated > 0 {
+        children.push(dir_node("undated", "undated", vec![], undated));
+    }
+
+    let note_count = children.iter().map(|c| c.note_count).sum();
+    let mut root = dir_node("", &file_name(vault_path), children, note_count);
+    if let Some(depth) = options.depth {
+        collapse_below(&mut root, depth);
+    }
+    let (total_notes, total_dirs) = count_totals(&root);
+
+    Ok(VaultTree {
+        root,
+        total_notes: total_notes + undated,
+        total_dirs,
+    })
+}
+
+/// `YYYY-MM-DD` for a note, preferring frontmatter over the file name.
+fn note_date(path: &Path, metadata: &FileMetadata) -> Option<String> {
+    let from_frontmatter = metadata
+        .frontmatter
+        .as_ref()
+        .and_then(|fm| fm.date.as_deref())
+        .and_then(|d| d.get(..10));
+    let from_name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .and_then(|stem| DAILY_NOTE_RE.captures(stem))
+        .map(|caps| caps.get(1).unwrap().as_str());
+
+    [from_frontmatter, from_name]
+        .into_iter()
+        .flatten()
+        .find(|d| parse_date(d).is_some())
+        .map(str::to_string)
+}
+
+/// Drop the children of directories `depth` levels down, keeping their
+/// note counts, as the tree does past its depth limit.
+fn collapse_below(node: &mut VaultNode, depth: usize) {
+    if depth == 0 {
+        node.children.clear();
+        return;
+    }
+    for child in &mut node.children {
+        collapse_below(child, depth - 1);
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+fn dir_node(path: &str, name: &str, children: Vec<VaultNode>, note_count: usize) -> VaultNode {
+    VaultNode {
+        path: PathBuf::from(path).to_string_lossy().to_string(),
+        name: name.to_string(),
+        is_dir: true,
+        metadata: None,
+        children,
+        note_count,
+        is_attachment: false,
+        attachment_count: 0,
+        size: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_tree;
+    use crate::testutils::create_test_vault;
+    use std::fs;
+
+    #[test]
+    fn groups_notes_by_date() {
+        let vault = create_test_vault();
+        fs::create_dir(vault.path().join("daily")).unwrap();
+        fs::write(vault.path().join("daily/2025-01-05.md"), "Journal\n").unwrap();
+        fs::write(vault.path().join("daily/2024-12-31.md"), "NYE\n").unwrap();
+
+        let timeline = generate_timeline(vault.path()).unwrap();
+        let years: Vec<&str> = timeline
+            .root
+            .children
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+
+        assert_eq!(years, vec!["2024", "2025", "undated"]);
+        assert_eq!(timeline.total_notes, 5);
+
+        let january = &timeline.root.children[1].children[0];
+        assert_eq!(january.name, "2025-01");
+        let names: Vec<&str> = january.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["2025-01-05.md", "note1.md"]);
+
+        let rendered = render_tree(&timeline);
+        assert!(rendered.contains("undated/ (2 notes)"));
+    }
+
+    #[test]
+    fn honours_exclude_filters_and_depth() {
+        let vault = create_test_vault();
+        fs::create_dir(vault.path().join("private")).unwrap();
+        fs::write(vault.path().join("private/2025-01-05.md"), "Secret\n").unwrap();
+        fs::write(vault.path().join("2025-01-06.md"), "#work day\n").unwrap();
+        let cache = MetadataCache::new();
+        let timeline = |options: &TreeOptions| {
+            generate_timeline_with_cache(vault.path(), options, &cache).unwrap()
+        };
+
+        let excluded = timeline(&TreeOptions {
+            exclude: vec!["private".to_string()],
+            ..Default::default()
+        });
+        let january = &excluded.root.children[0].children[0];
+        let names: Vec<&str> = january.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["2025-01-06.md", "note1.md"]);
+
+        let tagged = timeline(&TreeOptions {
+            filter_tags: vec!["work".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(tagged.total_notes, 1);
+
+        let years = timeline(&TreeOptions {
+            depth: Some(1),
+            ..Default::default()
+        });
+        let year = &years.root.children[0];
+        assert_eq!((year.name.as_str(), year.note_count), ("2025", 3));
+        assert!(year.children.is_empty());
+    }
+}
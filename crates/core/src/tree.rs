@@ -1,15 +1,22 @@
-use crate::frontmatter::{extract_frontmatter, Frontmatter};
-use crate::links::{extract_links, normalize_link_target, LinkIndex};
+use crate::cache::MetadataCache;
+use crate::canvas::{scan_vault_canvases, CanvasRefs};
+use crate::exclude::{ExcludeError, ExcludeSet};
+use crate::frontmatter::Frontmatter;
+use crate::links::{LinkIndex, LinkNormalization};
+use crate::resolver::LinkResolver;
+use crate::scan::NoteScan;
 use crate::utils::{
-    compare_dir_entries, count_totals, is_excluded, node_annotation, render_tree_ascii,
-    sum_child_notes, walk_markdown_files, TreeRenderable,
+    compare_dir_entries, count_totals, format_size, node_annotation, parse_date, write_tree_ascii,
+    TreeRenderable,
 };
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
+use walkdir::WalkDir;
 
 #[derive(Debug, Error)]
 pub enum TreeError {
@@ -17,11 +24,20 @@ pub enum TreeError {
     VaultNotFound(String),
     #[error("io error: {0}")]
     IoError(#[from] std::io::Error),
+    #[error("serialization error: {0}")]
+    SerializeError(String),
+    #[error("invalid date (expected YYYY-MM-DD): {0}")]
+    InvalidDate(String),
+    #[error(transparent)]
+    Exclude(#[from] ExcludeError),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileMetadata {
     pub frontmatter: Option<Frontmatter>,
+    /// Frontmatter and inline body tags combined.
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub outgoing_links: usize,
     pub incoming_links: usize,
 }
@@ -36,6 +52,15 @@ pub struct VaultNode {
     pub children: Vec<VaultNode>,
     #[serde(default)]
     pub note_count: usize,
+    /// Non-markdown file, present when `include_attachments` is set.
+    #[serde(default)]
+    pub is_attachment: bool,
+    /// Attachments in this directory and below.
+    #[serde(default)]
+    pub attachment_count: usize,
+    /// Attachment size in bytes; for directories, the recursive total.
+    #[serde(default)]
+    pub size: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +73,47 @@ pub struct VaultTree {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TreeOptions {
     pub depth: Option<usize>,
+    #[serde(default)]
+    pub format: TreeFormat,
+    /// Extra exclude globs, applied along with the vault's `.vaultignore`.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Show non-markdown files (images, PDFs, canvases) with sizes.
+    #[serde(default)]
+    pub include_attachments: bool,
+    /// Keep notes carrying any of these tags (nested tags like `project/x`
+    /// match `project`).
+    #[serde(default)]
+    pub filter_tags: Vec<String>,
+    /// Keep notes modified on or after this `YYYY-MM-DD` date (UTC).
+    pub modified_after: Option<String>,
+    /// Keep notes modified before this `YYYY-MM-DD` date (UTC).
+    pub modified_before: Option<String>,
+    /// Keep notes with at least this many incoming links.
+    pub min_incoming_links: Option<usize>,
+    /// How link targets are matched to notes when counting links.
+    #[serde(default)]
+    pub link_normalization: LinkNormalization,
+}
+
+impl TreeOptions {
+    pub(crate) fn has_filters(&self) -> bool {
+        !self.filter_tags.is_empty()
+            || self.modified_after.is_some()
+            || self.modified_before.is_some()
+            || self.min_incoming_links.is_some()
+    }
+}
+
+/// Output format for [`render_tree_as`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TreeFormat {
+    #[default]
+    Ascii,
+    Json,
+    Yaml,
+    Markdown,
 }
 
 impl TreeRenderable for VaultNode {
@@ -63,21 +129,30 @@ impl TreeRenderable for VaultNode {
         &self.children
     }
 
+    fn is_note(&self) -> bool {
+        !self.is_dir && !self.is_attachment
+    }
+
     fn annotation(&self) -> String {
+        if self.is_attachment {
+            return format!("  {}", format_size(self.size));
+        }
+
         let (tags, date, incoming, outgoing) = self
             .metadata
             .as_ref()
             .map(|meta| {
-                let (tags, date) = meta
-                    .frontmatter
-                    .as_ref()
-                    .map(|fm| (fm.tags.as_slice(), fm.date.as_deref()))
-                    .unwrap_or((&[], None));
-                (tags, date, meta.incoming_links, meta.outgoing_links)
+                let date = meta.frontmatter.as_ref().and_then(|fm| fm.date.as_deref());
+                (
+                    meta.tags.as_slice(),
+                    date,
+                    meta.incoming_links,
+                    meta.outgoing_links,
+                )
             })
             .unwrap_or((&[], None, 0, 0));
 
-        node_annotation(
+        let annotation = node_annotation(
             self.is_dir,
             self.note_count,
             !self.children.is_empty(),
@@ -85,91 +160,255 @@ impl TreeRenderable for VaultNode {
             date,
             incoming,
             outgoing,
-        )
+        );
+
+        if self.is_dir && self.attachment_count > 0 {
+            format!(
+                "{} [{} attachments, {}]",
+                annotation,
+                self.attachment_count,
+                format_size(self.size)
+            )
+        } else {
+            annotation
+        }
     }
 }
 
 pub fn generate_tree(vault_path: &Path, options: &TreeOptions) -> Result<VaultTree, TreeError> {
+    generate_tree_with_cache(vault_path, options, &MetadataCache::new())
+}
+
+/// [`generate_tree`], reusing note scans from `cache` for files whose size
+/// and mtime haven't changed.
+pub fn generate_tree_with_cache(
+    vault_path: &Path,
+    options: &TreeOptions,
+    cache: &MetadataCache,
+) -> Result<VaultTree, TreeError> {
+    generate_tree_with_links(vault_path, options, cache).map(|(tree, _)| tree)
+}
+
+/// Build the tree along with the link index used to annotate it.
+pub(crate) fn generate_tree_with_links(
+    vault_path: &Path,
+    options: &TreeOptions,
+    cache: &MetadataCache,
+) -> Result<(VaultTree, LinkIndex), TreeError> {
     if !vault_path.exists() {
         return Err(TreeError::VaultNotFound(vault_path.display().to_string()));
     }
 
-    let md_files = collect_markdown_files(vault_path);
-    let link_index = build_link_index(vault_path, &md_files);
-    let metadata_map = build_metadata_map(&md_files, &link_index);
-
-    let root = build_tree_node(vault_path, vault_path, options, 0, &metadata_map)?;
+    let exclude = ExcludeSet::load(vault_path, &options.exclude)?;
+    let (metadata_map, link_index) =
+        collect_metadata(vault_path, &exclude, options.link_normalization, cache);
+    let filter = if options.has_filters() {
+        Some(matching_notes(options, &metadata_map)?)
+    } else {
+        None
+    };
+
+    let ctx = TreeContext {
+        vault_path,
+        options,
+        metadata_map: &metadata_map,
+        filter: filter.as_ref(),
+        exclude: &exclude,
+    };
+    let root = build_tree_node(&ctx, vault_path, 0)?;
 
     let (total_notes, total_dirs) = count_totals(&root);
 
-    Ok(VaultTree {
-        root,
-        total_notes,
-        total_dirs,
-    })
+    Ok((
+        VaultTree {
+            root,
+            total_notes,
+            total_dirs,
+        },
+        link_index,
+    ))
 }
 
-fn collect_markdown_files(vault_path: &Path) -> Vec<PathBuf> {
-    walk_markdown_files(vault_path)
+/// Scan every note once and annotate it with tags and link counts.
+pub(crate) fn collect_metadata(
+    vault_path: &Path,
+    exclude: &ExcludeSet,
+    norm: LinkNormalization,
+    cache: &MetadataCache,
+) -> (HashMap<PathBuf, FileMetadata>, LinkIndex) {
+    let md_files = collect_markdown_files(vault_path, exclude);
+    let scans = scan_notes(&md_files, cache);
+    cache.prune_under(vault_path);
+    let canvases = scan_vault_canvases(vault_path, exclude);
+    let link_index = build_link_index(vault_path, &scans, &canvases, norm);
+    (
+        build_metadata_map(vault_path, scans, &link_index),
+        link_index,
+    )
+}
+
+fn collect_markdown_files(vault_path: &Path, exclude: &ExcludeSet) -> Vec<PathBuf> {
+    exclude
+        .walk_markdown(vault_path, vault_path)
         .map(|e| e.path().to_path_buf())
         .collect()
 }
 
-fn build_link_index(vault_path: &Path, files: &[PathBuf]) -> LinkIndex {
-    let file_links: Vec<(String, Vec<String>)> = files
+/// Read every changed note exactly once, keeping only frontmatter and link
+/// targets.
+fn scan_notes(files: &[PathBuf], cache: &MetadataCache) -> Vec<(PathBuf, NoteScan)> {
+    files
         .par_iter()
-        .filter_map(|path| {
-            let content = fs::read_to_string(path).ok()?;
-            let links = extract_links(&content);
-            let from = path
-                .strip_prefix(vault_path)
-                .ok()?
-                .to_string_lossy()
-                .to_string();
-            let targets: Vec<String> = links
-                .iter()
-                .map(|l| normalize_link_target(&l.target))
-                .collect();
-            Some((from, targets))
-        })
+        .filter_map(|path| Some((path.clone(), cache.scan(path).ok()?)))
+        .collect()
+}
+
+/// Links between notes, keyed by vault-relative path and resolved like the
+/// link graph. Links to missing notes are left out.
+fn build_link_index(
+    vault_path: &Path,
+    scans: &[(PathBuf, NoteScan)],
+    canvases: &[(String, CanvasRefs)],
+    norm: LinkNormalization,
+) -> LinkIndex {
+    let relative: Vec<(String, &NoteScan)> = scans
+        .iter()
+        .filter_map(|(path, scan)| Some((relative_path(vault_path, path)?, scan)))
         .collect();
+    let notes: Vec<(&str, &NoteScan)> = relative.iter().map(|(p, s)| (p.as_str(), *s)).collect();
+    let resolver = LinkResolver::from_scans(&notes, norm);
 
     let mut index = LinkIndex::new();
-    for (from, targets) in file_links {
-        let from_normalized = normalize_link_target(&from);
-        for target in targets {
-            index.add_link(&from_normalized, &target);
+    for (source, scan) in &notes {
+        for link in &scan.links {
+            if let Some(target) = resolver.lookup(&link.target, Some(source)) {
+                index.add_link(source, target);
+            }
+        }
+    }
+    for (canvas, refs) in canvases {
+        let resolve = |file: &str| resolver.lookup(file, Some(canvas));
+        for target in refs.files.iter().filter_map(|file| resolve(file)) {
+            index.add_link(canvas, target);
+        }
+        for (from, to) in &refs.connections {
+            if let (Some(from), Some(to)) = (resolve(from), resolve(to)) {
+                index.add_link(from, to);
+            }
         }
     }
     index
 }
 
-fn build_metadata_map(files: &[PathBuf], link_index: &LinkIndex) -> HashMap<PathBuf, FileMetadata> {
-    files
-        .par_iter()
-        .filter_map(|path| {
-            let content = fs::read_to_string(path).ok()?;
-            let frontmatter = extract_frontmatter(&content).ok();
-            let normalized = normalize_link_target(path.file_stem()?.to_str()?);
+fn relative_path(vault_path: &Path, path: &Path) -> Option<String> {
+    Some(
+        path.strip_prefix(vault_path)
+            .ok()?
+            .to_string_lossy()
+            .to_string(),
+    )
+}
+
+fn build_metadata_map(
+    vault_path: &Path,
+    scans: Vec<(PathBuf, NoteScan)>,
+    link_index: &LinkIndex,
+) -> HashMap<PathBuf, FileMetadata> {
+    scans
+        .into_par_iter()
+        .filter_map(|(path, scan)| {
+            let relative = relative_path(vault_path, &path)?;
 
             let metadata = FileMetadata {
-                frontmatter,
-                outgoing_links: link_index.outgoing_count(&normalized),
-                incoming_links: link_index.incoming_count(&normalized),
+                tags: scan.tags(),
+                frontmatter: scan.frontmatter,
+                outgoing_links: link_index.outgoing_count(&relative),
+                incoming_links: link_index.incoming_count(&relative),
             };
 
-            Some((path.clone(), metadata))
+            Some((path, metadata))
         })
         .collect()
 }
 
+/// Notes passing every filter in `options`.
+pub(crate) fn matching_notes(
+    options: &TreeOptions,
+    metadata_map: &HashMap<PathBuf, FileMetadata>,
+) -> Result<HashSet<PathBuf>, TreeError> {
+    let parse = |date: &Option<String>| {
+        date.as_deref()
+            .map(|d| parse_date(d).ok_or_else(|| TreeError::InvalidDate(d.to_string())))
+            .transpose()
+    };
+    let after = parse(&options.modified_after)?;
+    let before = parse(&options.modified_before)?;
+    let wanted: Vec<&str> = options
+        .filter_tags
+        .iter()
+        .map(|t| t.trim_start_matches('#'))
+        .collect();
+
+    Ok(metadata_map
+        .par_iter()
+        .filter(|(_, meta)| {
+            wanted.is_empty()
+                || meta
+                    .tags
+                    .iter()
+                    .any(|tag| wanted.iter().any(|w| tag_matches(tag, w)))
+        })
+        .filter(|(_, meta)| {
+            options
+                .min_incoming_links
+                .is_none_or(|min| meta.incoming_links >= min)
+        })
+        .filter(|(path, _)| {
+            if after.is_none() && before.is_none() {
+                return true;
+            }
+            let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) else {
+                return false;
+            };
+            after.is_none_or(|a| modified >= a) && before.is_none_or(|b| modified < b)
+        })
+        .map(|(path, _)| path.clone())
+        .collect())
+}
+
+/// Case-insensitive tag match where `project` also matches `project/sub`.
+pub(crate) fn tag_matches(tag: &str, wanted: &str) -> bool {
+    let tag = tag.to_lowercase();
+    let wanted = wanted.to_lowercase();
+    tag == wanted
+        || tag
+            .strip_prefix(&wanted)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Shared state for the recursive tree walk.
+struct TreeContext<'a> {
+    vault_path: &'a Path,
+    options: &'a TreeOptions,
+    metadata_map: &'a HashMap<PathBuf, FileMetadata>,
+    /// Notes passing the option filters; `None` when no filter is set.
+    filter: Option<&'a HashSet<PathBuf>>,
+    exclude: &'a ExcludeSet,
+}
+
 fn build_tree_node(
-    vault_path: &Path,
+    ctx: &TreeContext,
     current_path: &Path,
-    options: &TreeOptions,
     depth: usize,
-    metadata_map: &HashMap<PathBuf, FileMetadata>,
 ) -> Result<VaultNode, TreeError> {
+    let TreeContext {
+        vault_path,
+        options,
+        metadata_map,
+        filter,
+        exclude,
+    } = *ctx;
+
     let name = current_path
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
@@ -182,20 +421,39 @@ fn build_tree_node(
         .to_string();
 
     if current_path.is_file() {
-        let metadata = metadata_map.get(current_path).cloned();
+        let is_attachment = !is_note_path(current_path);
+        let size = if is_attachment {
+            fs::metadata(current_path)?.len()
+        } else {
+            0
+        };
         return Ok(VaultNode {
             path: relative_path,
             name,
             is_dir: false,
-            metadata,
+            metadata: metadata_map.get(current_path).cloned(),
             children: vec![],
             note_count: 0,
+            is_attachment,
+            attachment_count: 0,
+            size,
         });
     }
 
     if let Some(max_depth) = options.depth {
         if depth >= max_depth {
-            let note_count = count_notes_recursive(current_path);
+            let note_count = match filter {
+                Some(filter) => filter
+                    .iter()
+                    .filter(|p| p.starts_with(current_path))
+                    .count(),
+                None => exclude.walk_markdown(vault_path, current_path).count(),
+            };
+            let (attachment_count, size) = if options.include_attachments {
+                attachment_totals(ctx, current_path)
+            } else {
+                (0, 0)
+            };
             return Ok(VaultNode {
                 path: relative_path,
                 name,
@@ -203,26 +461,59 @@ fn build_tree_node(
                 metadata: None,
                 children: vec![],
                 note_count,
+                is_attachment: false,
+                attachment_count,
+                size,
             });
         }
     }
 
     let mut entries: Vec<_> = fs::read_dir(current_path)?
         .filter_map(|e| e.ok())
-        .filter(|e| !is_excluded(&e.path()))
-        .filter(|e| e.path().is_dir() || e.path().extension().is_some_and(|ext| ext == "md"))
+        .filter(|e| !exclude.is_excluded(vault_path, &e.path()))
+        .filter(|e| {
+            let path = e.path();
+            if path.is_dir() {
+                true
+            } else if is_note_path(&path) {
+                filter.is_none_or(|f| f.contains(&path))
+            } else {
+                options.include_attachments && is_attachment_path(&path)
+            }
+        })
         .collect();
 
     entries.sort_by(compare_dir_entries);
 
+    // Sibling subtrees are walked in parallel; `collect` keeps their order.
     let children: Vec<VaultNode> = entries
-        .into_iter()
-        .filter_map(|entry| {
-            build_tree_node(vault_path, &entry.path(), options, depth + 1, metadata_map).ok()
+        .into_par_iter()
+        .filter_map(|entry| build_tree_node(ctx, &entry.path(), depth + 1).ok())
+        // A filtered view drops directories with nothing left in them.
+        .filter(|child| {
+            filter.is_none() || !child.is_dir || child.note_count > 0 || child.attachment_count > 0
         })
         .collect();
 
-    let note_count = sum_child_notes(&children, |c| c.is_dir, |c| c.note_count);
+    let note_count = children
+        .iter()
+        .map(|c| match (c.is_dir, c.is_attachment) {
+            (true, _) => c.note_count,
+            (false, true) => 0,
+            (false, false) => 1,
+        })
+        .sum();
+    let attachment_count = children
+        .iter()
+        .map(|c| {
+            if c.is_attachment {
+                1
+            } else {
+                c.attachment_count
+            }
+        })
+        .sum();
+    let size = children.iter().map(|c| c.size).sum();
 
     Ok(VaultNode {
         path: relative_path,
@@ -231,20 +522,144 @@ fn build_tree_node(
         metadata: None,
         children,
         note_count,
+        is_attachment: false,
+        attachment_count,
+        size,
     })
 }
 
-fn count_notes_recursive(path: &Path) -> usize {
-    walk_markdown_files(path).count()
+fn is_note_path(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "md")
 }
 
-pub fn render_tree(tree: &VaultTree) -> String {
-    let mut output = render_tree_ascii(&tree.root, "", true);
-    output.push_str(&format!(
+/// Non-markdown files, skipping hidden ones like `.DS_Store`.
+fn is_attachment_path(path: &Path) -> bool {
+    !is_note_path(path)
+        && path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| !n.starts_with('.'))
+}
+
+/// Count and total size of attachments under `path`.
+fn attachment_totals(ctx: &TreeContext, path: &Path) -> (usize, u64) {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|e| !ctx.exclude.is_excluded(ctx.vault_path, e.path()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && is_attachment_path(e.path()))
+        .fold((0, 0), |(count, size), e| {
+            (count + 1, size + e.metadata().map_or(0, |m| m.len()))
+        })
+}
+
+/// Render a tree in the requested format. JSON and YAML serialize the full
+/// [`VaultTree`], including per-note metadata.
+pub fn render_tree_as(tree: &VaultTree, format: TreeFormat) -> Result<String, TreeError> {
+    let mut output = Vec::new();
+    render_tree_as_streaming(tree, format, &mut output)?;
+    Ok(String::from_utf8(output).expect("tree output is UTF-8"))
+}
+
+/// Write a tree in the requested format to `out` as it is rendered, so huge
+/// vaults never need the whole output in memory.
+pub fn render_tree_as_streaming<W: Write>(
+    tree: &VaultTree,
+    format: TreeFormat,
+    out: W,
+) -> Result<(), TreeError> {
+    match format {
+        TreeFormat::Ascii => render_tree_streaming(tree, out)?,
+        TreeFormat::Json => serde_json::to_writer_pretty(out, tree)
+            .map_err(|e| TreeError::SerializeError(e.to_string()))?,
+        TreeFormat::Yaml => serde_yaml::to_writer(out, tree)
+            .map_err(|e| TreeError::SerializeError(e.to_string()))?,
+        TreeFormat::Markdown => render_tree_markdown_streaming(tree, out)?,
+    }
+    Ok(())
+}
+
+/// A nested bullet list with a `[[wikilink]]` to every note, ready to save
+/// as an index note inside the vault.
+pub fn render_tree_markdown(tree: &VaultTree) -> String {
+    let mut output = Vec::new();
+    render_tree_markdown_streaming(tree, &mut output).expect("writing to a Vec cannot fail");
+    String::from_utf8(output).expect("tree names are UTF-8")
+}
+
+/// Write [`render_tree_markdown`] output to `out` line by line.
+pub fn render_tree_markdown_streaming<W: Write>(
+    tree: &VaultTree,
+    mut out: W,
+) -> std::io::Result<()> {
+    write_bullets(&tree.root, 0, &mut out)?;
+    write_footer(tree, &mut out)
+}
+
+fn write_bullets<W: Write>(node: &VaultNode, depth: usize, out: &mut W) -> std::io::Result<()> {
+    let indent = "  ".repeat(depth);
+    let annotation = node.annotation();
+    let annotation = annotation.trim();
+    let separator = if annotation.is_empty() { "" } else { " " };
+    if node.is_dir {
+        writeln!(
+            out,
+            "{}- **{}/**{}{}",
+            indent, node.name, separator, annotation
+        )?;
+    } else {
+        writeln!(
+            out,
+            "{}- {}{}`{}`",
+            indent,
+            wikilink(node),
+            separator,
+            annotation
+        )?;
+    }
+    for child in &node.children {
+        write_bullets(child, depth + 1, out)?;
+    }
+    Ok(())
+}
+
+/// `[[path]]` for a note, shown by name when it sits in a folder. Notes drop
+/// their `.md` extension; attachments keep theirs.
+fn wikilink(node: &VaultNode) -> String {
+    let path = node.path.replace('\\', "/");
+    let (target, name) = if node.is_attachment {
+        (path.as_str(), node.name.as_str())
+    } else {
+        (
+            path.strip_suffix(".md").unwrap_or(&path),
+            node.name.strip_suffix(".md").unwrap_or(&node.name),
+        )
+    };
+    if target == name {
+        format!("[[{}]]", target)
+    } else {
+        format!("[[{}|{}]]", target, name)
+    }
+}
+
+fn write_footer<W: Write>(tree: &VaultTree, out: &mut W) -> std::io::Result<()> {
+    write!(
+        out,
         "\n{} notes, {} directories\n",
         tree.total_notes, tree.total_dirs
-    ));
-    output
+    )
+}
+
+pub fn render_tree(tree: &VaultTree) -> String {
+    let mut output = Vec::new();
+    render_tree_streaming(tree, &mut output).expect("writing to a Vec cannot fail");
+    String::from_utf8(output).expect("tree names are UTF-8")
+}
+
+/// Write the ASCII tree to `out` line by line; see [`render_tree`].
+pub fn render_tree_streaming<W: Write>(tree: &VaultTree, mut out: W) -> std::io::Result<()> {
+    write_tree_ascii(&tree.root, "", true, &mut out)?;
+    write_footer(tree, &mut out)
 }
 
 #[cfg(test)]
@@ -252,6 +667,29 @@ mod tests {
     use super::*;
     use crate::testutils::create_test_vault;
 
+    #[test]
+    fn cache_forgets_deleted_notes() {
+        let vault = create_test_vault();
+        let cache = MetadataCache::new();
+        generate_tree_with_cache(vault.path(), &TreeOptions::default(), &cache).unwrap();
+        assert_eq!(cache.len(), 3);
+
+        std::fs::remove_file(vault.path().join("note1.md")).unwrap();
+        generate_tree_with_cache(vault.path(), &TreeOptions::default(), &cache).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        // Another vault, or an excluding walk, doesn't evict entries.
+        let other = create_test_vault();
+        generate_tree_with_cache(other.path(), &TreeOptions::default(), &cache).unwrap();
+        assert_eq!(cache.len(), 5);
+        let excluding = TreeOptions {
+            exclude: vec!["subdir".to_string()],
+            ..Default::default()
+        };
+        generate_tree_with_cache(vault.path(), &excluding, &cache).unwrap();
+        assert_eq!(cache.len(), 5);
+    }
+
     #[test]
     fn generates_tree() {
         let vault = create_test_vault();
@@ -264,7 +702,14 @@ mod tests {
     #[test]
     fn respects_depth_limit() {
         let vault = create_test_vault();
-        let tree = generate_tree(vault.path(), &TreeOptions { depth: Some(1) }).unwrap();
+        let tree = generate_tree(
+            vault.path(),
+            &TreeOptions {
+                depth: Some(1),
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
         let subdir = tree
             .root
@@ -277,6 +722,230 @@ mod tests {
         assert_eq!(subdir.note_count, 1);
     }
 
+    #[test]
+    fn resolves_links_through_aliases() {
+        let vault = create_test_vault();
+        std::fs::write(
+            vault.path().join("alias-target.md"),
+            "---\naliases: [Shortcut]\n---\nBody\n",
+        )
+        .unwrap();
+        std::fs::write(vault.path().join("linker.md"), "See [[Shortcut]]\n").unwrap();
+
+        let tree = generate_tree(vault.path(), &TreeOptions::default()).unwrap();
+        let target = tree
+            .root
+            .children
+            .iter()
+            .find(|c| c.name == "alias-target.md")
+            .unwrap();
+
+        assert_eq!(target.metadata.as_ref().unwrap().incoming_links, 1);
+    }
+
+    #[test]
+    fn counts_path_qualified_links_like_the_graph() {
+        let vault = create_test_vault();
+        fs::create_dir_all(vault.path().join("work")).unwrap();
+        fs::create_dir_all(vault.path().join("home")).unwrap();
+        fs::write(vault.path().join("work/todo.md"), "Tasks\n").unwrap();
+        fs::write(vault.path().join("home/todo.md"), "Chores\n").unwrap();
+        fs::write(
+            vault.path().join("plan.md"),
+            "[[work/todo]] [[work/todo|again]] [[missing]]\n",
+        )
+        .unwrap();
+
+        let tree = generate_tree(vault.path(), &TreeOptions::default()).unwrap();
+        let meta = |dir: &str, name: &str| {
+            let dir = tree.root.children.iter().find(|c| c.name == dir).unwrap();
+            let note = dir.children.iter().find(|c| c.name == name).unwrap();
+            note.metadata.clone().unwrap()
+        };
+        assert_eq!(meta("work", "todo.md").incoming_links, 2);
+        assert_eq!(meta("home", "todo.md").incoming_links, 0);
+
+        let plan = tree
+            .root
+            .children
+            .iter()
+            .find(|c| c.name == "plan.md")
+            .unwrap();
+        assert_eq!(plan.metadata.as_ref().unwrap().outgoing_links, 2);
+
+        let graph = crate::generate_link_graph(vault.path())
+            .unwrap()
+            .link_index();
+        assert_eq!(graph.incoming_count("work/todo.md"), 2);
+    }
+
+    #[test]
+    fn renders_structured_formats() {
+        let vault = create_test_vault();
+        let tree = generate_tree(vault.path(), &TreeOptions::default()).unwrap();
+
+        let json = render_tree_as(&tree, TreeFormat::Json).unwrap();
+        let parsed: VaultTree = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.total_notes, 3);
+
+        let yaml = render_tree_as(&tree, TreeFormat::Yaml).unwrap();
+        assert!(yaml.contains("total_notes: 3"));
+
+        let markdown = render_tree_as(&tree, TreeFormat::Markdown).unwrap();
+        assert!(markdown.contains("  - **subdir/**"));
+        assert!(markdown.contains("  - [[note1]] `[rust] 2025-01-18 <-1 ->1`"));
+        assert!(markdown.contains("    - [[subdir/nested|nested]]"));
+        assert_eq!(render_tree_markdown(&tree), markdown);
+    }
+
+    #[test]
+    fn filters_by_tag_and_incoming_links() {
+        let vault = create_test_vault();
+        std::fs::write(
+            vault.path().join("subdir/project.md"),
+            "Working on #project/vault-tree\n",
+        )
+        .unwrap();
+
+        let options = TreeOptions {
+            filter_tags: vec!["#project".to_string(), "mcp".to_string()],
+            ..Default::default()
+        };
+        let tree = generate_tree(vault.path(), &options).unwrap();
+        let names: Vec<&str> = tree.root.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["subdir", "note2.md"]);
+        assert_eq!(tree.root.children[0].children[0].name, "project.md");
+        assert_eq!(tree.total_notes, 2);
+
+        let options = TreeOptions {
+            min_incoming_links: Some(1),
+            ..Default::default()
+        };
+        let tree = generate_tree(vault.path(), &options).unwrap();
+        let names: Vec<&str> = tree.root.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["note1.md", "note2.md"]);
+    }
+
+    #[test]
+    fn filters_by_modified_date() {
+        let vault = create_test_vault();
+
+        let recent = TreeOptions {
+            modified_after: Some("2000-01-01".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(generate_tree(vault.path(), &recent).unwrap().total_notes, 3);
+
+        let old = TreeOptions {
+            modified_before: Some("2000-01-01".to_string()),
+            ..Default::default()
+        };
+        let tree = generate_tree(vault.path(), &old).unwrap();
+        assert_eq!(tree.total_notes, 0);
+        assert!(tree.root.children.is_empty());
+
+        let invalid = TreeOptions {
+            modified_after: Some("last week".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            generate_tree(vault.path(), &invalid),
+            Err(TreeError::InvalidDate(_))
+        ));
+    }
+
+    #[test]
+    fn includes_attachments_with_sizes() {
+        let vault = create_test_vault();
+        std::fs::write(vault.path().join("subdir/diagram.png"), vec![0u8; 2048]).unwrap();
+        std::fs::write(vault.path().join("subdir/.DS_Store"), "x").unwrap();
+
+        let plain = generate_tree(vault.path(), &TreeOptions::default()).unwrap();
+        assert!(!render_tree(&plain).contains("diagram.png"));
+
+        let options = TreeOptions {
+            include_attachments: true,
+            ..Default::default()
+        };
+        let tree = generate_tree(vault.path(), &options).unwrap();
+        assert_eq!(tree.total_notes, 3);
+        assert_eq!(tree.root.attachment_count, 1);
+        assert_eq!(tree.root.size, 2048);
+
+        let output = render_tree(&tree);
+        assert!(output.contains("subdir/ [1 attachments, 2.0 KB]"));
+        assert!(output.contains("diagram.png  2.0 KB"));
+        assert!(!output.contains(".DS_Store"));
+
+        let shallow = TreeOptions {
+            depth: Some(1),
+            include_attachments: true,
+            ..Default::default()
+        };
+        let tree = generate_tree(vault.path(), &shallow).unwrap();
+        let subdir = tree
+            .root
+            .children
+            .iter()
+            .find(|c| c.name == "subdir")
+            .unwrap();
+        assert_eq!(subdir.attachment_count, 1);
+        assert_eq!(subdir.note_count, 1);
+    }
+
+    #[test]
+    fn counts_canvas_links() {
+        let vault = create_test_vault();
+        std::fs::write(
+            vault.path().join("board.canvas"),
+            r#"{"nodes": [
+                {"id": "1", "type": "file", "file": "note2.md"},
+                {"id": "2", "type": "file", "file": "subdir/nested.md"}
+            ], "edges": [{"id": "e", "fromNode": "2", "toNode": "1"}]}"#,
+        )
+        .unwrap();
+
+        let tree = generate_tree(vault.path(), &TreeOptions::default()).unwrap();
+        let note2 = tree
+            .root
+            .children
+            .iter()
+            .find(|c| c.name == "note2.md")
+            .unwrap();
+
+        // note1 link, canvas card, and canvas edge from nested.
+        assert_eq!(note2.metadata.as_ref().unwrap().incoming_links, 3);
+    }
+
+    #[test]
+    fn honours_vaultignore_and_exclude_option() {
+        let vault = create_test_vault();
+        std::fs::write(vault.path().join(".vaultignore"), "# skip\nsubdir/\n").unwrap();
+
+        let tree = generate_tree(vault.path(), &TreeOptions::default()).unwrap();
+        assert_eq!(tree.total_notes, 2);
+        assert!(!tree.root.children.iter().any(|c| c.name == "subdir"));
+        // nested.md no longer contributes a backlink.
+        let note1 = &tree.root.children[0];
+        assert_eq!(note1.metadata.as_ref().unwrap().incoming_links, 0);
+
+        let options = TreeOptions {
+            exclude: vec!["note2.md".to_string()],
+            ..Default::default()
+        };
+        let tree = generate_tree(vault.path(), &options).unwrap();
+        assert_eq!(tree.total_notes, 1);
+
+        let invalid = TreeOptions {
+            exclude: vec!["[".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            generate_tree(vault.path(), &invalid),
+            Err(TreeError::Exclude(_))
+        ));
+    }
+
     #[test]
     fn excludes_obsidian_dir() {
         let vault = create_test_vault();
@@ -296,5 +965,43 @@ mod tests {
         assert!(output.contains("note1.md"));
         assert!(output.contains("subdir/"));
         assert!(output.contains("3 notes"));
+
+        let mut streamed = Vec::new();
+        render_tree_streaming(&tree, &mut streamed).unwrap();
+        assert_eq!(String::from_utf8(streamed).unwrap(), output);
+    }
+
+    #[test]
+    fn counts_links_across_unicode_forms_and_case() {
+        let vault = create_test_vault();
+        fs::write(vault.path().join("Cafe\u{301}.md"), "Menu\n").unwrap();
+        fs::write(
+            vault.path().join("visit.md"),
+            "[[caf\u{e9}]] and [[CAF\u{c9}]]\n",
+        )
+        .unwrap();
+
+        let incoming = |options: &TreeOptions| {
+            let tree = generate_tree(vault.path(), options).unwrap();
+            let cafe = tree
+                .root
+                .children
+                .iter()
+                .find(|c| c.name.starts_with("Cafe"))
+                .unwrap()
+                .clone();
+            cafe.metadata.unwrap().incoming_links
+        };
+
+        assert_eq!(incoming(&TreeOptions::default()), 2);
+
+        let case_sensitive = TreeOptions {
+            link_normalization: LinkNormalization {
+                case_insensitive: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(incoming(&case_sensitive), 0);
     }
 }
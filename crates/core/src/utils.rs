@@ -1,5 +1,9 @@
 use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::ops::ControlFlow;
 use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use walkdir::{DirEntry, WalkDir};
 
 /// Compare two tree entries: directories first, then alphabetically by name.
@@ -90,7 +94,7 @@ pub fn count_totals<T: TreeRenderable>(node: &T) -> (usize, usize) {
             notes += n;
             dirs += d;
         }
-    } else {
+    } else if node.is_note() {
         notes += 1;
     }
 
@@ -105,12 +109,25 @@ pub trait TreeRenderable {
     where
         Self: Sized;
     fn annotation(&self) -> String;
+    /// Whether this node counts towards note totals.
+    fn is_note(&self) -> bool {
+        !self.is_dir()
+    }
 }
 
 /// Render a tree node and its children as an ASCII tree.
 pub fn render_tree_ascii<T: TreeRenderable>(node: &T, prefix: &str, is_last: bool) -> String {
-    let mut output = String::new();
+    let mut output = Vec::new();
+    write_tree_ascii(node, prefix, is_last, &mut output).expect("writing to a Vec cannot fail");
+    String::from_utf8(output).expect("tree names are UTF-8")
+}
 
+/// Streaming form of [`render_tree_ascii`], writing one line at a time.
+pub fn write_tree_ascii<T, W>(node: &T, prefix: &str, is_last: bool, out: &mut W) -> io::Result<()>
+where
+    T: TreeRenderable,
+    W: Write + ?Sized,
+{
     let connector = if prefix.is_empty() {
         ""
     } else if is_last {
@@ -125,13 +142,14 @@ pub fn render_tree_ascii<T: TreeRenderable>(node: &T, prefix: &str, is_last: boo
         node.name().to_string()
     };
 
-    output.push_str(&format!(
-        "{}{}{}{}\n",
+    writeln!(
+        out,
+        "{}{}{}{}",
         prefix,
         connector,
         display_name,
         node.annotation()
-    ));
+    )?;
 
     let child_prefix = if prefix.is_empty() {
         String::new()
@@ -144,14 +162,10 @@ pub fn render_tree_ascii<T: TreeRenderable>(node: &T, prefix: &str, is_last: boo
     let children = node.children();
     let child_count = children.len();
     for (i, child) in children.iter().enumerate() {
-        output.push_str(&render_tree_ascii(
-            child,
-            &child_prefix,
-            i == child_count - 1,
-        ));
+        write_tree_ascii(child, &child_prefix, i == child_count - 1, out)?;
     }
 
-    output
+    Ok(())
 }
 
 /// Returns true if the path should be excluded from vault operations.
@@ -173,6 +187,44 @@ pub fn walk_markdown_files(path: &Path) -> impl Iterator<Item = DirEntry> {
         .filter(is_markdown_file)
 }
 
+/// Format a byte count with a binary unit, e.g. `1.5 MB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Parse a `YYYY-MM-DD` date as midnight UTC.
+pub fn parse_date(date: &str) -> Option<SystemTime> {
+    let mut parts = date.trim().splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Days since 1970-01-01 in the proleptic Gregorian calendar.
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    let secs = u64::try_from(days * 86_400).ok()?;
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
 /// Returns true if the entry is a markdown file.
 pub fn is_markdown_file(entry: &DirEntry) -> bool {
     entry.file_type().is_file()
@@ -183,6 +235,34 @@ pub fn is_markdown_file(entry: &DirEntry) -> bool {
             .unwrap_or(false)
 }
 
+const LINE_BUFFER_CAPACITY: usize = 64 * 1024;
+
+/// Calls `f` with each line of a file (1-based line number, no line ending),
+/// reusing a single buffer instead of reading the whole file into memory.
+/// Stops early when `f` returns `ControlFlow::Break`.
+pub fn for_each_line<F>(path: &Path, mut f: F) -> std::io::Result<()>
+where
+    F: FnMut(usize, &str) -> ControlFlow<()>,
+{
+    let mut reader = BufReader::with_capacity(LINE_BUFFER_CAPACITY, File::open(path)?);
+    let mut buf = String::new();
+    let mut line_number = 0;
+
+    loop {
+        buf.clear();
+        if reader.read_line(&mut buf)? == 0 {
+            return Ok(());
+        }
+        line_number += 1;
+
+        let line = buf.strip_suffix('\n').unwrap_or(&buf);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if f(line_number, line).is_break() {
+            return Ok(());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -229,4 +309,50 @@ mod tests {
         assert_eq!(files.len(), 2);
         assert!(files.iter().all(|f| f.path().extension().unwrap() == "md"));
     }
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+
+    #[test]
+    fn parses_dates_as_utc_midnight() {
+        let secs = |d| {
+            parse_date(d)
+                .unwrap()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        };
+        assert_eq!(secs("1970-01-01"), 0);
+        assert_eq!(secs("2000-03-01"), 951_868_800);
+        assert_eq!(secs("2025-01-18"), 1_737_158_400);
+        assert!(parse_date("2025-13-01").is_none());
+        assert!(parse_date("yesterday").is_none());
+    }
+
+    #[test]
+    fn reads_lines_without_endings() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("note.md");
+        fs::write(&path, "one\r\ntwo\nthree").unwrap();
+
+        let mut lines = Vec::new();
+        for_each_line(&path, |n, line| {
+            lines.push((n, line.to_string()));
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+        assert_eq!(
+            lines,
+            vec![
+                (1, "one".to_string()),
+                (2, "two".to_string()),
+                (3, "three".to_string())
+            ]
+        );
+    }
 }
@@ -0,0 +1,252 @@
+use crate::cache::MetadataCache;
+use crate::exclude::{ExcludeError, ExcludeSet, VAULTIGNORE};
+use crate::links::LinkIndex;
+use crate::tree::{generate_tree_with_links, TreeError, TreeOptions, VaultTree};
+use notify::event::{EventKind, ModifyKind};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, RwLock};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use thiserror::Error;
+
+/// How long to wait for a burst of file events to settle before rescanning.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Error)]
+pub enum WatchError {
+    #[error(transparent)]
+    Tree(#[from] TreeError),
+    #[error(transparent)]
+    Exclude(#[from] ExcludeError),
+    #[error("watch error: {0}")]
+    Notify(#[from] notify::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+/// A note, canvas, or `.vaultignore` that changed on disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultChange {
+    pub kind: ChangeKind,
+    /// Vault-relative path.
+    pub path: String,
+}
+
+/// The vault as of the last rescan.
+#[derive(Debug, Clone)]
+pub struct VaultSnapshot {
+    pub tree: VaultTree,
+    /// Links between notes, by vault-relative path.
+    pub links: LinkIndex,
+}
+
+/// Keeps a [`VaultSnapshot`] current while the vault changes. Watching stops
+/// when the watcher is dropped.
+pub struct VaultWatcher {
+    snapshot: Arc<RwLock<VaultSnapshot>>,
+    watcher: RecommendedWatcher,
+    worker: JoinHandle<()>,
+}
+
+impl VaultWatcher {
+    /// A copy of the current tree and link index.
+    pub fn snapshot(&self) -> VaultSnapshot {
+        self.snapshot
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Stop watching and wait for any in-flight rescan to finish.
+    pub fn stop(self) {
+        drop(self.watcher);
+        let _ = self.worker.join();
+    }
+}
+
+/// Scan the vault, then watch it for changes. After each burst of edits the
+/// tree and link index are rebuilt and `callback` receives the changed files
+/// along with the new snapshot.
+pub fn watch_vault<F>(vault_path: &Path, callback: F) -> Result<VaultWatcher, WatchError>
+where
+    F: FnMut(&[VaultChange], &VaultSnapshot) + Send + 'static,
+{
+    let vault_path = vault_path
+        .canonicalize()
+        .map_err(|_| TreeError::VaultNotFound(vault_path.display().to_string()))?;
+    let cache = MetadataCache::new();
+    let snapshot = Arc::new(RwLock::new(scan(&vault_path, &cache)?));
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(&vault_path, RecursiveMode::Recursive)?;
+
+    let shared = Arc::clone(&snapshot);
+    let worker = std::thread::spawn(move || run(&vault_path, rx, &shared, &cache, callback));
+
+    Ok(VaultWatcher {
+        snapshot,
+        watcher,
+        worker,
+    })
+}
+
+/// Rebuild the snapshot, re-reading only notes that changed since `cache`
+/// last saw them.
+fn scan(vault_path: &Path, cache: &MetadataCache) -> Result<VaultSnapshot, TreeError> {
+    let (tree, links) = generate_tree_with_links(vault_path, &TreeOptions::default(), cache)?;
+    Ok(VaultSnapshot { tree, links })
+}
+
+fn run<F>(
+    vault_path: &Path,
+    rx: Receiver<notify::Result<Event>>,
+    snapshot: &RwLock<VaultSnapshot>,
+    cache: &MetadataCache,
+    mut callback: F,
+) where
+    F: FnMut(&[VaultChange], &VaultSnapshot),
+{
+    // The channel closes when the `RecommendedWatcher` is dropped.
+    while let Ok(first) = rx.recv() {
+        let exclude = ExcludeSet::load(vault_path, &[]).unwrap_or_default();
+        let mut changes: BTreeMap<String, ChangeKind> = BTreeMap::new();
+        let mut event = Some(first);
+
+        while let Some(result) = event.take() {
+            if let Ok(event) = result {
+                collect_changes(vault_path, &exclude, &event, &mut changes);
+            }
+            event = match rx.recv_timeout(DEBOUNCE) {
+                Ok(next) => Some(next),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => return,
+            };
+        }
+
+        if changes.is_empty() {
+            continue;
+        }
+        if changes.values().any(|kind| *kind == ChangeKind::Removed) {
+            cache.prune();
+        }
+        let Ok(fresh) = scan(vault_path, cache) else {
+            continue;
+        };
+        let changes: Vec<VaultChange> = changes
+            .into_iter()
+            .map(|(path, kind)| VaultChange { kind, path })
+            .collect();
+
+        *snapshot.write().unwrap_or_else(|e| e.into_inner()) = fresh;
+        callback(
+            &changes,
+            &snapshot.read().unwrap_or_else(|e| e.into_inner()),
+        );
+    }
+}
+
+fn collect_changes(
+    vault_path: &Path,
+    exclude: &ExcludeSet,
+    event: &Event,
+    changes: &mut BTreeMap<String, ChangeKind>,
+) {
+    for path in &event.paths {
+        if !is_tracked(vault_path, exclude, path) {
+            continue;
+        }
+        let kind = match event.kind {
+            EventKind::Create(_) => ChangeKind::Created,
+            EventKind::Remove(_) => ChangeKind::Removed,
+            EventKind::Modify(ModifyKind::Name(_)) if path.exists() => ChangeKind::Created,
+            EventKind::Modify(ModifyKind::Name(_)) => ChangeKind::Removed,
+            EventKind::Modify(_) => ChangeKind::Modified,
+            _ => continue,
+        };
+        let Ok(relative) = path.strip_prefix(vault_path) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().to_string();
+
+        // A file created and then edited within one burst is still new.
+        let kind = match (changes.get(&relative), kind) {
+            (Some(ChangeKind::Created), ChangeKind::Modified) => ChangeKind::Created,
+            _ => kind,
+        };
+        changes.insert(relative, kind);
+    }
+}
+
+/// Whether a path affects the tree or link index: a note, canvas, or ignore
+/// file outside every excluded directory.
+fn is_tracked(vault_path: &Path, exclude: &ExcludeSet, path: &Path) -> bool {
+    let relevant = path.file_name().is_some_and(|n| n == VAULTIGNORE)
+        || path
+            .extension()
+            .is_some_and(|ext| ext == "md" || ext == "canvas");
+    relevant
+        && path
+            .ancestors()
+            .take_while(|p| p.starts_with(vault_path) && *p != vault_path)
+            .all(|p| !exclude.is_excluded(vault_path, p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils::create_test_vault;
+    use std::fs;
+
+    #[test]
+    fn tracks_notes_outside_excluded_dirs() {
+        let vault = Path::new("/vault");
+        let exclude = ExcludeSet::new(&["templates".to_string()]).unwrap();
+
+        assert!(is_tracked(vault, &exclude, &vault.join("a/note.md")));
+        assert!(is_tracked(vault, &exclude, &vault.join("board.canvas")));
+        assert!(is_tracked(vault, &exclude, &vault.join(VAULTIGNORE)));
+        assert!(!is_tracked(vault, &exclude, &vault.join("image.png")));
+        assert!(!is_tracked(vault, &exclude, &vault.join(".obsidian/x.md")));
+        assert!(!is_tracked(
+            vault,
+            &exclude,
+            &vault.join("templates/day.md")
+        ));
+    }
+
+    #[test]
+    fn rescans_on_change() {
+        let vault = create_test_vault();
+        let (tx, rx) = mpsc::channel();
+        let watcher = watch_vault(vault.path(), move |changes, snapshot| {
+            let _ = tx.send((changes.to_vec(), snapshot.tree.total_notes));
+        })
+        .unwrap();
+        assert_eq!(watcher.snapshot().tree.total_notes, 3);
+
+        fs::write(vault.path().join("fresh.md"), "Links to [[note2]]\n").unwrap();
+
+        let (changes, total) = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(changes.contains(&VaultChange {
+            kind: ChangeKind::Created,
+            path: "fresh.md".to_string(),
+        }));
+        assert_eq!(total, 4);
+
+        let snapshot = watcher.snapshot();
+        assert_eq!(snapshot.tree.total_notes, 4);
+        assert_eq!(snapshot.links.incoming_count("note2.md"), 2);
+        watcher.stop();
+    }
+}
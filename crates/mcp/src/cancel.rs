@@ -0,0 +1,117 @@
+//! Request cancellation. The server gives every in-flight request a
+//! [`CancelToken`], fired by `notifications/cancelled`. The transports stop
+//! waiting on a cancelled request right away; tool code that loops over slow
+//! work can also poll [`is_cancelled`] to stop early.
+//!
+//! The current token is thread-local. Threads a tool spawns, including the
+//! rayon workers behind core's scan and fingerprinting, don't see it unless
+//! it is passed along with [`current`].
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<Inner>);
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Relaxed);
+        self.0.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Resolves once the token is cancelled.
+    pub async fn cancelled(&self) {
+        let notified = self.0.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if !self.is_cancelled() {
+            notified.await;
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous token when dropped, even while unwinding, so a
+/// blocking thread that is reused never keeps a finished request's token.
+pub struct ScopeGuard {
+    previous: Option<CancelToken>,
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Make `token` the current thread's token until the guard is dropped.
+pub fn enter(token: &CancelToken) -> ScopeGuard {
+    ScopeGuard {
+        previous: CURRENT.with(|current| current.replace(Some(token.clone()))),
+    }
+}
+
+/// Run `f` with `token` as the current thread's token.
+pub fn scope<R>(token: &CancelToken, f: impl FnOnce() -> R) -> R {
+    let _guard = enter(token);
+    f()
+}
+
+/// The current thread's token, to hand to threads spawned for the request.
+pub fn current() -> Option<CancelToken> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Whether the request running on this thread has been cancelled.
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(CancelToken::is_cancelled)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_is_restored_after_a_panic() {
+        let token = CancelToken::default();
+        token.cancel();
+
+        let result = std::panic::catch_unwind(|| scope(&token, || panic!("tool failed")));
+
+        assert!(result.is_err());
+        assert!(current().is_none());
+        assert!(!is_cancelled());
+    }
+
+    #[test]
+    fn nested_scopes_restore_the_outer_token() {
+        let outer = CancelToken::default();
+        let inner = CancelToken::default();
+        inner.cancel();
+
+        scope(&outer, || {
+            scope(&inner, || assert!(is_cancelled()));
+            assert!(!is_cancelled());
+        });
+    }
+}
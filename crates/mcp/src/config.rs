@@ -0,0 +1,219 @@
+//! Server configuration, read from `~/.config/vault-tree/mcp.toml` or the
+//! file given with `--config`:
+//!
+//! ```toml
+//! vault = "~/notes"
+//! page_size = 100
+//!
+//! [knowledge]
+//! providers = ["wikipedia", "arxiv", "crates.io"]
+//! disabled_providers = ["reddit"]
+//! auto_order = ["crates.io", "wikipedia"]
+//! cache_size = 500
+//! cache_ttl_minutes = 60
+//! disk_cache = true
+//! offline = false
+//! github_token = "ghp_..."
+//! shodan_api_key = "..."
+//! etherscan_api_key = "..."
+//! youtube_api_key = "..."
+//! tmdb_api_key = "..."
+//!
+//! [knowledge.provider_ttl_minutes]
+//! defillama = 5
+//!
+//! [knowledge.feeds]
+//! rust = "https://blog.rust-lang.org/feed.xml"
+//!
+//! [http]
+//! token = "..."
+//! session_idle_minutes = 30
+//! max_sessions = 64
+//! ```
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Used as `vault_path` when a tool call leaves it out.
+    pub vault: Option<PathBuf>,
+    /// Items per page of `tools/list`, `prompts/list`, and `resources/list`
+    /// (default 100).
+    pub page_size: Option<usize>,
+    pub knowledge: KnowledgeConfig,
+    pub http: HttpConfig,
+}
+
+/// Settings for `--http`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpConfig {
+    /// Clients must send `Authorization: Bearer <token>`; required to serve
+    /// on a non-loopback address. Overridden by `VAULT_TREE_HTTP_TOKEN`.
+    pub token: Option<String>,
+    /// Sessions unused for this long are dropped (default 30).
+    pub session_idle_minutes: Option<u64>,
+    /// Sessions open at once; `initialize` is refused beyond it (default 64).
+    pub max_sessions: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KnowledgeConfig {
+    /// Providers to enable; all of them when unset.
+    pub providers: Option<Vec<String>>,
+    /// Providers to turn off, even if listed in `providers`. Replaced by
+    /// `VAULT_TREE_DISABLED_PROVIDERS` (comma-separated) when set.
+    pub disabled_providers: Vec<String>,
+    /// Providers `auto` lookups try, in order; the built-in order when
+    /// unset. Providers left out can still be looked up by name. Replaced by
+    /// `VAULT_TREE_AUTO_ORDER` (comma-separated) when set.
+    pub auto_order: Option<Vec<String>>,
+    /// Cached lookups to keep; 0 disables the cache (default 100).
+    pub cache_size: Option<usize>,
+    /// How long a cached lookup stays fresh (default 15).
+    pub cache_ttl_minutes: Option<u64>,
+    /// Per-provider overrides of `cache_ttl_minutes`.
+    pub provider_ttl_minutes: HashMap<String, u64>,
+    /// Also keep lookups on disk, so they survive restarts.
+    pub disk_cache: bool,
+    /// Directory for the disk cache (default
+    /// `~/.cache/vault-tree/knowledge`); setting it enables the disk cache.
+    pub cache_dir: Option<PathBuf>,
+    /// Answer only from the caches and fail other lookups at once, for
+    /// working without a network. `VAULT_TREE_OFFLINE=1` also enables it.
+    pub offline: bool,
+    /// How long a provider's availability check is reused (default 5).
+    pub availability_ttl_minutes: Option<u64>,
+    /// Overridden by `GITHUB_TOKEN`.
+    pub github_token: Option<String>,
+    /// Overridden by `SHODAN_API_KEY`.
+    pub shodan_api_key: Option<String>,
+    /// Overridden by `ETHERSCAN_API_KEY`.
+    pub etherscan_api_key: Option<String>,
+    /// Overridden by `YOUTUBE_API_KEY`.
+    pub youtube_api_key: Option<String>,
+    /// Overridden by `TMDB_API_KEY`.
+    pub tmdb_api_key: Option<String>,
+    /// Feed URLs by name, so the `rss` provider can be queried by name.
+    pub feeds: HashMap<String, String>,
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/vault-tree/mcp.toml`, falling back to
+    /// `~/.config/vault-tree/mcp.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home_dir().map(|home| home.join(".config")))?;
+        Some(config_home.join("vault-tree").join("mcp.toml"))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("cannot read config {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("invalid config {}", path.display()))
+    }
+
+    /// The default config file if it exists, otherwise an empty config.
+    pub fn load_default() -> Result<Self> {
+        match Self::default_path() {
+            Some(path) if path.is_file() => Self::load(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut config: Self = toml::from_str(text)?;
+        config.vault = config.vault.map(expand_home);
+        config.knowledge.cache_dir = config.knowledge.cache_dir.map(expand_home);
+        Ok(config)
+    }
+}
+
+impl KnowledgeConfig {
+    /// Where lookups are cached on disk, if the disk cache is enabled:
+    /// `cache_dir`, else `$XDG_CACHE_HOME/vault-tree/knowledge`, falling back
+    /// to `~/.cache/vault-tree/knowledge`.
+    pub fn disk_cache_dir(&self) -> Option<PathBuf> {
+        if let Some(dir) = &self.cache_dir {
+            return Some(dir.clone());
+        }
+        if !self.disk_cache {
+            return None;
+        }
+        let cache_home = std::env::var_os("XDG_CACHE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home_dir().map(|home| home.join(".cache")))?;
+        Some(cache_home.join("vault-tree").join("knowledge"))
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
+
+fn expand_home(path: PathBuf) -> PathBuf {
+    match (path.strip_prefix("~"), home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_paths_and_knowledge_settings() {
+        let config = Config::parse(
+            "vault = \"/notes\"\n\n[knowledge]\nproviders = [\"arxiv\"]\ncache_size = 0\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.vault, Some(PathBuf::from("/notes")));
+        assert_eq!(config.knowledge.providers, Some(vec!["arxiv".to_string()]));
+        assert_eq!(config.knowledge.cache_size, Some(0));
+    }
+
+    #[test]
+    fn cache_dir_enables_the_disk_cache() {
+        let config = Config::parse("[knowledge]\ncache_dir = \"/tmp/kc\"\n").unwrap();
+        assert_eq!(
+            config.knowledge.disk_cache_dir(),
+            Some(PathBuf::from("/tmp/kc"))
+        );
+        assert_eq!(KnowledgeConfig::default().disk_cache_dir(), None);
+
+        let config = Config::parse("[knowledge.provider_ttl_minutes]\ndefillama = 5\n").unwrap();
+        assert_eq!(config.knowledge.provider_ttl_minutes["defillama"], 5);
+    }
+
+    #[test]
+    fn parses_provider_lists() {
+        let config = Config::parse(
+            "[knowledge]\ndisabled_providers = [\"reddit\"]\nauto_order = [\"npm\", \"wikipedia\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.knowledge.disabled_providers, ["reddit"]);
+        assert_eq!(
+            config.knowledge.auto_order,
+            Some(vec!["npm".to_string(), "wikipedia".to_string()])
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(Config::parse("valut = \"/notes\"\n").is_err());
+        assert!(Config::parse("library = \"~/papers\"\n").is_err());
+    }
+}
@@ -0,0 +1,247 @@
+//! Streamable HTTP transport: JSON-RPC messages are POSTed to `/mcp` and
+//! answered with JSON, or, when the client accepts `text/event-stream`, with
+//! an SSE stream of the request's log notifications followed by its response.
+//!
+//! Each client gets its own [`McpServer`], keyed by the `Mcp-Session-Id`
+//! header issued on `initialize`; requests within a session may run
+//! concurrently. Idle sessions expire, and their number is capped. With a
+//! token configured, every request must carry it as a bearer token.
+
+use axum::body::{Body, Bytes};
+use axum::extract::State;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::Router;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tokio::sync::mpsc;
+
+use crate::config::{Config, HttpConfig};
+use crate::logging::Notifier;
+use crate::server::McpServer;
+use crate::transport::JsonRpcRequest;
+
+pub const SESSION_HEADER: &str = "mcp-session-id";
+
+type Session = Arc<McpServer>;
+
+struct SessionEntry {
+    server: Session,
+    last_used: Instant,
+}
+
+#[derive(Clone)]
+struct AppState {
+    sessions: Arc<Mutex<HashMap<String, SessionEntry>>>,
+    config: Config,
+    token: Option<Arc<str>>,
+    idle: Duration,
+    max_sessions: usize,
+}
+
+/// The bearer token clients must send, if any.
+fn token(config: &HttpConfig) -> Option<String> {
+    std::env::var("VAULT_TREE_HTTP_TOKEN")
+        .ok()
+        .or_else(|| config.token.clone())
+        .filter(|token| !token.is_empty())
+}
+
+/// Routes for the `/mcp` endpoint. Every session is served with `config`.
+pub fn router(config: Config) -> Router {
+    let state = AppState {
+        token: token(&config.http).map(Arc::from),
+        idle: Duration::from_secs(config.http.session_idle_minutes.unwrap_or(30) * 60),
+        max_sessions: config.http.max_sessions.unwrap_or(64),
+        sessions: Default::default(),
+        config,
+    };
+    Router::new()
+        .route(
+            "/mcp",
+            post(handle_post).get(handle_get).delete(handle_delete),
+        )
+        .with_state(state)
+}
+
+/// Serve MCP over HTTP on `addr` until the process exits. Addresses other
+/// than loopback are refused unless a token is configured.
+pub async fn serve(addr: impl ToSocketAddrs, config: Config) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    let local = listener.local_addr()?;
+    if !local.ip().is_loopback() && token(&config.http).is_none() {
+        return Err(std::io::Error::other(format!(
+            "refusing to serve on {} without a token; set http.token or VAULT_TREE_HTTP_TOKEN",
+            local
+        )));
+    }
+    axum::serve(listener, router(config)).await
+}
+
+async fn handle_post(State(state): State<AppState>, headers: HeaderMap, body: Bytes) -> Response {
+    if let Some(rejection) = check_origin(&headers).or_else(|| check_token(&state, &headers)) {
+        return rejection;
+    }
+    let Ok(input) = String::from_utf8(body.to_vec()) else {
+        return (StatusCode::BAD_REQUEST, "body is not UTF-8").into_response();
+    };
+
+    let parsed = serde_json::from_str::<JsonRpcRequest>(&input).ok();
+    let is_initialize = parsed.as_ref().is_some_and(|r| r.method == "initialize");
+    let expects_response = parsed.as_ref().is_some_and(|r| r.id.is_some());
+    let (session_id, session) = if is_initialize {
+        let mut sessions = lock(&state.sessions);
+        sessions.retain(|_, entry| entry.last_used.elapsed() < state.idle);
+        if sessions.len() >= state.max_sessions {
+            return (StatusCode::SERVICE_UNAVAILABLE, "too many sessions").into_response();
+        }
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        let session = Arc::new(McpServer::with_config(state.config.clone()));
+        sessions.insert(
+            id.clone(),
+            SessionEntry {
+                server: session.clone(),
+                last_used: Instant::now(),
+            },
+        );
+        (id, session)
+    } else {
+        match session_for(&state, &headers) {
+            Ok(found) => found,
+            Err(rejection) => return rejection.into_response(),
+        }
+    };
+
+    let mut response = if expects_response && accepts_event_stream(&headers) {
+        event_stream(session, input)
+    } else {
+        match session.handle_request_async(input).await {
+            // Notifications and client responses get no reply.
+            None => StatusCode::ACCEPTED.into_response(),
+            Some(json) => ([(header::CONTENT_TYPE, "application/json")], json).into_response(),
+        }
+    };
+    if let Ok(value) = HeaderValue::from_str(&session_id) {
+        response.headers_mut().insert(SESSION_HEADER, value);
+    }
+    response
+}
+
+/// Stream the request's log notifications as they happen, then its response,
+/// as SSE `message` events.
+fn event_stream(session: Session, input: String) -> Response {
+    let (tx, rx) = mpsc::unbounded_channel::<String>();
+    let notify_tx = tx.clone();
+    let notify: Notifier = Arc::new(move |notification| {
+        let _ = notify_tx.send(notification);
+    });
+    let pending = session.handle_request_notifying(input, notify);
+    tokio::spawn(async move {
+        if let Some(response) = pending.await {
+            let _ = tx.send(response);
+        }
+    });
+
+    // Ends once the handler and its notifier have dropped their senders.
+    let events = futures_util::stream::unfold(rx, |mut rx| async move {
+        let message = rx.recv().await?;
+        let event = format!("event: message\ndata: {}\n\n", message);
+        Some((Ok::<_, Infallible>(event), rx))
+    });
+    (
+        [(header::CONTENT_TYPE, "text/event-stream")],
+        Body::from_stream(events),
+    )
+        .into_response()
+}
+
+/// The server never sends unsolicited messages, so there is no stream to open.
+async fn handle_get() -> StatusCode {
+    StatusCode::METHOD_NOT_ALLOWED
+}
+
+async fn handle_delete(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if let Some(rejection) = check_origin(&headers).or_else(|| check_token(&state, &headers)) {
+        return rejection;
+    }
+    let id = headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok());
+    match id.and_then(|id| lock(&state.sessions).remove(id)) {
+        Some(_) => StatusCode::NO_CONTENT.into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn session_for(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<(String, Session), (StatusCode, &'static str)> {
+    let Some(id) = headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok()) else {
+        return Err((StatusCode::BAD_REQUEST, "missing Mcp-Session-Id header"));
+    };
+    let mut sessions = lock(&state.sessions);
+    match sessions.get_mut(id) {
+        Some(entry) if entry.last_used.elapsed() < state.idle => {
+            entry.last_used = Instant::now();
+            Ok((id.to_string(), entry.server.clone()))
+        }
+        Some(_) => {
+            sessions.remove(id);
+            Err((StatusCode::NOT_FOUND, "session expired"))
+        }
+        None => Err((StatusCode::NOT_FOUND, "unknown session")),
+    }
+}
+
+/// With a token configured, requests must send it as
+/// `Authorization: Bearer <token>`.
+fn check_token(state: &AppState, headers: &HeaderMap) -> Option<Response> {
+    let expected = state.token.as_deref()?;
+    let sent = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    // Compared without an early exit, so timing doesn't reveal the token.
+    let matches = sent.len() == expected.len()
+        && sent
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0;
+    (!matches).then(|| {
+        (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            "missing or invalid bearer token",
+        )
+            .into_response()
+    })
+}
+
+/// Browsers send `Origin`; only local pages may talk to the server, which
+/// guards against DNS rebinding. Other clients don't send the header.
+fn check_origin(headers: &HeaderMap) -> Option<Response> {
+    let origin = headers.get(header::ORIGIN)?.to_str().unwrap_or_default();
+    let authority = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+    let host = match authority.rsplit_once(':') {
+        Some((host, port)) if port.bytes().all(|b| b.is_ascii_digit()) => host,
+        _ => authority,
+    };
+    let local = matches!(host, "localhost" | "127.0.0.1" | "[::1]");
+    (!local).then(|| (StatusCode::FORBIDDEN, "origin not allowed").into_response())
+}
+
+fn accepts_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/event-stream"))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
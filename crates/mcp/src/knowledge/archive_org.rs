@@ -0,0 +1,210 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+const WAYBACK_API: &str = "https://archive.org/wayback/available";
+
+/// The closest Wayback Machine snapshot of a URL, for dead links.
+pub struct ArchiveOrgProvider {
+    client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct AvailabilityResponse {
+    archived_snapshots: Snapshots,
+}
+
+#[derive(Debug, Deserialize)]
+struct Snapshots {
+    closest: Option<Snapshot>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Snapshot {
+    available: bool,
+    url: String,
+    timestamp: String,
+    status: Option<String>,
+}
+
+impl ArchiveOrgProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+        }
+    }
+
+    /// Split `https://example.com/page 20150101` into the URL and an
+    /// optional `YYYY[MMDDhhmmss]` timestamp to find the closest snapshot to.
+    /// Queries that don't look like a URL give `None`.
+    fn parse_query(query: &str) -> Option<(&str, Option<&str>)> {
+        let mut parts = query.split_whitespace();
+        let url = parts.next()?;
+        let timestamp = parts.next();
+        if parts.next().is_some() || !url.contains('.') {
+            return None;
+        }
+        match timestamp {
+            Some(t) if t.len() < 4 || !t.chars().all(|c| c.is_ascii_digit()) => None,
+            _ => Some((url, timestamp)),
+        }
+    }
+
+    /// `20130919044612` as `2013-09-19 04:46:12`.
+    fn format_timestamp(timestamp: &str) -> String {
+        let part = |range: std::ops::Range<usize>| timestamp.get(range).unwrap_or("");
+        if timestamp.len() < 14 {
+            return timestamp.to_string();
+        }
+        format!(
+            "{}-{}-{} {}:{}:{}",
+            part(0..4),
+            part(4..6),
+            part(6..8),
+            part(8..10),
+            part(10..12),
+            part(12..14)
+        )
+    }
+
+    fn snapshot_to_entry(original: &str, snapshot: &Snapshot) -> KnowledgeEntry {
+        let archived = Self::format_timestamp(&snapshot.timestamp);
+        let mut summary = format!("Archived {}", archived);
+        if let Some(status) = &snapshot.status {
+            summary.push_str(&format!(" (HTTP {})", status));
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("originalUrl".to_string(), serde_json::json!(original));
+        metadata.insert(
+            "timestamp".to_string(),
+            serde_json::json!(snapshot.timestamp),
+        );
+        metadata.insert("archived".to_string(), serde_json::json!(archived));
+        if let Some(status) = &snapshot.status {
+            metadata.insert("status".to_string(), serde_json::json!(status));
+        }
+
+        KnowledgeEntry {
+            title: format!("Wayback Machine: {}", original),
+            summary,
+            url: Some(snapshot.url.replacen("http://", "https://", 1)),
+            source: "archive.org".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+
+    fn closest(
+        &self,
+        url: &str,
+        timestamp: Option<&str>,
+    ) -> Result<Option<KnowledgeEntry>, String> {
+        let mut request = format!("{}?url={}", WAYBACK_API, urlencoding::encode(url));
+        if let Some(timestamp) = timestamp {
+            request.push_str(&format!("&timestamp={}", timestamp));
+        }
+
+        let response = self
+            .client
+            .get(&request)
+            .send()
+            .map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("availability check failed: {}", response.status()));
+        }
+
+        let data: AvailabilityResponse = response.json().map_err(|e| e.to_string())?;
+        Ok(data
+            .archived_snapshots
+            .closest
+            .filter(|s| s.available)
+            .map(|s| Self::snapshot_to_entry(url, &s)))
+    }
+}
+
+impl Default for ArchiveOrgProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for ArchiveOrgProvider {
+    fn name(&self) -> &'static str {
+        "archive.org"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn accepts(&self, query: &str) -> bool {
+        Self::parse_query(query).is_some()
+    }
+
+    fn lookup(&self, query: &str, _options: &LookupOptions) -> LookupResult {
+        // Only URLs can be looked up; anything else has no snapshots.
+        let Some((url, timestamp)) = Self::parse_query(query) else {
+            return LookupResult::success(self.name(), vec![]);
+        };
+
+        match self.closest(url, timestamp) {
+            Ok(entry) => LookupResult::success(self.name(), entry.into_iter().collect()),
+            Err(e) => LookupResult::error(self.name(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Requires network
+    fn archive_org_lookup() {
+        let provider = ArchiveOrgProvider::new();
+        let result = provider.lookup("example.com", &LookupOptions::default());
+        assert!(result.success);
+        assert!(!result.entries.is_empty());
+    }
+
+    #[test]
+    fn parse_availability() {
+        let json = r#"{"url": "example.com", "archived_snapshots": {"closest": {
+            "status": "200", "available": true,
+            "url": "http://web.archive.org/web/20130919044612/http://example.com/",
+            "timestamp": "20130919044612"
+        }}}"#;
+
+        let data: AvailabilityResponse = serde_json::from_str(json).unwrap();
+        let snapshot = data.archived_snapshots.closest.unwrap();
+        let entry = ArchiveOrgProvider::snapshot_to_entry("example.com", &snapshot);
+
+        assert_eq!(
+            entry.url.as_deref(),
+            Some("https://web.archive.org/web/20130919044612/http://example.com/")
+        );
+        assert_eq!(entry.summary, "Archived 2013-09-19 04:46:12 (HTTP 200)");
+    }
+
+    #[test]
+    fn only_urls_are_looked_up() {
+        assert_eq!(
+            ArchiveOrgProvider::parse_query("https://example.com/a 2015"),
+            Some(("https://example.com/a", Some("2015")))
+        );
+        assert_eq!(
+            ArchiveOrgProvider::parse_query("example.com"),
+            Some(("example.com", None))
+        );
+        assert_eq!(ArchiveOrgProvider::parse_query("rust programming"), None);
+
+        let result = ArchiveOrgProvider::new().lookup("rust", &LookupOptions::default());
+        assert!(result.success && result.entries.is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
@@ -13,6 +14,17 @@ pub struct LruCache {
     max_size: usize,
     ttl: Duration,
     order: Vec<String>,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub max_entries: usize,
+    pub ttl_minutes: u64,
+    pub hits: u64,
+    pub misses: u64,
 }
 
 impl LruCache {
@@ -22,17 +34,24 @@ impl LruCache {
             max_size,
             ttl: Duration::from_secs(ttl_minutes * 60),
             order: Vec::new(),
+            hits: 0,
+            misses: 0,
         }
     }
 
     pub fn get(&mut self, key: &str) -> Option<LookupResult> {
-        let entry = self.cache.get(key)?;
+        let Some(entry) = self.cache.get(key) else {
+            self.misses += 1;
+            return None;
+        };
 
         if Instant::now() > entry.expires_at {
             self.cache.remove(key);
             self.order.retain(|k| k != key);
+            self.misses += 1;
             return None;
         }
+        self.hits += 1;
 
         // Move to end (most recently used)
         self.order.retain(|k| k != key);
@@ -41,7 +60,12 @@ impl LruCache {
         Some(entry.value.clone())
     }
 
+    #[cfg(test)]
     pub fn set(&mut self, key: String, value: LookupResult) {
+        self.set_with_ttl(key, value, self.ttl);
+    }
+
+    pub fn set_with_ttl(&mut self, key: String, value: LookupResult, ttl: Duration) {
         // Evict oldest if at capacity
         if self.cache.len() >= self.max_size && !self.cache.contains_key(&key) {
             if let Some(oldest_key) = self.order.first().cloned() {
@@ -57,11 +81,17 @@ impl LruCache {
             key,
             CacheEntry {
                 value,
-                expires_at: Instant::now() + self.ttl,
+                expires_at: Instant::now() + ttl,
             },
         );
     }
 
+    #[cfg(test)]
+    pub fn expires_in(&self, key: &str) -> Option<Duration> {
+        let entry = self.cache.get(key)?;
+        Some(entry.expires_at.saturating_duration_since(Instant::now()))
+    }
+
     pub fn clear(&mut self) {
         self.cache.clear();
         self.order.clear();
@@ -70,6 +100,16 @@ impl LruCache {
     pub fn size(&self) -> usize {
         self.cache.len()
     }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.cache.len(),
+            max_entries: self.max_size,
+            ttl_minutes: self.ttl.as_secs() / 60,
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
 }
 
 pub fn create_cache_key(provider: &str, query: &str, max_results: Option<usize>) -> String {
@@ -122,6 +162,22 @@ mod tests {
         assert!(cache.get("key3").is_some());
     }
 
+    #[test]
+    fn cache_counts_hits_and_misses() {
+        let mut cache = LruCache::new(10, 15);
+        cache.set("key1".to_string(), test_result());
+        cache.get("key1");
+        cache.get("key1");
+        cache.get("key2");
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 1));
+        assert_eq!(
+            (stats.entries, stats.max_entries, stats.ttl_minutes),
+            (1, 10, 15)
+        );
+    }
+
     #[test]
     fn cache_key_format() {
         assert_eq!(create_cache_key("wiki", "rust", None), "wiki:rust");
@@ -0,0 +1,289 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+const COINGECKO_API: &str = "https://api.coingecko.com/api/v3";
+
+/// Token prices, market caps, and project links from CoinGecko.
+pub struct CoinGeckoProvider {
+    client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    coins: Vec<SearchCoin>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchCoin {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Market {
+    id: String,
+    symbol: String,
+    name: String,
+    current_price: Option<f64>,
+    market_cap: Option<f64>,
+    market_cap_rank: Option<u32>,
+    total_volume: Option<f64>,
+    price_change_percentage_24h: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoinDetails {
+    links: Links,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Links {
+    #[serde(default)]
+    homepage: Vec<String>,
+    whitepaper: Option<String>,
+    #[serde(default)]
+    blockchain_site: Vec<String>,
+    #[serde(default)]
+    repos_url: HashMap<String, Vec<String>>,
+}
+
+impl Links {
+    /// Non-empty links by kind.
+    fn to_map(&self) -> HashMap<&'static str, Vec<&str>> {
+        fn non_empty(urls: &[String]) -> Vec<&str> {
+            urls.iter()
+                .map(String::as_str)
+                .filter(|url| !url.is_empty())
+                .collect()
+        }
+        let mut links = HashMap::new();
+        links.insert("homepage", non_empty(&self.homepage));
+        links.insert("explorers", non_empty(&self.blockchain_site));
+        links.insert(
+            "repos",
+            self.repos_url
+                .values()
+                .flat_map(|urls| non_empty(urls))
+                .collect(),
+        );
+        if let Some(whitepaper) = self.whitepaper.as_deref().filter(|w| !w.is_empty()) {
+            links.insert("whitepaper", vec![whitepaper]);
+        }
+        links.retain(|_, urls| !urls.is_empty());
+        links
+    }
+}
+
+impl CoinGeckoProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+        }
+    }
+
+    fn format_usd(value: Option<f64>) -> String {
+        match value {
+            None => "N/A".to_string(),
+            Some(v) if v >= 1e9 => format!("${:.2}B", v / 1e9),
+            Some(v) if v >= 1e6 => format!("${:.2}M", v / 1e6),
+            Some(v) if v >= 1.0 => format!("${:.2}", v),
+            Some(v) => format!("${:.6}", v),
+        }
+    }
+
+    fn market_to_entry(market: &Market, links: Option<&Links>) -> KnowledgeEntry {
+        let mut lines = vec![format!(
+            "Price: {} | Market cap: {}{}",
+            Self::format_usd(market.current_price),
+            Self::format_usd(market.market_cap),
+            market
+                .market_cap_rank
+                .map(|rank| format!(" (#{})", rank))
+                .unwrap_or_default()
+        )];
+        if let Some(change) = market.price_change_percentage_24h {
+            let sign = if change >= 0.0 { "+" } else { "" };
+            lines.push(format!(
+                "24h Change: {}{:.2}% | Volume: {}",
+                sign,
+                change,
+                Self::format_usd(market.total_volume)
+            ));
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("id".to_string(), serde_json::json!(market.id));
+        metadata.insert(
+            "symbol".to_string(),
+            serde_json::json!(market.symbol.to_uppercase()),
+        );
+        metadata.insert(
+            "priceUsd".to_string(),
+            serde_json::json!(market.current_price),
+        );
+        metadata.insert(
+            "marketCapUsd".to_string(),
+            serde_json::json!(market.market_cap),
+        );
+        metadata.insert(
+            "marketCapRank".to_string(),
+            serde_json::json!(market.market_cap_rank),
+        );
+        metadata.insert(
+            "volumeUsd".to_string(),
+            serde_json::json!(market.total_volume),
+        );
+        metadata.insert(
+            "change24h".to_string(),
+            serde_json::json!(market.price_change_percentage_24h),
+        );
+        if let Some(links) = links.map(Links::to_map) {
+            if let Some(homepage) = links.get("homepage").and_then(|urls| urls.first()) {
+                lines.push(format!("Homepage: {}", homepage));
+            }
+            metadata.insert("links".to_string(), serde_json::json!(links));
+        }
+
+        KnowledgeEntry {
+            title: format!("{} ({})", market.name, market.symbol.to_uppercase()),
+            summary: lines.join("\n"),
+            url: Some(format!("https://www.coingecko.com/en/coins/{}", market.id)),
+            source: "coingecko".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, String> {
+        let response = self.client.get(url).send().map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("CoinGecko request failed: {}", response.status()));
+        }
+
+        response.json().map_err(|e| e.to_string())
+    }
+
+    fn lookup_coins(&self, query: &str, limit: usize) -> Result<Vec<KnowledgeEntry>, String> {
+        let search: SearchResponse = self.get(&format!(
+            "{}/search?query={}",
+            COINGECKO_API,
+            urlencoding::encode(query)
+        ))?;
+        let ids: Vec<&str> = search
+            .coins
+            .iter()
+            .take(limit)
+            .map(|c| c.id.as_str())
+            .collect();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut markets: Vec<Market> = self.get(&format!(
+            "{}/coins/markets?vs_currency=usd&ids={}",
+            COINGECKO_API,
+            urlencoding::encode(&ids.join(","))
+        ))?;
+        // Keep the search's relevance order
+        markets.sort_by_key(|m| ids.iter().position(|id| *id == m.id));
+
+        // Links need a request per coin, so only the best match gets them.
+        let links = self
+            .get::<CoinDetails>(&format!(
+                "{}/coins/{}?localization=false&tickers=false&market_data=false&community_data=false&developer_data=false",
+                COINGECKO_API, ids[0]
+            ))
+            .ok()
+            .map(|details| details.links);
+
+        Ok(markets
+            .iter()
+            .map(|market| {
+                let links = links.as_ref().filter(|_| market.id == ids[0]);
+                Self::market_to_entry(market, links)
+            })
+            .collect())
+    }
+}
+
+impl Default for CoinGeckoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for CoinGeckoProvider {
+    fn name(&self) -> &'static str {
+        "coingecko"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn lookup(&self, query: &str, options: &LookupOptions) -> LookupResult {
+        let limit = options.max_results.unwrap_or(5);
+
+        match self.lookup_coins(query, limit) {
+            Ok(entries) => LookupResult::success(self.name(), entries),
+            Err(e) => LookupResult::error(self.name(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Requires network
+    fn coingecko_lookup() {
+        let provider = CoinGeckoProvider::new();
+        let result = provider.lookup("ethereum", &LookupOptions::default());
+        assert!(result.success);
+        assert!(!result.entries.is_empty());
+    }
+
+    #[test]
+    fn parse_market_and_links() {
+        let market: Market = serde_json::from_str(
+            r#"{"id": "ethereum", "symbol": "eth", "name": "Ethereum",
+                "current_price": 3012.5, "market_cap": 362000000000.0, "market_cap_rank": 2,
+                "total_volume": 15000000000.0, "price_change_percentage_24h": -1.234}"#,
+        )
+        .unwrap();
+        let details: CoinDetails = serde_json::from_str(
+            r#"{"links": {"homepage": ["https://www.ethereum.org/", "", ""],
+                "whitepaper": "https://github.com/ethereum/wiki/wiki/White-Paper",
+                "blockchain_site": ["https://etherscan.io/", ""],
+                "repos_url": {"github": ["https://github.com/ethereum/go-ethereum"], "bitbucket": []}}}"#,
+        )
+        .unwrap();
+
+        let entry = CoinGeckoProvider::market_to_entry(&market, Some(&details.links));
+        let metadata = entry.metadata.unwrap();
+
+        assert_eq!(entry.title, "Ethereum (ETH)");
+        assert_eq!(
+            entry.summary,
+            "Price: $3012.50 | Market cap: $362.00B (#2)\n\
+             24h Change: -1.23% | Volume: $15.00B\n\
+             Homepage: https://www.ethereum.org/"
+        );
+        assert_eq!(metadata["marketCapRank"], 2);
+        assert_eq!(
+            metadata["links"]["repos"],
+            serde_json::json!(["https://github.com/ethereum/go-ethereum"])
+        );
+        assert_eq!(
+            metadata["links"]["explorers"],
+            serde_json::json!(["https://etherscan.io/"])
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{strip_tags, KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+const CROSSREF_API: &str = "https://api.crossref.org";
+
+/// Papers by DOI or keyword from CrossRef, which covers journals and
+/// conferences beyond arXiv.
+pub struct CrossrefProvider {
+    client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkResponse {
+    message: Work,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    message: SearchMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchMessage {
+    items: Vec<Work>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Work {
+    #[serde(rename = "DOI")]
+    doi: String,
+    #[serde(default)]
+    title: Vec<String>,
+    #[serde(default)]
+    author: Vec<Author>,
+    #[serde(default)]
+    container_title: Vec<String>,
+    issued: Option<DateParts>,
+    #[serde(default)]
+    is_referenced_by_count: u64,
+    publisher: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    volume: Option<String>,
+    issue: Option<String>,
+    page: Option<String>,
+    #[serde(rename = "abstract")]
+    abstract_text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    given: Option<String>,
+    family: Option<String>,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct DateParts {
+    date_parts: Vec<Vec<Option<i32>>>,
+}
+
+impl Author {
+    fn display(&self) -> Option<String> {
+        match (&self.given, &self.family) {
+            (Some(given), Some(family)) => Some(format!("{} {}", given, family)),
+            (None, Some(family)) => Some(family.clone()),
+            _ => self.name.clone(),
+        }
+    }
+
+    /// `Family, Given`, as BibTeX expects.
+    fn bibtex(&self) -> Option<String> {
+        match (&self.given, &self.family) {
+            (Some(given), Some(family)) => Some(format!("{}, {}", family, given)),
+            (None, Some(family)) => Some(family.clone()),
+            _ => self.name.clone(),
+        }
+    }
+}
+
+impl Work {
+    fn year(&self) -> Option<i32> {
+        self.issued.as_ref()?.date_parts.first()?.first().copied()?
+    }
+
+    fn title(&self) -> String {
+        self.title
+            .first()
+            .cloned()
+            .unwrap_or_else(|| self.doi.clone())
+    }
+
+    fn bibtex(&self) -> String {
+        let entry_type = match self.kind.as_deref() {
+            Some("journal-article") => "article",
+            Some("proceedings-article") => "inproceedings",
+            Some("book") | Some("monograph") => "book",
+            Some("book-chapter") => "incollection",
+            _ => "misc",
+        };
+        let first_author = self
+            .author
+            .first()
+            .and_then(|a| a.family.clone().or_else(|| a.name.clone()))
+            .unwrap_or_else(|| "anon".to_string());
+        let key: String = format!(
+            "{}{}",
+            first_author,
+            self.year().map(|y| y.to_string()).unwrap_or_default()
+        )
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect();
+
+        let authors: Vec<String> = self.author.iter().filter_map(Author::bibtex).collect();
+        let mut fields = vec![("title", self.title())];
+        if !authors.is_empty() {
+            fields.push(("author", authors.join(" and ")));
+        }
+        if let Some(container) = self.container_title.first() {
+            let field = if entry_type == "article" {
+                "journal"
+            } else {
+                "booktitle"
+            };
+            fields.push((field, container.clone()));
+        }
+        if let Some(year) = self.year() {
+            fields.push(("year", year.to_string()));
+        }
+        for (name, value) in [
+            ("volume", &self.volume),
+            ("number", &self.issue),
+            ("pages", &self.page),
+            ("publisher", &self.publisher),
+        ] {
+            if let Some(value) = value {
+                fields.push((name, value.clone()));
+            }
+        }
+        fields.push(("doi", self.doi.clone()));
+
+        let body: Vec<String> = fields
+            .iter()
+            .map(|(name, value)| format!("  {} = {{{}}}", name, value))
+            .collect();
+        format!("@{}{{{},\n{}\n}}", entry_type, key, body.join(",\n"))
+    }
+
+    fn to_entry(&self) -> KnowledgeEntry {
+        let authors: Vec<String> = self.author.iter().filter_map(Author::display).collect();
+        let author_list = if authors.len() > 3 {
+            format!("{} et al.", authors[..3].join(", "))
+        } else {
+            authors.join(", ")
+        };
+
+        let mut lines = Vec::new();
+        let venue = self.container_title.first().or(self.publisher.as_ref());
+        match (venue, self.year()) {
+            (Some(venue), Some(year)) => {
+                lines.push(format!("{} - {} ({})", author_list, venue, year))
+            }
+            (None, Some(year)) => lines.push(format!("{} ({})", author_list, year)),
+            _ => lines.push(author_list),
+        }
+        lines.push(format!("Citations: {}", self.is_referenced_by_count));
+        // Abstracts come as JATS XML
+        if let Some(text) = &self.abstract_text {
+            let text = strip_tags(text);
+            let text = if text.chars().count() > 400 {
+                format!("{}...", text.chars().take(400).collect::<String>())
+            } else {
+                text
+            };
+            lines.push(String::new());
+            lines.push(text);
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("doi".to_string(), serde_json::json!(self.doi));
+        metadata.insert("authors".to_string(), serde_json::json!(authors));
+        metadata.insert(
+            "citationCount".to_string(),
+            serde_json::json!(self.is_referenced_by_count),
+        );
+        if let Some(year) = self.year() {
+            metadata.insert("year".to_string(), serde_json::json!(year));
+        }
+        if let Some(container) = self.container_title.first() {
+            metadata.insert("venue".to_string(), serde_json::json!(container));
+        }
+        if let Some(publisher) = &self.publisher {
+            metadata.insert("publisher".to_string(), serde_json::json!(publisher));
+        }
+        if let Some(kind) = &self.kind {
+            metadata.insert("type".to_string(), serde_json::json!(kind));
+        }
+        metadata.insert("bibtex".to_string(), serde_json::json!(self.bibtex()));
+
+        KnowledgeEntry {
+            title: self.title(),
+            summary: lines.join("\n"),
+            url: Some(format!("https://doi.org/{}", self.doi)),
+            source: "crossref".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+}
+
+impl CrossrefProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+        }
+    }
+
+    /// The DOI in `query`, given bare (`10.1000/xyz`), as `doi:10...`, or as
+    /// a doi.org URL.
+    fn extract_doi(query: &str) -> Option<&str> {
+        let query = query.trim();
+        let doi = query
+            .strip_prefix("https://doi.org/")
+            .or_else(|| query.strip_prefix("http://doi.org/"))
+            .or_else(|| query.strip_prefix("doi:"))
+            .unwrap_or(query);
+        (doi.starts_with("10.") && doi.contains('/') && !doi.contains(' ')).then_some(doi)
+    }
+
+    /// `doi` percent-encoded for the URL path, except for its `/`, which the
+    /// API expects unencoded. DOIs may contain `#`, `?`, `%`, and spaces.
+    fn doi_path(doi: &str) -> String {
+        urlencoding::encode(doi).replace("%2F", "/")
+    }
+
+    fn lookup_doi(&self, doi: &str) -> Result<Option<KnowledgeEntry>, String> {
+        let url = format!("{}/works/{}", CROSSREF_API, Self::doi_path(doi));
+
+        let response = self.client.get(&url).send().map_err(|e| e.to_string())?;
+
+        if response.status().as_u16() == 404 {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            return Err(format!("lookup failed: {}", response.status()));
+        }
+
+        let data: WorkResponse = response.json().map_err(|e| e.to_string())?;
+        Ok(Some(data.message.to_entry()))
+    }
+
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<KnowledgeEntry>, String> {
+        let url = format!(
+            "{}/works?query={}&rows={}",
+            CROSSREF_API,
+            urlencoding::encode(query),
+            limit
+        );
+
+        let response = self.client.get(&url).send().map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("search failed: {}", response.status()));
+        }
+
+        let data: SearchResponse = response.json().map_err(|e| e.to_string())?;
+        Ok(data.message.items.iter().map(Work::to_entry).collect())
+    }
+}
+
+impl Default for CrossrefProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for CrossrefProvider {
+    fn name(&self) -> &'static str {
+        "crossref"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn lookup(&self, query: &str, options: &LookupOptions) -> LookupResult {
+        let limit = options.max_results.unwrap_or(5);
+
+        if let Some(doi) = Self::extract_doi(query) {
+            return match self.lookup_doi(doi) {
+                Ok(entry) => LookupResult::success(self.name(), entry.into_iter().collect()),
+                Err(e) => LookupResult::error(self.name(), e),
+            };
+        }
+
+        match self.search(query, limit) {
+            Ok(entries) => LookupResult::success(self.name(), entries),
+            Err(e) => LookupResult::error(self.name(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Requires network
+    fn crossref_lookup() {
+        let provider = CrossrefProvider::new();
+        let result = provider.lookup("10.1038/nature14539", &LookupOptions::default());
+        assert!(result.success);
+        assert!(!result.entries.is_empty());
+    }
+
+    #[test]
+    fn parse_work_and_bibtex() {
+        let json = r#"{"message": {
+            "DOI": "10.1038/nature14539",
+            "title": ["Deep learning"],
+            "author": [
+                {"given": "Yann", "family": "LeCun"},
+                {"given": "Yoshua", "family": "Bengio"}
+            ],
+            "container-title": ["Nature"],
+            "issued": {"date-parts": [[2015, 5, 27]]},
+            "is-referenced-by-count": 50000,
+            "publisher": "Springer",
+            "type": "journal-article",
+            "volume": "521",
+            "page": "436-444",
+            "abstract": "<jats:p>Deep learning allows  models.</jats:p>"
+        }}"#;
+
+        let work: WorkResponse = serde_json::from_str(json).unwrap();
+        let entry = work.message.to_entry();
+        let metadata = entry.metadata.unwrap();
+
+        assert_eq!(entry.title, "Deep learning");
+        assert_eq!(
+            entry.url.as_deref(),
+            Some("https://doi.org/10.1038/nature14539")
+        );
+        assert!(entry.summary.contains("Nature (2015)"));
+        assert!(entry.summary.contains("Deep learning allows models."));
+        assert_eq!(metadata["citationCount"], 50000);
+        let bibtex = metadata["bibtex"].as_str().unwrap();
+        assert!(bibtex.starts_with("@article{LeCun2015,\n"));
+        assert!(bibtex.contains("author = {LeCun, Yann and Bengio, Yoshua}"));
+        assert!(bibtex.contains("journal = {Nature}"));
+    }
+
+    #[test]
+    fn extracts_dois() {
+        assert_eq!(
+            CrossrefProvider::extract_doi("https://doi.org/10.1000/xyz"),
+            Some("10.1000/xyz")
+        );
+        assert_eq!(
+            CrossrefProvider::extract_doi("doi:10.1000/xyz"),
+            Some("10.1000/xyz")
+        );
+        assert_eq!(CrossrefProvider::extract_doi("deep learning"), None);
+    }
+
+    #[test]
+    fn encodes_dois_for_the_path() {
+        assert_eq!(CrossrefProvider::doi_path("10.1000/xyz"), "10.1000/xyz");
+        assert_eq!(
+            CrossrefProvider::doi_path("10.1002/(SICI)1097#4?a%b c"),
+            "10.1002/%28SICI%291097%234%3Fa%25b%20c"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::LookupResult;
+
+/// Lookup results kept as one JSON file per cache key, so they survive
+/// server restarts. Write failures are ignored: the cache is best effort.
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    /// Seconds since the Unix epoch.
+    expires_at: u64,
+    value: LookupResult,
+}
+
+impl DiskCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The cached result for `key` and how long it has left to live.
+    pub fn get(&self, key: &str) -> Option<(LookupResult, Duration)> {
+        let path = self.path(key);
+        let text = fs::read_to_string(&path).ok()?;
+        let entry: DiskEntry = serde_json::from_str(&text).ok()?;
+        if entry.key != key {
+            return None;
+        }
+        let now = now();
+        if entry.expires_at <= now {
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+        Some((entry.value, Duration::from_secs(entry.expires_at - now)))
+    }
+
+    pub fn set(&self, key: &str, value: &LookupResult, ttl: Duration) {
+        let entry = DiskEntry {
+            key: key.to_string(),
+            expires_at: now() + ttl.as_secs(),
+            value: value.clone(),
+        };
+        let Ok(text) = serde_json::to_string(&entry) else {
+            return;
+        };
+        if fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        // Write then rename, so concurrent readers never see a partial file.
+        // Each write gets its own temp file, so concurrent writers of a key
+        // can't interleave.
+        static WRITES: AtomicU64 = AtomicU64::new(0);
+        let path = self.path(key);
+        let tmp = path.with_extension(format!(
+            "tmp{}-{}",
+            std::process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed)
+        ));
+        if fs::write(&tmp, text).is_ok() && fs::rename(&tmp, &path).is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+    }
+
+    pub fn clear(&self) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            if entry.path().extension().is_some_and(|ext| ext == "json") {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    /// Named by a BLAKE3 hash of the key, which (unlike `DefaultHasher`)
+    /// stays the same across Rust releases.
+    fn path(&self, key: &str) -> PathBuf {
+        let hash = blake3::hash(key.as_bytes()).to_hex();
+        self.dir.join(format!("{}.json", &hash[..32]))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_persist_until_they_expire() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let result = LookupResult::success("test", Vec::new());
+
+        DiskCache::new(dir.path().to_path_buf()).set("test:rust", &result, Duration::from_secs(60));
+        DiskCache::new(dir.path().to_path_buf()).set("test:gone", &result, Duration::ZERO);
+
+        // A new instance stands in for a restarted server.
+        let cache = DiskCache::new(dir.path().to_path_buf());
+        assert_eq!(cache.get("test:rust").unwrap().0.provider, "test");
+        assert!(cache.get("test:gone").is_none());
+        assert!(cache.get("test:other").is_none());
+
+        cache.clear();
+        assert!(cache.get("test:rust").is_none());
+    }
+
+    #[test]
+    fn file_names_are_stable() {
+        let cache = DiskCache::new(PathBuf::from("/cache"));
+        assert_eq!(
+            cache.path("wikipedia:rust:5"),
+            PathBuf::from("/cache/445aa151f5d883cca91adf1eb0ceaaf0.json")
+        );
+    }
+
+    #[test]
+    fn concurrent_writes_of_a_key_leave_one_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cache = DiskCache::new(dir.path().to_path_buf());
+        let result = LookupResult::success("test", Vec::new());
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| cache.set("test:rust", &result, Duration::from_secs(60)));
+            }
+        });
+
+        assert_eq!(cache.get("test:rust").unwrap().0.provider, "test");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
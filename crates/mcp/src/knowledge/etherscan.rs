@@ -0,0 +1,327 @@
+use reqwest::blocking::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+const ETHERSCAN_API: &str = "https://api.etherscan.io/v2/api?chainid=1";
+
+/// Ethereum addresses resolved to contract names, verification status,
+/// token details, and balances via Etherscan.
+pub struct EtherscanProvider {
+    client: Client,
+    api_key: String,
+}
+
+/// Etherscan's envelope; `result` is a message string when `status` is "0".
+#[derive(Debug, Deserialize)]
+struct ApiResponse {
+    status: String,
+    message: String,
+    result: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SourceCode {
+    #[serde(default)]
+    contract_name: String,
+    #[serde(default)]
+    compiler_version: String,
+    #[serde(default)]
+    proxy: String,
+    #[serde(default)]
+    implementation: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenTransfer {
+    token_name: String,
+    token_symbol: String,
+    token_decimal: String,
+}
+
+#[derive(Debug)]
+struct AddressInfo {
+    source: Option<SourceCode>,
+    token: Option<TokenTransfer>,
+    balance_wei: Option<u128>,
+}
+
+impl EtherscanProvider {
+    pub fn new() -> Self {
+        Self::with_api_key("")
+    }
+
+    pub fn with_api_key(api_key: impl Into<String>) -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            api_key: api_key.into(),
+        }
+    }
+
+    fn is_address(query: &str) -> bool {
+        query.len() == 42
+            && query.starts_with("0x")
+            && query[2..].chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    fn call<T: DeserializeOwned>(&self, params: &str) -> Result<Option<T>, String> {
+        // Errors leave out the URL, which carries the API key.
+        let url = format!("{}&{}&apikey={}", ETHERSCAN_API, params, self.api_key);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| e.without_url().to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("Etherscan request failed: {}", response.status()));
+        }
+
+        let data: ApiResponse = response.json().map_err(|e| e.without_url().to_string())?;
+        Self::parse_result(data)
+    }
+
+    /// The result, `None` for an empty one ("No transactions found"), or
+    /// Etherscan's error message.
+    fn parse_result<T: DeserializeOwned>(data: ApiResponse) -> Result<Option<T>, String> {
+        if data.status != "1" {
+            return match data.result {
+                serde_json::Value::Array(_) => Ok(None),
+                serde_json::Value::String(reason) => Err(format!("{}: {}", data.message, reason)),
+                _ => Err(data.message),
+            };
+        }
+        serde_json::from_value(data.result)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    }
+
+    fn address_info(&self, address: &str) -> Result<AddressInfo, String> {
+        let source: Option<Vec<SourceCode>> = self.call(&format!(
+            "module=contract&action=getsourcecode&address={}",
+            address
+        ))?;
+        // The latest transfer of the token names it; tokeninfo is a paid endpoint.
+        let token: Option<Vec<TokenTransfer>> = self.call(&format!(
+            "module=account&action=tokentx&contractaddress={}&page=1&offset=1&sort=desc",
+            address
+        ))?;
+        let balance: Option<String> = self.call(&format!(
+            "module=account&action=balance&address={}&tag=latest",
+            address
+        ))?;
+
+        Ok(AddressInfo {
+            source: source.and_then(|s| s.into_iter().next()),
+            token: token.and_then(|t| t.into_iter().next()),
+            balance_wei: balance.and_then(|b| b.parse().ok()),
+        })
+    }
+
+    fn info_to_entry(address: &str, info: &AddressInfo) -> KnowledgeEntry {
+        let contract = info.source.as_ref().filter(|s| !s.contract_name.is_empty());
+
+        let mut lines = Vec::new();
+        let mut metadata = HashMap::new();
+        metadata.insert("address".to_string(), serde_json::json!(address));
+        metadata.insert(
+            "verified".to_string(),
+            serde_json::json!(contract.is_some()),
+        );
+
+        match contract {
+            Some(source) => {
+                lines.push(format!(
+                    "Verified contract: {} ({})",
+                    source.contract_name, source.compiler_version
+                ));
+                metadata.insert(
+                    "contractName".to_string(),
+                    serde_json::json!(source.contract_name),
+                );
+                metadata.insert(
+                    "compilerVersion".to_string(),
+                    serde_json::json!(source.compiler_version),
+                );
+                if source.proxy == "1" && !source.implementation.is_empty() {
+                    lines.push(format!("Proxy for: {}", source.implementation));
+                    metadata.insert(
+                        "implementation".to_string(),
+                        serde_json::json!(source.implementation),
+                    );
+                }
+            }
+            None => lines.push("Unverified contract or externally owned account".to_string()),
+        }
+
+        if let Some(token) = &info.token {
+            lines.push(format!(
+                "Token: {} ({}), {} decimals",
+                token.token_name, token.token_symbol, token.token_decimal
+            ));
+            metadata.insert("tokenName".to_string(), serde_json::json!(token.token_name));
+            metadata.insert(
+                "tokenSymbol".to_string(),
+                serde_json::json!(token.token_symbol),
+            );
+            metadata.insert(
+                "tokenDecimals".to_string(),
+                serde_json::json!(token.token_decimal.parse::<u32>().ok()),
+            );
+        }
+
+        if let Some(wei) = info.balance_wei {
+            let eth = wei as f64 / 1e18;
+            lines.push(format!("Balance: {:.4} ETH", eth));
+            metadata.insert("balanceEth".to_string(), serde_json::json!(eth));
+        }
+
+        let title = contract
+            .map(|s| s.contract_name.clone())
+            .or_else(|| info.token.as_ref().map(|t| t.token_name.clone()))
+            .unwrap_or_else(|| address.to_string());
+
+        KnowledgeEntry {
+            title,
+            summary: lines.join("\n"),
+            url: Some(format!("https://etherscan.io/address/{}", address)),
+            source: "etherscan".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+}
+
+impl Default for EtherscanProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for EtherscanProvider {
+    fn name(&self) -> &'static str {
+        "etherscan"
+    }
+
+    fn is_available(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    fn accepts(&self, query: &str) -> bool {
+        Self::is_address(query.trim())
+    }
+
+    fn lookup(&self, query: &str, _options: &LookupOptions) -> LookupResult {
+        if self.api_key.is_empty() {
+            return LookupResult::error(self.name(), "Etherscan API key not configured");
+        }
+
+        // Only addresses can be resolved
+        let address = query.trim();
+        if !Self::is_address(address) {
+            return LookupResult::success(self.name(), vec![]);
+        }
+
+        match self.address_info(address) {
+            Ok(info) => {
+                LookupResult::success(self.name(), vec![Self::info_to_entry(address, &info)])
+            }
+            Err(e) => LookupResult::error(self.name(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn etherscan_requires_api_key() {
+        let provider = EtherscanProvider::new();
+        assert!(!provider.is_available());
+    }
+
+    #[test]
+    fn parse_address_info() {
+        let source = r#"{"status": "1", "message": "OK", "result": [{
+            "SourceCode": "pragma solidity ^0.4.18;", "ABI": "[]",
+            "ContractName": "TetherToken", "CompilerVersion": "v0.4.18+commit.9cf6e910",
+            "Proxy": "0", "Implementation": ""
+        }]}"#;
+        let token = r#"{"status": "1", "message": "OK", "result": [{
+            "tokenName": "Tether USD", "tokenSymbol": "USDT", "tokenDecimal": "6"
+        }]}"#;
+        let balance = r#"{"status": "1", "message": "OK", "result": "1500000000000000000"}"#;
+
+        let parse = |json: &str| {
+            EtherscanProvider::parse_result::<serde_json::Value>(
+                serde_json::from_str(json).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+        };
+        let info = AddressInfo {
+            source: serde_json::from_value::<Vec<SourceCode>>(parse(source))
+                .unwrap()
+                .pop(),
+            token: serde_json::from_value::<Vec<TokenTransfer>>(parse(token))
+                .unwrap()
+                .pop(),
+            balance_wei: parse(balance).as_str().and_then(|b| b.parse().ok()),
+        };
+
+        let address = "0xdac17f958d2ee523a2206206994597c13d831ec7";
+        let entry = EtherscanProvider::info_to_entry(address, &info);
+        let metadata = entry.metadata.unwrap();
+
+        assert_eq!(entry.title, "TetherToken");
+        assert_eq!(
+            entry.summary,
+            "Verified contract: TetherToken (v0.4.18+commit.9cf6e910)\n\
+             Token: Tether USD (USDT), 6 decimals\n\
+             Balance: 1.5000 ETH"
+        );
+        assert_eq!(metadata["verified"], true);
+        assert_eq!(metadata["tokenDecimals"], 6);
+    }
+
+    #[test]
+    fn empty_results_and_errors() {
+        let empty: ApiResponse = serde_json::from_str(
+            r#"{"status": "0", "message": "No transactions found", "result": []}"#,
+        )
+        .unwrap();
+        assert!(EtherscanProvider::parse_result::<Vec<TokenTransfer>>(empty)
+            .unwrap()
+            .is_none());
+
+        let error: ApiResponse = serde_json::from_str(
+            r#"{"status": "0", "message": "NOTOK", "result": "Invalid API Key"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            EtherscanProvider::parse_result::<String>(error).unwrap_err(),
+            "NOTOK: Invalid API Key"
+        );
+    }
+
+    #[test]
+    fn only_addresses_are_looked_up() {
+        assert!(EtherscanProvider::is_address(
+            "0xdac17f958d2ee523a2206206994597c13d831ec7"
+        ));
+        assert!(!EtherscanProvider::is_address("0xdac17f"));
+        assert!(!EtherscanProvider::is_address("ethereum"));
+
+        let result =
+            EtherscanProvider::with_api_key("key").lookup("uniswap", &LookupOptions::default());
+        assert!(result.success && result.entries.is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+const GUTENDEX_API: &str = "https://gutendex.com/books";
+
+/// Public-domain books from Project Gutenberg, via Gutendex, with direct
+/// download links.
+pub struct GutenbergProvider {
+    client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    results: Vec<Book>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Book {
+    id: u64,
+    title: String,
+    #[serde(default)]
+    authors: Vec<Person>,
+    #[serde(default)]
+    subjects: Vec<String>,
+    #[serde(default)]
+    languages: Vec<String>,
+    #[serde(default)]
+    formats: HashMap<String, String>,
+    #[serde(default)]
+    download_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Person {
+    name: String,
+    birth_year: Option<i32>,
+    death_year: Option<i32>,
+}
+
+impl Person {
+    fn display(&self) -> String {
+        match (self.birth_year, self.death_year) {
+            (Some(birth), Some(death)) => format!("{} ({}-{})", self.name, birth, death),
+            _ => self.name.clone(),
+        }
+    }
+}
+
+impl GutenbergProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+        }
+    }
+
+    /// The download URL for the first format whose MIME type starts with
+    /// `mime` (formats carry parameters like `; charset=utf-8`).
+    fn format_url<'a>(book: &'a Book, mime: &str) -> Option<&'a str> {
+        let mut formats: Vec<(&String, &String)> = book.formats.iter().collect();
+        formats.sort();
+        formats
+            .into_iter()
+            .find(|(kind, _)| kind.starts_with(mime))
+            .map(|(_, url)| url.as_str())
+    }
+
+    fn book_to_entry(book: &Book) -> KnowledgeEntry {
+        let authors: Vec<String> = book.authors.iter().map(Person::display).collect();
+        let epub = Self::format_url(book, "application/epub+zip");
+        let text = Self::format_url(book, "text/plain");
+
+        let mut lines = Vec::new();
+        if !authors.is_empty() {
+            lines.push(authors.join(", "));
+        }
+        if !book.subjects.is_empty() {
+            let subjects: Vec<&str> = book.subjects.iter().take(3).map(String::as_str).collect();
+            lines.push(format!("Subjects: {}", subjects.join("; ")));
+        }
+        lines.push(format!(
+            "Languages: {} | Downloads: {}",
+            book.languages.join(", "),
+            book.download_count
+        ));
+        if let Some(epub) = epub {
+            lines.push(format!("EPUB: {}", epub));
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("gutenbergId".to_string(), serde_json::json!(book.id));
+        metadata.insert(
+            "authors".to_string(),
+            serde_json::json!(book.authors.iter().map(|a| &a.name).collect::<Vec<_>>()),
+        );
+        metadata.insert("subjects".to_string(), serde_json::json!(book.subjects));
+        metadata.insert("languages".to_string(), serde_json::json!(book.languages));
+        metadata.insert(
+            "downloadCount".to_string(),
+            serde_json::json!(book.download_count),
+        );
+        if let Some(epub) = epub {
+            metadata.insert("epubUrl".to_string(), serde_json::json!(epub));
+        }
+        if let Some(text) = text {
+            metadata.insert("textUrl".to_string(), serde_json::json!(text));
+        }
+
+        KnowledgeEntry {
+            title: book.title.clone(),
+            summary: lines.join("\n"),
+            url: Some(format!("https://www.gutenberg.org/ebooks/{}", book.id)),
+            source: "gutenberg".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<KnowledgeEntry>, String> {
+        let url = format!("{}?search={}", GUTENDEX_API, urlencoding::encode(query));
+
+        let response = self.client.get(&url).send().map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("search failed: {}", response.status()));
+        }
+
+        let data: SearchResponse = response.json().map_err(|e| e.to_string())?;
+
+        Ok(data
+            .results
+            .iter()
+            .take(limit)
+            .map(Self::book_to_entry)
+            .collect())
+    }
+}
+
+impl Default for GutenbergProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for GutenbergProvider {
+    fn name(&self) -> &'static str {
+        "gutenberg"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn lookup(&self, query: &str, options: &LookupOptions) -> LookupResult {
+        let limit = options.max_results.unwrap_or(5);
+
+        match self.search(query, limit) {
+            Ok(entries) => LookupResult::success(self.name(), entries),
+            Err(e) => LookupResult::error(self.name(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Requires network
+    fn gutenberg_search() {
+        let provider = GutenbergProvider::new();
+        let result = provider.lookup("moby dick", &LookupOptions::default());
+        assert!(result.success);
+        assert!(!result.entries.is_empty());
+    }
+
+    #[test]
+    fn parse_book() {
+        let json = r#"{"count": 1, "results": [{
+            "id": 2701,
+            "title": "Moby Dick; Or, The Whale",
+            "authors": [{"name": "Melville, Herman", "birth_year": 1819, "death_year": 1891}],
+            "subjects": ["Whaling -- Fiction", "Sea stories"],
+            "languages": ["en"],
+            "formats": {
+                "application/epub+zip": "https://www.gutenberg.org/ebooks/2701.epub3.images",
+                "text/plain; charset=us-ascii": "https://www.gutenberg.org/ebooks/2701.txt.utf-8"
+            },
+            "download_count": 12345
+        }]}"#;
+
+        let data: SearchResponse = serde_json::from_str(json).unwrap();
+        let entry = GutenbergProvider::book_to_entry(&data.results[0]);
+        let metadata = entry.metadata.unwrap();
+
+        assert_eq!(
+            entry.url.as_deref(),
+            Some("https://www.gutenberg.org/ebooks/2701")
+        );
+        assert!(entry.summary.starts_with("Melville, Herman (1819-1891)\n"));
+        assert_eq!(
+            metadata["epubUrl"],
+            "https://www.gutenberg.org/ebooks/2701.epub3.images"
+        );
+        assert_eq!(
+            metadata["textUrl"],
+            "https://www.gutenberg.org/ebooks/2701.txt.utf-8"
+        );
+    }
+}
@@ -1,46 +1,76 @@
+mod archive_org;
 mod arxiv;
 mod cache;
+mod coingecko;
 mod crates_io;
+mod crossref;
 mod dbpedia;
 mod defillama;
+mod disk_cache;
+mod etherscan;
 mod github;
+mod gutenberg;
 mod musicbrainz;
+mod nominatim;
 mod npm;
+mod nvd;
 mod openlibrary;
+mod pubmed;
 mod reddit;
+mod rss;
 mod shodan;
 mod sourceforge;
 mod stackoverflow;
+mod tmdb;
 mod wikiart;
 mod wikidata;
 mod wikipedia;
+mod wiktionary;
+mod youtube;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
+use crate::config::KnowledgeConfig;
 use cache::{create_cache_key, LruCache};
+use disk_cache::DiskCache;
 
+pub use archive_org::ArchiveOrgProvider;
 pub use arxiv::ArxivProvider;
+pub use cache::CacheStats;
+pub use coingecko::CoinGeckoProvider;
 pub use crates_io::CratesIoProvider;
+pub use crossref::CrossrefProvider;
 pub use dbpedia::DBpediaProvider;
 pub use defillama::DefiLlamaProvider;
+pub use etherscan::EtherscanProvider;
 pub use github::GitHubProvider;
+pub use gutenberg::GutenbergProvider;
 pub use musicbrainz::MusicBrainzProvider;
+pub use nominatim::NominatimProvider;
 pub use npm::NpmProvider;
+pub use nvd::NvdProvider;
 pub use openlibrary::OpenLibraryProvider;
+pub use pubmed::PubMedProvider;
 pub use reddit::RedditProvider;
+pub use rss::RssProvider;
 pub use shodan::ShodanProvider;
 pub use sourceforge::SourceForgeProvider;
 pub use stackoverflow::StackOverflowProvider;
+pub use tmdb::TmdbProvider;
 pub use wikiart::WikiArtProvider;
 pub use wikidata::WikidataProvider;
 pub use wikipedia::WikipediaProvider;
+pub use wiktionary::WiktionaryProvider;
+pub use youtube::YouTubeProvider;
 
 const PROVIDER_ORDER: &[&str] = &[
     "wikipedia",
     "dbpedia",
     "wikidata",
+    "wiktionary",
     "github",
     "sourceforge",
     "npm",
@@ -48,11 +78,23 @@ const PROVIDER_ORDER: &[&str] = &[
     "stackoverflow",
     "reddit",
     "openlibrary",
+    "gutenberg",
     "arxiv",
+    "crossref",
+    "pubmed",
     "musicbrainz",
+    "youtube",
+    "tmdb",
     "wikiart",
     "defillama",
+    "coingecko",
+    "etherscan",
+    "archive.org",
+    "rss",
     "shodan",
+    "nvd",
+    // Last, so `auto` only geocodes queries nothing else answers.
+    "nominatim",
 ];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -101,16 +143,84 @@ impl LookupResult {
     }
 }
 
+/// Entries merged from several providers by [`KnowledgeRegistry::multi_lookup`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MultiLookupResult {
+    /// Providers that were queried, in priority order.
+    pub providers: Vec<String>,
+    /// Entries from every provider, highest-priority provider first, with
+    /// duplicates (same URL, or same title when there is no URL) dropped.
+    pub entries: Vec<KnowledgeEntry>,
+    /// Error message for each provider whose lookup failed.
+    pub errors: HashMap<String, String>,
+}
+
+/// Whether a provider can be used, as reported by
+/// [`KnowledgeRegistry::provider_statuses`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderState {
+    Available,
+    /// Enabled, but failing its availability check (e.g. a missing API key).
+    Unavailable,
+    /// Enabled, but offline mode is on.
+    Offline,
+    /// Turned off in the config.
+    Disabled,
+}
+
+impl ProviderState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Available => "available",
+            Self::Unavailable => "unavailable",
+            Self::Offline => "offline",
+            Self::Disabled => "disabled",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderStatus {
+    pub name: String,
+    pub state: ProviderState,
+    /// Position in the auto-lookup order, from 1; `None` when `auto` skips it.
+    pub auto_rank: Option<usize>,
+}
+
 pub trait KnowledgeProvider: Send + Sync {
     fn name(&self) -> &'static str;
     fn is_available(&self) -> bool;
     fn lookup(&self, query: &str, options: &LookupOptions) -> LookupResult;
+
+    /// Whether `query` is something this provider can look up at all.
+    /// [`KnowledgeRegistry::multi_lookup`] over every provider skips those
+    /// that only take URLs or addresses when given anything else.
+    fn accepts(&self, _query: &str) -> bool {
+        true
+    }
 }
 
+/// Most lookups [`KnowledgeRegistry::multi_lookup`] runs at once.
+const MULTI_LOOKUP_THREADS: usize = 8;
+
+/// Why lookups fail in offline mode.
+const OFFLINE_ERROR: &str = "offline mode: network lookups are disabled";
+
 pub struct KnowledgeRegistry {
     providers: HashMap<String, Box<dyn KnowledgeProvider>>,
     cache: Mutex<LruCache>,
     cache_enabled: bool,
+    disk_cache: Option<DiskCache>,
+    ttl: Duration,
+    provider_ttls: HashMap<String, Duration>,
+    /// Answers only from the caches, never the network.
+    offline: bool,
+    /// Each provider's last `is_available()` answer and when it was given.
+    availability: Mutex<HashMap<String, (bool, Instant)>>,
+    availability_ttl: Duration,
+    /// Providers `auto_lookup` tries, in order.
+    auto_order: Vec<String>,
 }
 
 impl KnowledgeRegistry {
@@ -119,18 +229,65 @@ impl KnowledgeRegistry {
     }
 
     pub fn with_cache(enabled: bool, max_size: usize, ttl_minutes: u64) -> Self {
+        Self::build(enabled, max_size, ttl_minutes, &KnowledgeConfig::default())
+    }
+
+    /// A registry with the providers, cache, and API keys set in the server
+    /// config. API keys and provider lists in the environment take precedence.
+    pub fn from_config(config: &KnowledgeConfig) -> Self {
+        let cache_size = config.cache_size.unwrap_or(100);
+        let mut registry = Self::build(
+            cache_size > 0,
+            cache_size,
+            config.cache_ttl_minutes.unwrap_or(15),
+            config,
+        );
+        let enabled = Self::configured_providers(config);
+        registry
+            .providers
+            .retain(|name, _| enabled.contains(&name.as_str()));
+        if let Some(order) = auto_order(config) {
+            registry.auto_order = order;
+        }
+        registry.disk_cache = config.disk_cache_dir().map(DiskCache::new);
+        registry.provider_ttls = config
+            .provider_ttl_minutes
+            .iter()
+            .map(|(name, minutes)| (name.clone(), Duration::from_secs(minutes * 60)))
+            .collect();
+        registry.offline |= config.offline;
+        if let Some(minutes) = config.availability_ttl_minutes {
+            registry.availability_ttl = Duration::from_secs(minutes * 60);
+        }
+        registry
+    }
+
+    fn build(
+        cache_enabled: bool,
+        max_size: usize,
+        ttl_minutes: u64,
+        config: &KnowledgeConfig,
+    ) -> Self {
         let mut registry = Self {
             providers: HashMap::new(),
             cache: Mutex::new(LruCache::new(max_size, ttl_minutes)),
-            cache_enabled: enabled,
+            cache_enabled,
+            disk_cache: None,
+            ttl: Duration::from_secs(ttl_minutes * 60),
+            provider_ttls: HashMap::new(),
+            offline: env_flag("VAULT_TREE_OFFLINE"),
+            availability: Mutex::new(HashMap::new()),
+            availability_ttl: Duration::from_secs(5 * 60),
+            auto_order: PROVIDER_ORDER.iter().map(|name| name.to_string()).collect(),
         };
         registry.register(Box::new(WikipediaProvider::new()));
         registry.register(Box::new(DBpediaProvider::new()));
         registry.register(Box::new(WikidataProvider::new()));
+        registry.register(Box::new(WiktionaryProvider::new()));
 
-        let github = match std::env::var("GITHUB_TOKEN") {
-            Ok(token) if !token.is_empty() => GitHubProvider::with_token(token),
-            _ => GitHubProvider::new(),
+        let github = match env_key("GITHUB_TOKEN").or(config.github_token.clone()) {
+            Some(token) => GitHubProvider::with_token(token),
+            None => GitHubProvider::new(),
         };
         registry.register(Box::new(github));
 
@@ -140,16 +297,44 @@ impl KnowledgeRegistry {
         registry.register(Box::new(StackOverflowProvider::new()));
         registry.register(Box::new(RedditProvider::new()));
         registry.register(Box::new(OpenLibraryProvider::new()));
+        registry.register(Box::new(GutenbergProvider::new()));
         registry.register(Box::new(ArxivProvider::new()));
+        registry.register(Box::new(CrossrefProvider::new()));
+        registry.register(Box::new(PubMedProvider::new()));
         registry.register(Box::new(MusicBrainzProvider::new()));
+
+        let youtube = match env_key("YOUTUBE_API_KEY").or(config.youtube_api_key.clone()) {
+            Some(key) => YouTubeProvider::with_api_key(key),
+            None => YouTubeProvider::new(),
+        };
+        registry.register(Box::new(youtube));
+
+        let tmdb = match env_key("TMDB_API_KEY").or(config.tmdb_api_key.clone()) {
+            Some(key) => TmdbProvider::with_api_key(key),
+            None => TmdbProvider::new(),
+        };
+        registry.register(Box::new(tmdb));
+
         registry.register(Box::new(WikiArtProvider::new()));
         registry.register(Box::new(DefiLlamaProvider::new()));
+        registry.register(Box::new(CoinGeckoProvider::new()));
 
-        let shodan = match std::env::var("SHODAN_API_KEY") {
-            Ok(key) if !key.is_empty() => ShodanProvider::with_api_key(key),
-            _ => ShodanProvider::new(),
+        let etherscan = match env_key("ETHERSCAN_API_KEY").or(config.etherscan_api_key.clone()) {
+            Some(key) => EtherscanProvider::with_api_key(key),
+            None => EtherscanProvider::new(),
+        };
+        registry.register(Box::new(etherscan));
+
+        registry.register(Box::new(ArchiveOrgProvider::new()));
+        registry.register(Box::new(RssProvider::with_feeds(config.feeds.clone())));
+
+        let shodan = match env_key("SHODAN_API_KEY").or(config.shodan_api_key.clone()) {
+            Some(key) => ShodanProvider::with_api_key(key),
+            None => ShodanProvider::new(),
         };
         registry.register(Box::new(shodan));
+        registry.register(Box::new(NvdProvider::new()));
+        registry.register(Box::new(NominatimProvider::new()));
 
         registry
     }
@@ -164,63 +349,172 @@ impl KnowledgeRegistry {
         query: &str,
         options: &LookupOptions,
     ) -> Option<LookupResult> {
-        let cache_key = create_cache_key(provider, query, options.max_results);
+        // Disabled providers get nothing, not even results cached before.
+        let provider = self.providers.get(provider)?;
+        let cache_key = create_cache_key(provider.name(), query, options.max_results);
 
-        // Check cache first
-        if self.cache_enabled {
-            if let Ok(mut cache) = self.cache.lock() {
-                if let Some(cached) = cache.get(&cache_key) {
-                    return Some(cached);
-                }
-            }
+        if let Some(cached) = self.cached(&cache_key) {
+            return Some(cached);
         }
 
-        let result = self.providers.get(provider).map(|p| p.lookup(query, options))?;
+        if self.offline {
+            return Some(LookupResult::error(provider.name(), OFFLINE_ERROR));
+        }
+        let result = provider.lookup(query, options);
 
-        // Cache successful results
-        if self.cache_enabled && result.success {
-            if let Ok(mut cache) = self.cache.lock() {
-                cache.set(cache_key, result.clone());
-            }
+        if result.success {
+            self.store(cache_key, &result);
         }
 
         Some(result)
     }
 
+    /// Names of the providers [`from_config`](Self::from_config) registers,
+    /// in lookup order, without building the registry.
+    pub fn configured_providers(config: &KnowledgeConfig) -> Vec<&'static str> {
+        let disabled = disabled_providers(config);
+        PROVIDER_ORDER
+            .iter()
+            .copied()
+            .filter(|name| {
+                config
+                    .providers
+                    .as_ref()
+                    .is_none_or(|enabled| enabled.iter().any(|e| e == name))
+                    && !disabled.iter().any(|d| d == name)
+            })
+            .collect()
+    }
+
+    /// Registered providers, sorted by name, without checking availability.
+    pub fn provider_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.providers.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
     pub fn available_providers(&self) -> Vec<&str> {
         self.providers
             .iter()
-            .filter(|(_, p)| p.is_available())
+            .filter(|(_, p)| self.is_available(p.as_ref()))
             .map(|(name, _)| name.as_str())
             .collect()
     }
 
+    /// Whether `provider` can be used, remembered for the availability TTL
+    /// since some providers check by making a request. Always false offline.
+    fn is_available(&self, provider: &dyn KnowledgeProvider) -> bool {
+        if self.offline {
+            return false;
+        }
+        let name = provider.name();
+        let availability = self.availability.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((available, checked)) = availability.get(name) {
+            if checked.elapsed() < self.availability_ttl {
+                return *available;
+            }
+        }
+        // Checked without the lock so slow providers don't block the others.
+        drop(availability);
+        let available = provider.is_available();
+        self.availability
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), (available, Instant::now()));
+        available
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Every known provider in `PROVIDER_ORDER`, then any other registered
+    /// ones, with whether it can be used. Availability is checked
+    /// concurrently, since some checks make a request.
+    pub fn provider_statuses(&self) -> Vec<ProviderStatus> {
+        let mut extra: Vec<&str> = self
+            .providers
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !PROVIDER_ORDER.contains(name))
+            .collect();
+        extra.sort_unstable();
+        let names: Vec<&str> = PROVIDER_ORDER.iter().copied().chain(extra).collect();
+
+        let states: Vec<ProviderState> = std::thread::scope(|scope| {
+            let handles: Vec<_> = names
+                .iter()
+                .map(|name| {
+                    scope.spawn(move || match self.providers.get(*name) {
+                        None => ProviderState::Disabled,
+                        Some(_) if self.offline => ProviderState::Offline,
+                        Some(provider) if self.is_available(provider.as_ref()) => {
+                            ProviderState::Available
+                        }
+                        Some(_) => ProviderState::Unavailable,
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or(ProviderState::Unavailable))
+                .collect()
+        });
+
+        let auto: Vec<&str> = self
+            .auto_order
+            .iter()
+            .map(String::as_str)
+            .filter(|name| self.providers.contains_key(*name))
+            .collect();
+        names
+            .iter()
+            .zip(states)
+            .map(|(name, state)| ProviderStatus {
+                name: name.to_string(),
+                state,
+                auto_rank: auto.iter().position(|a| a == name).map(|i| i + 1),
+            })
+            .collect()
+    }
+
     pub fn auto_lookup(&self, query: &str, options: &LookupOptions) -> LookupResult {
         let cache_key = create_cache_key("auto", query, options.max_results);
 
-        // Check cache first
-        if self.cache_enabled {
-            if let Ok(mut cache) = self.cache.lock() {
-                if let Some(cached) = cache.get(&cache_key) {
-                    return cached;
-                }
-            }
+        if let Some(cached) = self.cached(&cache_key) {
+            return cached;
+        }
+        if self.offline {
+            return LookupResult::error("auto", OFFLINE_ERROR);
         }
 
-        for &provider_name in PROVIDER_ORDER {
+        // Only "define X" queries go to the dictionary, and they go there
+        // before the encyclopedias.
+        let order: Vec<&str> = self.auto_order.iter().map(String::as_str).collect();
+        let definition =
+            order.contains(&"wiktionary") && wiktionary::definition_term(query).is_some();
+        let rest = order.iter().copied().filter(|name| *name != "wiktionary");
+        let order = definition.then_some("wiktionary").into_iter().chain(rest);
+
+        for provider_name in order {
+            if crate::cancel::is_cancelled() {
+                return LookupResult::error("auto", "request cancelled");
+            }
             if let Some(provider) = self.providers.get(provider_name) {
-                if !provider.is_available() {
+                if !self.is_available(provider.as_ref()) {
                     continue;
                 }
 
                 let result = provider.lookup(query, options);
+                if let Some(error) = &result.error {
+                    crate::logging::log(
+                        crate::logging::LogLevel::Warning,
+                        "knowledge_lookup",
+                        format!("{} lookup failed: {}", provider_name, error),
+                    );
+                }
                 if result.success && !result.entries.is_empty() {
-                    // Cache the result
-                    if self.cache_enabled {
-                        if let Ok(mut cache) = self.cache.lock() {
-                            cache.set(cache_key, result.clone());
-                        }
-                    }
+                    self.store(cache_key, &result);
                     return result;
                 }
             }
@@ -229,15 +523,238 @@ impl KnowledgeRegistry {
         LookupResult::success("auto", Vec::new())
     }
 
+    /// Query `providers` (every available provider that accepts `query` when
+    /// empty) concurrently and merge their entries, ranked like auto lookups:
+    /// `auto_order` first, then the rest in `PROVIDER_ORDER`.
+    pub fn multi_lookup(
+        &self,
+        providers: &[String],
+        query: &str,
+        options: &LookupOptions,
+    ) -> Result<MultiLookupResult, String> {
+        if crate::cancel::is_cancelled() {
+            return Err("request cancelled".to_string());
+        }
+        // Availability checks can make requests, so with every provider
+        // they run on the workers below rather than one at a time here.
+        let every = providers.is_empty();
+        let mut names: Vec<&str> = if every {
+            if self.offline {
+                return Err(OFFLINE_ERROR.to_string());
+            }
+            self.providers
+                .values()
+                .filter(|p| p.accepts(query))
+                .map(|p| p.name())
+                .collect()
+        } else {
+            providers.iter().map(String::as_str).collect()
+        };
+        if let Some(unknown) = names.iter().find(|n| !self.providers.contains_key(**n)) {
+            return Err(format!("unknown or disabled provider: {}", unknown));
+        }
+        names.sort_by_key(|name| {
+            let auto = self.auto_order.iter().position(|p| p == name);
+            let rank = PROVIDER_ORDER.iter().position(|p| p == name);
+            (
+                auto.unwrap_or(usize::MAX),
+                rank.unwrap_or(PROVIDER_ORDER.len()),
+                *name,
+            )
+        });
+        names.dedup();
+
+        // A few workers take providers in turn, rather than a thread each.
+        // They run under the request's cancel token so a cancelled request
+        // stops starting lookups.
+        let token = crate::cancel::current().unwrap_or_default();
+        let next = std::sync::atomic::AtomicUsize::new(0);
+        // `None` until the lookup finishes; `Some(None)` for a provider that
+        // turned out to be unavailable.
+        let slots: Vec<Mutex<Option<Option<LookupResult>>>> =
+            names.iter().map(|_| Mutex::new(None)).collect();
+        std::thread::scope(|scope| {
+            for _ in 0..MULTI_LOOKUP_THREADS.min(names.len()) {
+                scope.spawn(|| {
+                    let _guard = crate::cancel::enter(&token);
+                    loop {
+                        let i = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        let Some(name) = names.get(i) else { break };
+                        let result = if crate::cancel::is_cancelled() {
+                            Some(Some(LookupResult::error(name, "request cancelled")))
+                        } else {
+                            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                                let provider = self.providers.get(*name)?;
+                                if every && !self.is_available(provider.as_ref()) {
+                                    return Some(None);
+                                }
+                                self.lookup(name, query, options).map(Some)
+                            }))
+                            .ok()
+                            .flatten()
+                        };
+                        *slots[i].lock().unwrap_or_else(|e| e.into_inner()) = result;
+                    }
+                });
+            }
+        });
+        let results: Vec<(&str, LookupResult)> = slots
+            .into_iter()
+            .zip(&names)
+            .filter_map(|(slot, name)| {
+                let result = slot
+                    .into_inner()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .unwrap_or_else(|| Some(LookupResult::error(name, "lookup panicked")))?;
+                Some((*name, result))
+            })
+            .collect();
+
+        let mut merged = MultiLookupResult {
+            providers: results.iter().map(|(name, _)| name.to_string()).collect(),
+            ..Default::default()
+        };
+        let mut seen = std::collections::HashSet::new();
+        for (name, result) in results {
+            if let Some(error) = result.error {
+                crate::logging::log(
+                    crate::logging::LogLevel::Warning,
+                    "knowledge_multi",
+                    format!("{} lookup failed: {}", name, error),
+                );
+                merged.errors.insert(name.to_string(), error);
+                continue;
+            }
+            for entry in result.entries {
+                let key = match &entry.url {
+                    Some(url) => url.trim_end_matches('/').to_lowercase(),
+                    None => entry.title.to_lowercase(),
+                };
+                if seen.insert(key) {
+                    merged.entries.push(entry);
+                }
+            }
+        }
+        Ok(merged)
+    }
+
+    /// A cached result from memory, else from disk (which also refills the
+    /// memory cache).
+    fn cached(&self, key: &str) -> Option<LookupResult> {
+        if self.cache_enabled {
+            if let Ok(mut cache) = self.cache.lock() {
+                if let Some(cached) = cache.get(key) {
+                    return Some(cached);
+                }
+            }
+        }
+        let (cached, remaining) = self.disk_cache.as_ref()?.get(key)?;
+        if self.cache_enabled {
+            if let Ok(mut cache) = self.cache.lock() {
+                let ttl = remaining.min(self.ttl_for(&cached.provider));
+                cache.set_with_ttl(key.to_string(), cached.clone(), ttl);
+            }
+        }
+        Some(cached)
+    }
+
+    /// Cache a successful result for its provider's TTL.
+    fn store(&self, key: String, result: &LookupResult) {
+        let ttl = self.ttl_for(&result.provider);
+        if let Some(disk) = &self.disk_cache {
+            disk.set(&key, result, ttl);
+        }
+        if self.cache_enabled {
+            if let Ok(mut cache) = self.cache.lock() {
+                cache.set_with_ttl(key, result.clone(), ttl);
+            }
+        }
+    }
+
+    fn ttl_for(&self, provider: &str) -> Duration {
+        self.provider_ttls
+            .get(provider)
+            .copied()
+            .unwrap_or(self.ttl)
+    }
+
     pub fn clear_cache(&self) {
         if let Ok(mut cache) = self.cache.lock() {
             cache.clear();
         }
+        if let Some(disk) = &self.disk_cache {
+            disk.clear();
+        }
+    }
+
+    /// The disk cache's directory, if lookups are cached on disk.
+    pub fn disk_cache_dir(&self) -> Option<&std::path::Path> {
+        self.disk_cache.as_ref().map(DiskCache::dir)
     }
 
     pub fn cache_size(&self) -> usize {
         self.cache.lock().map(|c| c.size()).unwrap_or(0)
     }
+
+    pub fn cache_enabled(&self) -> bool {
+        self.cache_enabled
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).stats()
+    }
+}
+
+/// The text of an HTML or XML fragment, with common entities decoded and
+/// whitespace collapsed.
+fn strip_tags(markup: &str) -> String {
+    let mut text = String::with_capacity(markup.len());
+    let mut in_tag = false;
+    for c in markup.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn env_key(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|key| !key.is_empty())
+}
+
+/// A comma-separated list, without blank items.
+fn env_list(name: &str) -> Option<Vec<String>> {
+    env_key(name).map(|value| {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+}
+
+fn disabled_providers(config: &KnowledgeConfig) -> Vec<String> {
+    env_list("VAULT_TREE_DISABLED_PROVIDERS").unwrap_or_else(|| config.disabled_providers.clone())
+}
+
+fn auto_order(config: &KnowledgeConfig) -> Option<Vec<String>> {
+    env_list("VAULT_TREE_AUTO_ORDER").or_else(|| config.auto_order.clone())
+}
+
+/// Set to anything but empty, `0`, or `false`.
+fn env_flag(name: &str) -> bool {
+    env_key(name).is_some_and(|value| value != "0" && !value.eq_ignore_ascii_case("false"))
 }
 
 impl Default for KnowledgeRegistry {
@@ -245,3 +762,381 @@ impl Default for KnowledgeRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Stub(&'static str, &'static [(&'static str, &'static str)]);
+
+    impl KnowledgeProvider for Stub {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn lookup(&self, _query: &str, _options: &LookupOptions) -> LookupResult {
+            let entries = self
+                .1
+                .iter()
+                .map(|(title, url)| KnowledgeEntry {
+                    title: title.to_string(),
+                    summary: String::new(),
+                    url: Some(url.to_string()),
+                    source: self.0.to_string(),
+                    metadata: None,
+                })
+                .collect();
+            LookupResult::success(self.0, entries)
+        }
+    }
+
+    fn registry(stubs: Vec<Stub>) -> KnowledgeRegistry {
+        registry_with(KnowledgeConfig::default(), stubs)
+    }
+
+    fn registry_with(config: KnowledgeConfig, stubs: Vec<Stub>) -> KnowledgeRegistry {
+        let mut registry = KnowledgeRegistry::from_config(&KnowledgeConfig {
+            providers: Some(Vec::new()),
+            ..config
+        });
+        for stub in stubs {
+            registry.register(Box::new(stub));
+        }
+        registry
+    }
+
+    #[test]
+    fn multi_lookup_ranks_and_dedupes() {
+        let registry = registry(vec![
+            Stub(
+                "npm",
+                &[
+                    ("serde", "https://example.com/serde/"),
+                    ("left-pad", "https://example.com/left-pad"),
+                ],
+            ),
+            Stub("wikipedia", &[("Serde", "https://example.com/serde")]),
+        ]);
+
+        let merged = registry
+            .multi_lookup(&[], "serde", &LookupOptions::default())
+            .unwrap();
+
+        assert_eq!(merged.providers, ["wikipedia", "npm"]);
+        let titles: Vec<&str> = merged.entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, ["Serde", "left-pad"]);
+        assert!(merged.errors.is_empty());
+    }
+
+    #[test]
+    fn multi_lookup_follows_auto_order_and_availability() {
+        let config = KnowledgeConfig {
+            auto_order: Some(vec!["npm".to_string()]),
+            ..Default::default()
+        };
+        let mut registry = registry_with(
+            config,
+            vec![
+                Stub(
+                    "crates.io",
+                    &[("serde", "https://example.com/crates/serde")],
+                ),
+                Stub("npm", &[("serde", "https://example.com/npm/serde")]),
+            ],
+        );
+        registry.register(Box::new(Probe(Default::default())));
+        let options = LookupOptions::default();
+
+        let merged = registry.multi_lookup(&[], "serde", &options).unwrap();
+        assert_eq!(merged.providers, ["npm", "crates.io"]);
+        assert_eq!(merged.entries[0].source, "npm");
+
+        registry.offline = true;
+        assert_eq!(
+            registry.multi_lookup(&[], "serde", &options).unwrap_err(),
+            OFFLINE_ERROR
+        );
+        // Cached results are still served.
+        let merged = registry
+            .multi_lookup(&["npm".to_string()], "serde", &options)
+            .unwrap();
+        assert_eq!(merged.entries.len(), 1);
+        let merged = registry
+            .multi_lookup(&["npm".to_string()], "tokio", &options)
+            .unwrap();
+        assert_eq!(merged.errors["npm"], OFFLINE_ERROR);
+    }
+
+    /// Takes only URLs, and fails on anything it is asked.
+    struct UrlOnly;
+
+    impl KnowledgeProvider for UrlOnly {
+        fn name(&self) -> &'static str {
+            "rss"
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn lookup(&self, _query: &str, _options: &LookupOptions) -> LookupResult {
+            LookupResult::error("rss", "not a feed")
+        }
+
+        fn accepts(&self, query: &str) -> bool {
+            query.starts_with("https://")
+        }
+    }
+
+    #[test]
+    fn multi_lookup_skips_unavailable_and_non_matching_providers() {
+        let checks = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut registry = registry(vec![Stub("npm", &[("serde", "https://example.com/serde")])]);
+        registry.register(Box::new(Probe(checks.clone())));
+        registry.register(Box::new(UrlOnly));
+        let options = LookupOptions::default();
+
+        let merged = registry.multi_lookup(&[], "serde", &options).unwrap();
+        assert_eq!(merged.providers, ["npm"]);
+        assert!(merged.errors.is_empty());
+        assert_eq!(checks.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Named providers are always asked.
+        let merged = registry
+            .multi_lookup(&["rss".to_string()], "serde", &options)
+            .unwrap();
+        assert_eq!(merged.errors["rss"], "not a feed");
+    }
+
+    #[test]
+    fn multi_lookup_rejects_unknown_providers() {
+        let registry = registry(vec![Stub("npm", &[])]);
+        let err = registry
+            .multi_lookup(&["reddit".to_string()], "serde", &LookupOptions::default())
+            .unwrap_err();
+        assert!(err.contains("reddit"));
+    }
+
+    #[test]
+    fn auto_order_reorders_and_limits_auto_lookup() {
+        let stubs = || {
+            vec![
+                Stub("wikipedia", &[("Serde", "https://example.com/wiki/serde")]),
+                Stub("npm", &[("serde", "https://example.com/npm/serde")]),
+                Stub("reddit", &[]),
+            ]
+        };
+        let options = LookupOptions::default();
+        let ordered = |order: &[&str]| KnowledgeConfig {
+            auto_order: Some(order.iter().map(|name| name.to_string()).collect()),
+            ..Default::default()
+        };
+
+        let registry = registry_with(ordered(&["npm", "wikipedia"]), stubs());
+        assert_eq!(registry.auto_lookup("serde", &options).provider, "npm");
+
+        // Wikipedia has results, but auto no longer tries it.
+        let registry = registry_with(ordered(&["reddit"]), stubs());
+        let result = registry.auto_lookup("serde", &options);
+        assert!(result.success && result.entries.is_empty());
+        assert!(registry.lookup("wikipedia", "serde", &options).is_some());
+    }
+
+    #[test]
+    fn auto_sends_only_definitions_to_wiktionary() {
+        let registry = registry_with(
+            KnowledgeConfig {
+                auto_order: Some(vec!["wiktionary".to_string(), "npm".to_string()]),
+                ..Default::default()
+            },
+            vec![
+                Stub("wiktionary", &[("serde", "https://example.com/wiki/serde")]),
+                Stub("npm", &[("serde", "https://example.com/npm/serde")]),
+            ],
+        );
+        let options = LookupOptions::default();
+
+        assert_eq!(registry.auto_lookup("serde", &options).provider, "npm");
+        let definition = registry.auto_lookup("define serde", &options);
+        assert_eq!(definition.provider, "wiktionary");
+    }
+
+    #[test]
+    fn disabled_providers_are_not_served_from_the_disk_cache() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = KnowledgeConfig {
+            cache_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let options = LookupOptions::default();
+
+        let enabled = registry_with(
+            config.clone(),
+            vec![Stub("npm", &[("serde", "https://example.com/serde")])],
+        );
+        assert!(enabled.lookup("npm", "serde", &options).is_some());
+
+        let disabled = registry_with(config, vec![]);
+        assert!(disabled.lookup("npm", "serde", &options).is_none());
+    }
+
+    #[test]
+    fn disabled_providers_are_not_registered() {
+        let config = KnowledgeConfig {
+            providers: Some(vec!["reddit".to_string(), "npm".to_string()]),
+            disabled_providers: vec!["reddit".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(KnowledgeRegistry::configured_providers(&config), ["npm"]);
+    }
+
+    #[test]
+    fn provider_statuses_report_state_and_auto_rank() {
+        let config = KnowledgeConfig {
+            auto_order: Some(vec!["npm".to_string(), "wikipedia".to_string()]),
+            ..Default::default()
+        };
+        let mut registry = registry_with(config, vec![Stub("npm", &[])]);
+        registry.register(Box::new(Probe(Default::default())));
+        let statuses = registry.provider_statuses();
+        let status = |name: &str| statuses.iter().find(|s| s.name == name).unwrap();
+
+        assert_eq!(statuses.len(), PROVIDER_ORDER.len());
+        assert_eq!(status("npm").state, ProviderState::Available);
+        assert_eq!(status("npm").auto_rank, Some(1));
+        assert_eq!(status("wikipedia").state, ProviderState::Unavailable);
+        assert_eq!(status("wikipedia").auto_rank, Some(2));
+        assert_eq!(status("reddit").state, ProviderState::Disabled);
+        assert_eq!(status("reddit").auto_rank, None);
+
+        registry.offline = true;
+        let statuses = registry.provider_statuses();
+        assert_eq!(statuses[PROVIDER_ORDER.len() - 1].name, "nominatim");
+        assert!(statuses
+            .iter()
+            .filter(|s| s.name == "npm" || s.name == "wikipedia")
+            .all(|s| s.state == ProviderState::Offline));
+    }
+
+    /// Counts its availability checks.
+    struct Probe(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+    impl KnowledgeProvider for Probe {
+        fn name(&self) -> &'static str {
+            "wikipedia"
+        }
+
+        fn is_available(&self) -> bool {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            false
+        }
+
+        fn lookup(&self, _query: &str, _options: &LookupOptions) -> LookupResult {
+            LookupResult::error("wikipedia", "unreachable")
+        }
+    }
+
+    #[test]
+    fn availability_is_checked_once_per_ttl() {
+        let checks = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut registry = registry(vec![]);
+        registry.register(Box::new(Probe(checks.clone())));
+        let options = LookupOptions::default();
+
+        registry.auto_lookup("rust", &options);
+        registry.auto_lookup("serde", &options);
+        assert!(registry.available_providers().is_empty());
+        assert_eq!(checks.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        registry.availability_ttl = Duration::ZERO;
+        registry.auto_lookup("tokio", &options);
+        assert_eq!(checks.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn offline_mode_fails_fast_but_serves_the_cache() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = KnowledgeConfig {
+            cache_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let options = LookupOptions::default();
+
+        let online = registry_with(
+            config.clone(),
+            vec![Stub("npm", &[("serde", "https://example.com/serde")])],
+        );
+        online.lookup("npm", "serde", &options).unwrap();
+
+        let offline = registry_with(
+            KnowledgeConfig {
+                offline: true,
+                ..config
+            },
+            vec![Stub("npm", &[("tokio", "https://example.com/tokio")])],
+        );
+        assert!(offline.is_offline());
+        let cached = offline.lookup("npm", "serde", &options).unwrap();
+        assert_eq!(cached.entries[0].title, "serde");
+
+        let missed = offline.lookup("npm", "tokio", &options).unwrap();
+        assert_eq!(missed.error.as_deref(), Some(OFFLINE_ERROR));
+        let auto = offline.auto_lookup("tokio", &options);
+        assert_eq!(auto.error.as_deref(), Some(OFFLINE_ERROR));
+        assert!(offline.available_providers().is_empty());
+    }
+
+    #[test]
+    fn disk_cache_outlives_the_registry() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = KnowledgeConfig {
+            cache_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let options = LookupOptions::default();
+
+        let first = registry_with(
+            config.clone(),
+            vec![Stub("npm", &[("serde", "https://example.com/serde")])],
+        );
+        first.lookup("npm", "serde", &options).unwrap();
+
+        // A restarted server answers from disk, not from the provider.
+        let restarted = registry_with(config, vec![Stub("npm", &[])]);
+        let result = restarted.lookup("npm", "serde", &options).unwrap();
+        assert_eq!(result.entries[0].title, "serde");
+    }
+
+    #[test]
+    fn disk_hits_keep_their_remaining_ttl() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = KnowledgeConfig {
+            cache_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let options = LookupOptions::default();
+        let key = create_cache_key("npm", "serde", options.max_results);
+        let entry = KnowledgeEntry {
+            title: "serde".to_string(),
+            summary: String::new(),
+            url: None,
+            source: "npm".to_string(),
+            metadata: None,
+        };
+        let stale = LookupResult::success("npm", vec![entry]);
+        DiskCache::new(dir.path().to_path_buf()).set(&key, &stale, Duration::from_secs(60));
+
+        let registry = registry_with(config, vec![Stub("npm", &[])]);
+        let result = registry.lookup("npm", "serde", &options).unwrap();
+        assert_eq!(result.entries[0].title, "serde");
+
+        // The memory copy expires with the disk entry, not after the
+        // registry's default TTL.
+        let expires_in = registry.cache.lock().unwrap().expires_in(&key).unwrap();
+        assert!(expires_in <= Duration::from_secs(60));
+    }
+}
@@ -127,7 +127,7 @@ impl MusicBrainzProvider {
                     .as_ref()
                     .map(|tags| {
                         let mut sorted = tags.clone();
-                        sorted.sort_by(|a, b| b.count.cmp(&a.count));
+                        sorted.sort_by_key(|t| std::cmp::Reverse(t.count));
                         sorted.iter().take(3).map(|t| t.name.clone()).collect::<Vec<_>>().join(", ")
                     })
                     .filter(|s| !s.is_empty())
@@ -0,0 +1,250 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+const NOMINATIM_API: &str = "https://nominatim.openstreetmap.org";
+
+/// Places from OpenStreetMap via Nominatim: geocodes place names and
+/// reverse-geocodes `lat,lon` queries.
+pub struct NominatimProvider {
+    client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct Place {
+    osm_type: Option<String>,
+    osm_id: Option<u64>,
+    lat: String,
+    lon: String,
+    #[serde(default, alias = "class")]
+    category: String,
+    #[serde(default, rename = "type")]
+    kind: String,
+    name: Option<String>,
+    display_name: String,
+}
+
+/// Reverse lookups that find nothing answer with an error object.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ReverseResponse {
+    Place(Place),
+    Error {
+        #[allow(dead_code)]
+        error: String,
+    },
+}
+
+impl NominatimProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+        }
+    }
+
+    /// `48.8584, 2.2945` or `48.8584 2.2945` as a latitude and longitude.
+    fn parse_coordinates(query: &str) -> Option<(f64, f64)> {
+        let mut parts = query
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty());
+        let lat: f64 = parts.next()?.parse().ok()?;
+        let lon: f64 = parts.next()?.parse().ok()?;
+        if parts.next().is_some()
+            || !(-90.0..=90.0).contains(&lat)
+            || !(-180.0..=180.0).contains(&lon)
+        {
+            return None;
+        }
+        Some((lat, lon))
+    }
+
+    fn place_to_entry(place: &Place) -> KnowledgeEntry {
+        let title = place
+            .name
+            .as_deref()
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&place.display_name);
+
+        let lines = [
+            place.display_name.clone(),
+            format!("Type: {} ({})", place.kind, place.category),
+            format!("Coordinates: {}, {}", place.lat, place.lon),
+        ];
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "displayName".to_string(),
+            serde_json::json!(place.display_name),
+        );
+        metadata.insert("category".to_string(), serde_json::json!(place.category));
+        metadata.insert("type".to_string(), serde_json::json!(place.kind));
+        metadata.insert(
+            "lat".to_string(),
+            serde_json::json!(place.lat.parse::<f64>().ok()),
+        );
+        metadata.insert(
+            "lon".to_string(),
+            serde_json::json!(place.lon.parse::<f64>().ok()),
+        );
+
+        let url = match (&place.osm_type, place.osm_id) {
+            (Some(osm_type), Some(osm_id)) => {
+                metadata.insert("osmType".to_string(), serde_json::json!(osm_type));
+                metadata.insert("osmId".to_string(), serde_json::json!(osm_id));
+                format!("https://www.openstreetmap.org/{}/{}", osm_type, osm_id)
+            }
+            _ => format!(
+                "https://www.openstreetmap.org/?mlat={}&mlon={}",
+                place.lat, place.lon
+            ),
+        };
+
+        KnowledgeEntry {
+            title: title.to_string(),
+            summary: lines.join("\n"),
+            url: Some(url),
+            source: "nominatim".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+
+    fn get(&self, path: &str, options: &LookupOptions) -> Result<String, String> {
+        let mut url = format!("{}/{}&format=jsonv2", NOMINATIM_API, path);
+        if let Some(language) = &options.language {
+            url.push_str(&format!(
+                "&accept-language={}",
+                urlencoding::encode(language)
+            ));
+        }
+
+        let response = self.client.get(&url).send().map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("geocoding failed: {}", response.status()));
+        }
+
+        response.text().map_err(|e| e.to_string())
+    }
+
+    fn search(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &LookupOptions,
+    ) -> Result<Vec<KnowledgeEntry>, String> {
+        let body = self.get(
+            &format!("search?q={}&limit={}", urlencoding::encode(query), limit),
+            options,
+        )?;
+        let places: Vec<Place> = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+        Ok(places.iter().map(Self::place_to_entry).collect())
+    }
+
+    fn reverse(
+        &self,
+        lat: f64,
+        lon: f64,
+        options: &LookupOptions,
+    ) -> Result<Vec<KnowledgeEntry>, String> {
+        let body = self.get(&format!("reverse?lat={}&lon={}", lat, lon), options)?;
+        match serde_json::from_str(&body).map_err(|e| e.to_string())? {
+            ReverseResponse::Place(place) => Ok(vec![Self::place_to_entry(&place)]),
+            ReverseResponse::Error { .. } => Ok(Vec::new()),
+        }
+    }
+}
+
+impl Default for NominatimProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for NominatimProvider {
+    fn name(&self) -> &'static str {
+        "nominatim"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn lookup(&self, query: &str, options: &LookupOptions) -> LookupResult {
+        let limit = options.max_results.unwrap_or(5);
+
+        let result = match Self::parse_coordinates(query) {
+            Some((lat, lon)) => self.reverse(lat, lon, options),
+            None => self.search(query, limit, options),
+        };
+
+        match result {
+            Ok(entries) => LookupResult::success(self.name(), entries),
+            Err(e) => LookupResult::error(self.name(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Requires network
+    fn nominatim_lookup() {
+        let provider = NominatimProvider::new();
+        let result = provider.lookup("Eiffel Tower", &LookupOptions::default());
+        assert!(result.success);
+        assert!(!result.entries.is_empty());
+    }
+
+    #[test]
+    fn parse_place() {
+        let json = r#"[{
+            "place_id": 88066702, "osm_type": "way", "osm_id": 5013364,
+            "lat": "48.8582599", "lon": "2.2945006",
+            "category": "man_made", "type": "tower", "place_rank": 30,
+            "name": "Tour Eiffel",
+            "display_name": "Tour Eiffel, 5, Avenue Anatole France, Paris, France"
+        }]"#;
+
+        let places: Vec<Place> = serde_json::from_str(json).unwrap();
+        let entry = NominatimProvider::place_to_entry(&places[0]);
+        let metadata = entry.metadata.unwrap();
+
+        assert_eq!(entry.title, "Tour Eiffel");
+        assert_eq!(
+            entry.url.as_deref(),
+            Some("https://www.openstreetmap.org/way/5013364")
+        );
+        assert!(entry.summary.contains("Type: tower (man_made)"));
+        assert_eq!(metadata["lat"], 48.8582599);
+        assert_eq!(metadata["lon"], 2.2945006);
+    }
+
+    #[test]
+    fn reverse_misses_are_empty() {
+        let response: ReverseResponse =
+            serde_json::from_str(r#"{"error": "Unable to geocode"}"#).unwrap();
+        assert!(matches!(response, ReverseResponse::Error { .. }));
+    }
+
+    #[test]
+    fn recognizes_coordinates() {
+        assert_eq!(
+            NominatimProvider::parse_coordinates("48.8584, 2.2945"),
+            Some((48.8584, 2.2945))
+        );
+        assert_eq!(
+            NominatimProvider::parse_coordinates("-33.86 151.21"),
+            Some((-33.86, 151.21))
+        );
+        assert_eq!(NominatimProvider::parse_coordinates("91, 0"), None);
+        assert_eq!(NominatimProvider::parse_coordinates("Paris"), None);
+        assert_eq!(NominatimProvider::parse_coordinates("1 2 3"), None);
+    }
+}
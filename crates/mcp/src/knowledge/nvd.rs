@@ -0,0 +1,330 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+const NVD_API: &str = "https://services.nvd.nist.gov/rest/json/cves/2.0";
+
+/// CVEs from the NIST National Vulnerability Database, by identifier or
+/// keyword.
+pub struct NvdProvider {
+    client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct CveResponse {
+    #[serde(default)]
+    vulnerabilities: Vec<Vulnerability>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Vulnerability {
+    cve: Cve,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Cve {
+    id: String,
+    published: Option<String>,
+    vuln_status: Option<String>,
+    #[serde(default)]
+    descriptions: Vec<Description>,
+    #[serde(default)]
+    metrics: Metrics,
+    #[serde(default)]
+    configurations: Vec<Configuration>,
+    #[serde(default)]
+    references: Vec<Reference>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Description {
+    lang: String,
+    value: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Metrics {
+    #[serde(default)]
+    cvss_metric_v31: Vec<CvssMetric>,
+    #[serde(default)]
+    cvss_metric_v30: Vec<CvssMetric>,
+    #[serde(default)]
+    cvss_metric_v2: Vec<CvssMetric>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CvssMetric {
+    cvss_data: CvssData,
+    /// Where CVSS v2 puts the severity.
+    base_severity: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CvssData {
+    version: Option<String>,
+    base_score: f64,
+    base_severity: Option<String>,
+    vector_string: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Configuration {
+    #[serde(default)]
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Node {
+    #[serde(default)]
+    cpe_match: Vec<CpeMatch>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CpeMatch {
+    vulnerable: bool,
+    criteria: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Reference {
+    url: String,
+}
+
+impl Cve {
+    /// The newest CVSS version scored for this CVE.
+    fn cvss(&self) -> Option<&CvssMetric> {
+        self.metrics
+            .cvss_metric_v31
+            .first()
+            .or_else(|| self.metrics.cvss_metric_v30.first())
+            .or_else(|| self.metrics.cvss_metric_v2.first())
+    }
+
+    /// `vendor:product` for each vulnerable CPE, in order, without repeats.
+    fn affected_products(&self) -> Vec<String> {
+        let mut products: Vec<String> = Vec::new();
+        let matches = self
+            .configurations
+            .iter()
+            .flat_map(|c| &c.nodes)
+            .flat_map(|n| &n.cpe_match)
+            .filter(|m| m.vulnerable);
+        for cpe in matches {
+            // cpe:2.3:part:vendor:product:version:...
+            let parts: Vec<&str> = cpe.criteria.split(':').collect();
+            if let (Some(vendor), Some(product)) = (parts.get(3), parts.get(4)) {
+                let product = format!("{}:{}", vendor, product);
+                if !products.contains(&product) {
+                    products.push(product);
+                }
+            }
+        }
+        products
+    }
+}
+
+impl NvdProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+        }
+    }
+
+    fn is_cve_id(query: &str) -> bool {
+        let mut parts = query.split('-');
+        matches!(
+            (parts.next(), parts.next(), parts.next(), parts.next()),
+            (Some(prefix), Some(year), Some(number), None)
+                if prefix.eq_ignore_ascii_case("cve")
+                    && year.len() == 4
+                    && year.chars().all(|c| c.is_ascii_digit())
+                    && number.len() >= 4
+                    && number.chars().all(|c| c.is_ascii_digit())
+        )
+    }
+
+    fn cve_to_entry(cve: &Cve) -> KnowledgeEntry {
+        let description = cve
+            .descriptions
+            .iter()
+            .find(|d| d.lang == "en")
+            .or(cve.descriptions.first())
+            .map(|d| d.value.clone())
+            .unwrap_or_default();
+        let products = cve.affected_products();
+
+        let mut lines = Vec::new();
+        let mut metadata = HashMap::new();
+        if let Some(metric) = cve.cvss() {
+            let data = &metric.cvss_data;
+            let severity = data
+                .base_severity
+                .as_ref()
+                .or(metric.base_severity.as_ref());
+            lines.push(format!(
+                "CVSS {}: {}{}",
+                data.version.as_deref().unwrap_or("?"),
+                data.base_score,
+                severity.map(|s| format!(" ({})", s)).unwrap_or_default()
+            ));
+            metadata.insert("cvssScore".to_string(), serde_json::json!(data.base_score));
+            if let Some(severity) = severity {
+                metadata.insert("severity".to_string(), serde_json::json!(severity));
+            }
+            if let Some(vector) = &data.vector_string {
+                metadata.insert("cvssVector".to_string(), serde_json::json!(vector));
+            }
+        }
+        lines.push(description);
+        if !products.is_empty() {
+            let shown: Vec<&str> = products.iter().take(5).map(String::as_str).collect();
+            let more = if products.len() > 5 {
+                format!(" (+{} more)", products.len() - 5)
+            } else {
+                String::new()
+            };
+            lines.push(format!("Affected: {}{}", shown.join(", "), more));
+        }
+
+        metadata.insert("cveId".to_string(), serde_json::json!(cve.id));
+        metadata.insert("affectedProducts".to_string(), serde_json::json!(products));
+        if let Some(published) = &cve.published {
+            metadata.insert("published".to_string(), serde_json::json!(published));
+        }
+        if let Some(status) = &cve.vuln_status {
+            metadata.insert("status".to_string(), serde_json::json!(status));
+        }
+        let references: Vec<&str> = cve
+            .references
+            .iter()
+            .take(5)
+            .map(|r| r.url.as_str())
+            .collect();
+        metadata.insert("references".to_string(), serde_json::json!(references));
+
+        KnowledgeEntry {
+            title: cve.id.clone(),
+            summary: lines.join("\n"),
+            url: Some(format!("https://nvd.nist.gov/vuln/detail/{}", cve.id)),
+            source: "nvd".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+
+    fn fetch(&self, params: &str) -> Result<Vec<KnowledgeEntry>, String> {
+        let url = format!("{}?{}", NVD_API, params);
+
+        let response = self.client.get(&url).send().map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("NVD request failed: {}", response.status()));
+        }
+
+        let data: CveResponse = response.json().map_err(|e| e.to_string())?;
+        Ok(data
+            .vulnerabilities
+            .iter()
+            .map(|v| Self::cve_to_entry(&v.cve))
+            .collect())
+    }
+}
+
+impl Default for NvdProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for NvdProvider {
+    fn name(&self) -> &'static str {
+        "nvd"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn lookup(&self, query: &str, options: &LookupOptions) -> LookupResult {
+        let limit = options.max_results.unwrap_or(5);
+        let query = query.trim();
+
+        let params = if Self::is_cve_id(query) {
+            format!("cveId={}", query.to_uppercase())
+        } else {
+            format!(
+                "keywordSearch={}&resultsPerPage={}",
+                urlencoding::encode(query),
+                limit
+            )
+        };
+
+        match self.fetch(&params) {
+            Ok(entries) => LookupResult::success(self.name(), entries),
+            Err(e) => LookupResult::error(self.name(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Requires network
+    fn nvd_lookup() {
+        let provider = NvdProvider::new();
+        let result = provider.lookup("CVE-2021-44228", &LookupOptions::default());
+        assert!(result.success);
+        assert!(!result.entries.is_empty());
+    }
+
+    #[test]
+    fn parse_cve() {
+        let json = r#"{"vulnerabilities": [{"cve": {
+            "id": "CVE-2021-44228",
+            "published": "2021-12-10T10:15:09.143",
+            "vulnStatus": "Analyzed",
+            "descriptions": [{"lang": "en", "value": "Log4j2 JNDI features do not protect against attacker-controlled LDAP."}],
+            "metrics": {"cvssMetricV31": [{"cvssData": {
+                "version": "3.1", "baseScore": 10.0, "baseSeverity": "CRITICAL",
+                "vectorString": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H"
+            }}]},
+            "configurations": [{"nodes": [{"cpeMatch": [
+                {"vulnerable": true, "criteria": "cpe:2.3:a:apache:log4j:2.0:*:*:*:*:*:*:*"},
+                {"vulnerable": true, "criteria": "cpe:2.3:a:apache:log4j:2.1:*:*:*:*:*:*:*"},
+                {"vulnerable": false, "criteria": "cpe:2.3:o:linux:linux_kernel:-:*:*:*:*:*:*:*"}
+            ]}]}],
+            "references": [{"url": "https://logging.apache.org/log4j/2.x/security.html"}]
+        }}]}"#;
+
+        let data: CveResponse = serde_json::from_str(json).unwrap();
+        let entry = NvdProvider::cve_to_entry(&data.vulnerabilities[0].cve);
+        let metadata = entry.metadata.unwrap();
+
+        assert_eq!(entry.title, "CVE-2021-44228");
+        assert!(entry.summary.starts_with("CVSS 3.1: 10 (CRITICAL)\n"));
+        assert_eq!(metadata["cvssScore"], 10.0);
+        assert_eq!(
+            metadata["affectedProducts"],
+            serde_json::json!(["apache:log4j"])
+        );
+    }
+
+    #[test]
+    fn recognizes_cve_ids() {
+        assert!(NvdProvider::is_cve_id("CVE-2021-44228"));
+        assert!(NvdProvider::is_cve_id("cve-2014-0160"));
+        assert!(!NvdProvider::is_cve_id("log4j"));
+        assert!(!NvdProvider::is_cve_id("CVE-21-1"));
+    }
+}
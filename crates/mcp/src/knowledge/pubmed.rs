@@ -0,0 +1,340 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+const EUTILS_API: &str = "https://eutils.ncbi.nlm.nih.gov/entrez/eutils";
+
+/// Biomedical literature from PubMed, via the NCBI E-utilities API.
+pub struct PubMedProvider {
+    client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    esearchresult: SearchResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResult {
+    #[serde(default)]
+    idlist: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct Article {
+    pmid: String,
+    title: String,
+    abstract_text: String,
+    authors: Vec<String>,
+    journal: String,
+    year: String,
+    doi: Option<String>,
+}
+
+impl PubMedProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+        }
+    }
+
+    fn search_ids(&self, query: &str, limit: usize) -> Result<Vec<String>, String> {
+        let url = format!(
+            "{}/esearch.fcgi?db=pubmed&retmode=json&retmax={}&term={}",
+            EUTILS_API,
+            limit,
+            urlencoding::encode(query)
+        );
+
+        let response = self.client.get(&url).send().map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("search failed: {}", response.status()));
+        }
+
+        let data: SearchResponse = response.json().map_err(|e| e.to_string())?;
+        Ok(data.esearchresult.idlist)
+    }
+
+    fn fetch(&self, ids: &[String]) -> Result<Vec<Article>, String> {
+        let url = format!(
+            "{}/efetch.fcgi?db=pubmed&retmode=xml&id={}",
+            EUTILS_API,
+            ids.join(",")
+        );
+
+        let response = self.client.get(&url).send().map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("fetch failed: {}", response.status()));
+        }
+
+        let xml = response.text().map_err(|e| e.to_string())?;
+        Ok(Self::parse_articles(&xml))
+    }
+
+    /// Parse an efetch `PubmedArticleSet`. Titles and abstracts may contain
+    /// inline markup (`<i>`, `<sup>`), whose text is kept.
+    fn parse_articles(xml: &str) -> Vec<Article> {
+        let mut articles = Vec::new();
+        let mut reader = Reader::from_str(xml);
+
+        let mut buf = Vec::new();
+        let mut path: Vec<String> = Vec::new();
+        let mut article = Article::default();
+        let mut last_name = String::new();
+        let mut fore_name = String::new();
+        let mut id_type = String::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => {
+                    let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    match tag.as_str() {
+                        "PubmedArticle" => article = Article::default(),
+                        "Author" => {
+                            last_name.clear();
+                            fore_name.clear();
+                        }
+                        "AbstractText" => {
+                            if !article.abstract_text.is_empty() {
+                                article.abstract_text.push(' ');
+                            }
+                            let label = e
+                                .attributes()
+                                .flatten()
+                                .find(|a| a.key.as_ref() == b"Label")
+                                .map(|a| String::from_utf8_lossy(&a.value).to_string());
+                            if let Some(label) = label {
+                                article.abstract_text.push_str(&format!("{}: ", label));
+                            }
+                        }
+                        "ArticleId" => {
+                            id_type = e
+                                .attributes()
+                                .flatten()
+                                .find(|a| a.key.as_ref() == b"IdType")
+                                .map(|a| String::from_utf8_lossy(&a.value).to_string())
+                                .unwrap_or_default();
+                        }
+                        _ => {}
+                    }
+                    path.push(tag);
+                }
+                Ok(Event::End(e)) => {
+                    let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    path.pop();
+                    match tag.as_str() {
+                        "PubmedArticle" if !article.pmid.is_empty() => {
+                            articles.push(std::mem::take(&mut article));
+                        }
+                        "Author" => {
+                            let name = format!("{} {}", fore_name.trim(), last_name.trim());
+                            if !name.trim().is_empty() {
+                                article.authors.push(name.trim().to_string());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::Text(e)) => {
+                    let text = e.unescape().map(|s| s.to_string()).unwrap_or_default();
+                    let within = |tag: &str| path.iter().any(|t| t == tag);
+                    let parent = path.iter().rev().nth(1).map(String::as_str);
+                    match path.last().map(String::as_str) {
+                        Some("PMID") if parent == Some("MedlineCitation") => {
+                            article.pmid = text.trim().to_string()
+                        }
+                        Some("LastName") | Some("CollectiveName") if within("Author") => {
+                            last_name.push_str(&text)
+                        }
+                        Some("ForeName") if within("Author") => fore_name.push_str(&text),
+                        Some("Title") if parent == Some("Journal") => {
+                            article.journal.push_str(&text)
+                        }
+                        Some("Year") if within("PubDate") => article.year = text.trim().to_string(),
+                        Some("MedlineDate") if article.year.is_empty() => {
+                            article.year = text.trim().chars().take(4).collect()
+                        }
+                        Some("ArticleId") if id_type == "doi" && within("PubmedData") => {
+                            article.doi = Some(text.trim().to_string())
+                        }
+                        _ if within("ArticleTitle") => article.title.push_str(&text),
+                        _ if within("AbstractText") => article.abstract_text.push_str(&text),
+                        _ => {}
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        articles
+    }
+
+    fn article_to_entry(article: Article) -> KnowledgeEntry {
+        let collapse = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+        let author_list = if article.authors.len() > 3 {
+            format!("{} et al.", article.authors[..3].join(", "))
+        } else {
+            article.authors.join(", ")
+        };
+        let abstract_text = collapse(&article.abstract_text);
+        let abstract_excerpt = if abstract_text.chars().count() > 400 {
+            format!("{}...", abstract_text.chars().take(400).collect::<String>())
+        } else {
+            abstract_text.clone()
+        };
+        let journal = collapse(&article.journal);
+
+        let mut summary = format!("{} - {} ({})", author_list, journal, article.year);
+        if !abstract_excerpt.is_empty() {
+            summary.push_str(&format!("\n\n{}", abstract_excerpt));
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("pmid".to_string(), serde_json::json!(article.pmid));
+        metadata.insert("authors".to_string(), serde_json::json!(article.authors));
+        metadata.insert("journal".to_string(), serde_json::json!(journal));
+        metadata.insert("year".to_string(), serde_json::json!(article.year));
+        if !abstract_text.is_empty() {
+            metadata.insert("abstract".to_string(), serde_json::json!(abstract_text));
+        }
+        if let Some(doi) = &article.doi {
+            metadata.insert("doi".to_string(), serde_json::json!(doi));
+        }
+
+        KnowledgeEntry {
+            title: collapse(&article.title),
+            summary,
+            url: Some(format!("https://pubmed.ncbi.nlm.nih.gov/{}/", article.pmid)),
+            source: "pubmed".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+}
+
+impl Default for PubMedProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for PubMedProvider {
+    fn name(&self) -> &'static str {
+        "pubmed"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn lookup(&self, query: &str, options: &LookupOptions) -> LookupResult {
+        let limit = options.max_results.unwrap_or(5);
+
+        // A bare number is taken as a PMID
+        let query = query.trim();
+        let ids = if !query.is_empty() && query.chars().all(|c| c.is_ascii_digit()) {
+            vec![query.to_string()]
+        } else {
+            match self.search_ids(query, limit) {
+                Ok(ids) => ids,
+                Err(e) => return LookupResult::error(self.name(), e),
+            }
+        };
+
+        if ids.is_empty() {
+            return LookupResult::success(self.name(), vec![]);
+        }
+
+        match self.fetch(&ids) {
+            Ok(articles) => LookupResult::success(
+                self.name(),
+                articles.into_iter().map(Self::article_to_entry).collect(),
+            ),
+            Err(e) => LookupResult::error(self.name(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Requires network
+    fn pubmed_lookup() {
+        let provider = PubMedProvider::new();
+        let result = provider.lookup("CRISPR gene editing", &LookupOptions::default());
+        assert!(result.success);
+        assert!(!result.entries.is_empty());
+    }
+
+    #[test]
+    fn parse_efetch_xml() {
+        let xml = r#"<?xml version="1.0" ?>
+<PubmedArticleSet>
+  <PubmedArticle>
+    <MedlineCitation>
+      <PMID Version="1">12345</PMID>
+      <Article>
+        <Journal>
+          <JournalIssue><PubDate><Year>2020</Year></PubDate></JournalIssue>
+          <Title>Nature medicine</Title>
+        </Journal>
+        <ArticleTitle>The <i>BRCA1</i> gene &amp; cancer.</ArticleTitle>
+        <Abstract>
+          <AbstractText Label="BACKGROUND">Some background.</AbstractText>
+          <AbstractText Label="RESULTS">Some results.</AbstractText>
+        </Abstract>
+        <AuthorList>
+          <Author><LastName>Doe</LastName><ForeName>Jane</ForeName></Author>
+          <Author><CollectiveName>The Study Group</CollectiveName></Author>
+        </AuthorList>
+      </Article>
+      <CommentsCorrectionsList>
+        <CommentsCorrections><PMID Version="1">99999</PMID></CommentsCorrections>
+      </CommentsCorrectionsList>
+    </MedlineCitation>
+    <PubmedData>
+      <ArticleIdList>
+        <ArticleId IdType="pubmed">12345</ArticleId>
+        <ArticleId IdType="doi">10.1000/brca1</ArticleId>
+      </ArticleIdList>
+    </PubmedData>
+  </PubmedArticle>
+</PubmedArticleSet>"#;
+
+        let articles = PubMedProvider::parse_articles(xml);
+        assert_eq!(articles.len(), 1);
+
+        let entry = PubMedProvider::article_to_entry(articles.into_iter().next().unwrap());
+        let metadata = entry.metadata.unwrap();
+        assert_eq!(entry.title, "The BRCA1 gene & cancer.");
+        assert_eq!(
+            entry.url.as_deref(),
+            Some("https://pubmed.ncbi.nlm.nih.gov/12345/")
+        );
+        assert_eq!(metadata["pmid"], "12345");
+        assert_eq!(metadata["journal"], "Nature medicine");
+        assert_eq!(metadata["year"], "2020");
+        assert_eq!(metadata["doi"], "10.1000/brca1");
+        assert_eq!(
+            metadata["authors"],
+            serde_json::json!(["Jane Doe", "The Study Group"])
+        );
+        assert_eq!(
+            metadata["abstract"],
+            "BACKGROUND: Some background. RESULTS: Some results."
+        );
+    }
+}
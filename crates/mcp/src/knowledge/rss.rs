@@ -0,0 +1,325 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use reqwest::blocking::Client;
+use std::collections::HashMap;
+
+use super::{strip_tags, KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+/// The latest items of an RSS or Atom feed, given its URL or a name from the
+/// `[knowledge.feeds]` config table.
+pub struct RssProvider {
+    client: Client,
+    feeds: HashMap<String, String>,
+}
+
+#[derive(Debug, Default)]
+struct Feed {
+    title: String,
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Default)]
+struct Item {
+    title: String,
+    link: String,
+    date: String,
+    summary: String,
+}
+
+impl RssProvider {
+    pub fn new() -> Self {
+        Self::with_feeds(HashMap::new())
+    }
+
+    pub fn with_feeds(feeds: HashMap<String, String>) -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            feeds,
+        }
+    }
+
+    /// The feed URL for a configured name or a URL query; `None` otherwise.
+    fn feed_url<'a>(&'a self, query: &'a str) -> Option<&'a str> {
+        let query = query.trim();
+        if let Some(url) = self.feeds.get(query) {
+            return Some(url);
+        }
+        (query.starts_with("https://") || query.starts_with("http://")).then_some(query)
+    }
+
+    /// Atom's `<link href>`, preferring `rel="alternate"` (or no `rel`).
+    fn atom_link(e: &BytesStart) -> Option<(String, bool)> {
+        let mut href = None;
+        let mut alternate = true;
+        for attr in e.attributes().flatten() {
+            match attr.key.local_name().as_ref() {
+                b"href" => href = Some(String::from_utf8_lossy(&attr.value).to_string()),
+                b"rel" => alternate = attr.value.as_ref() == b"alternate",
+                _ => {}
+            }
+        }
+        href.map(|href| (href, alternate))
+    }
+
+    /// Parse RSS 2.0, RSS 1.0 (RDF), or Atom. Tags are matched by local
+    /// name, so `dc:date` counts as a date.
+    fn parse_feed(xml: &str) -> Feed {
+        let mut feed = Feed::default();
+        let mut reader = Reader::from_str(xml);
+
+        let mut buf = Vec::new();
+        let mut path: Vec<String> = Vec::new();
+        let mut item: Option<Item> = None;
+
+        loop {
+            let event = reader.read_event_into(&mut buf);
+            match event {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    let tag = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                    match tag.as_str() {
+                        "item" | "entry" => item = Some(Item::default()),
+                        "link" => {
+                            if let (Some(item), Some((href, alternate))) =
+                                (item.as_mut(), Self::atom_link(e))
+                            {
+                                if alternate || item.link.is_empty() {
+                                    item.link = href;
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                    if matches!(event, Ok(Event::Start(_))) {
+                        path.push(tag);
+                    }
+                }
+                Ok(Event::End(e)) => {
+                    let tag = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                    path.pop();
+                    if tag == "item" || tag == "entry" {
+                        feed.items.extend(item.take());
+                    }
+                }
+                Ok(Event::Text(_)) | Ok(Event::CData(_)) => {
+                    let text = match event {
+                        Ok(Event::Text(e)) => {
+                            e.unescape().map(|s| s.to_string()).unwrap_or_default()
+                        }
+                        Ok(Event::CData(e)) => String::from_utf8_lossy(&e).to_string(),
+                        _ => unreachable!(),
+                    };
+                    let tag = path.last().map(String::as_str);
+                    match item.as_mut() {
+                        Some(item) => match tag {
+                            Some("title") => item.title.push_str(&text),
+                            Some("link") => item.link.push_str(text.trim()),
+                            Some("pubDate") | Some("date") | Some("published") => {
+                                item.date = text.trim().to_string()
+                            }
+                            Some("updated") if item.date.is_empty() => {
+                                item.date = text.trim().to_string()
+                            }
+                            Some("description") | Some("summary") | Some("content")
+                            | Some("encoded")
+                                if item.summary.is_empty() =>
+                            {
+                                item.summary = text
+                            }
+                            _ => {}
+                        },
+                        None if tag == Some("title") && feed.title.is_empty() => {
+                            feed.title = text.trim().to_string()
+                        }
+                        None => {}
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        feed
+    }
+
+    fn item_to_entry(feed_url: &str, feed: &Feed, item: &Item) -> KnowledgeEntry {
+        let summary = strip_tags(&item.summary);
+
+        let mut lines = Vec::new();
+        let mut header = vec![feed.title.as_str()];
+        if !item.date.is_empty() {
+            header.push(&item.date);
+        }
+        lines.push(header.join(" | "));
+        if !summary.is_empty() {
+            lines.push(if summary.chars().count() > 300 {
+                format!("{}...", summary.chars().take(300).collect::<String>())
+            } else {
+                summary
+            });
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("feed".to_string(), serde_json::json!(feed.title));
+        metadata.insert("feedUrl".to_string(), serde_json::json!(feed_url));
+        if !item.date.is_empty() {
+            metadata.insert("published".to_string(), serde_json::json!(item.date));
+        }
+
+        KnowledgeEntry {
+            title: item.title.trim().to_string(),
+            summary: lines.join("\n"),
+            url: (!item.link.is_empty()).then(|| item.link.clone()),
+            source: "rss".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+
+    fn fetch(&self, url: &str, limit: usize) -> Result<Vec<KnowledgeEntry>, String> {
+        let response = self.client.get(url).send().map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("feed request failed: {}", response.status()));
+        }
+
+        let xml = response.text().map_err(|e| e.to_string())?;
+        let feed = Self::parse_feed(&xml);
+
+        Ok(feed
+            .items
+            .iter()
+            .take(limit)
+            .map(|item| Self::item_to_entry(url, &feed, item))
+            .collect())
+    }
+}
+
+impl Default for RssProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for RssProvider {
+    fn name(&self) -> &'static str {
+        "rss"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn accepts(&self, query: &str) -> bool {
+        self.feed_url(query).is_some()
+    }
+
+    fn lookup(&self, query: &str, options: &LookupOptions) -> LookupResult {
+        let limit = options.max_results.unwrap_or(5);
+
+        // Only feed URLs and configured feeds can be read
+        let Some(url) = self.feed_url(query) else {
+            return LookupResult::success(self.name(), vec![]);
+        };
+
+        match self.fetch(url, limit) {
+            Ok(entries) => LookupResult::success(self.name(), entries),
+            Err(e) => LookupResult::error(self.name(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Requires network
+    fn rss_lookup() {
+        let provider = RssProvider::new();
+        let result = provider.lookup(
+            "https://blog.rust-lang.org/feed.xml",
+            &LookupOptions::default(),
+        );
+        assert!(result.success);
+        assert!(!result.entries.is_empty());
+    }
+
+    #[test]
+    fn parse_rss() {
+        let xml = r#"<?xml version="1.0"?>
+            <rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
+            <channel>
+              <title>Example Journal</title>
+              <link>https://example.org/</link>
+              <atom:link href="https://example.org/feed" rel="self"/>
+              <item>
+                <title>First &amp; foremost</title>
+                <link>https://example.org/1</link>
+                <pubDate>Tue, 01 Oct 2024 09:00:00 GMT</pubDate>
+                <description><![CDATA[<p>An <b>important</b> result.</p>]]></description>
+              </item>
+              <item><title>Second</title><link>https://example.org/2</link></item>
+            </channel>
+            </rss>"#;
+
+        let feed = RssProvider::parse_feed(xml);
+        assert_eq!(feed.title, "Example Journal");
+        assert_eq!(feed.items.len(), 2);
+
+        let entry = RssProvider::item_to_entry("https://example.org/feed", &feed, &feed.items[0]);
+        assert_eq!(entry.title, "First & foremost");
+        assert_eq!(entry.url.as_deref(), Some("https://example.org/1"));
+        assert_eq!(
+            entry.summary,
+            "Example Journal | Tue, 01 Oct 2024 09:00:00 GMT\nAn important result."
+        );
+    }
+
+    #[test]
+    fn parse_atom() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+            <feed xmlns="http://www.w3.org/2005/Atom">
+              <title>Example Blog</title>
+              <link href="https://example.com/"/>
+              <entry>
+                <title type="html">Release notes</title>
+                <link rel="replies" href="https://example.com/post#comments"/>
+                <link rel="alternate" href="https://example.com/post"/>
+                <updated>2024-10-02T00:00:00Z</updated>
+                <published>2024-10-01T00:00:00Z</published>
+                <summary>What changed.</summary>
+              </entry>
+            </feed>"#;
+
+        let feed = RssProvider::parse_feed(xml);
+        assert_eq!(feed.title, "Example Blog");
+        let item = &feed.items[0];
+        assert_eq!(item.title, "Release notes");
+        assert_eq!(item.link, "https://example.com/post");
+        assert_eq!(item.date, "2024-10-01T00:00:00Z");
+        assert_eq!(item.summary, "What changed.");
+    }
+
+    #[test]
+    fn resolves_named_feeds_and_urls() {
+        let feeds = HashMap::from([(
+            "rust".to_string(),
+            "https://blog.rust-lang.org/feed.xml".to_string(),
+        )]);
+        let provider = RssProvider::with_feeds(feeds);
+
+        assert_eq!(
+            provider.feed_url("rust"),
+            Some("https://blog.rust-lang.org/feed.xml")
+        );
+        assert_eq!(
+            provider.feed_url(" https://example.org/feed "),
+            Some("https://example.org/feed")
+        );
+        assert_eq!(provider.feed_url("rust programming"), None);
+    }
+}
@@ -74,9 +74,14 @@ impl ShodanProvider {
     }
 
     fn lookup_host(&self, ip: &str) -> Result<Option<KnowledgeEntry>, String> {
+        // The key rides in the query string, so errors drop the URL here and in `search`.
         let url = format!("{}/shodan/host/{}?key={}", SHODAN_API, ip, self.api_key);
 
-        let response = self.client.get(&url).send().map_err(|e| e.to_string())?;
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| e.without_url().to_string())?;
 
         if response.status().as_u16() == 404 {
             return Ok(None);
@@ -86,7 +91,7 @@ impl ShodanProvider {
             return Err(format!("host lookup failed: {}", response.status()));
         }
 
-        let data: HostResult = response.json().map_err(|e| e.to_string())?;
+        let data: HostResult = response.json().map_err(|e| e.without_url().to_string())?;
 
         let ip_str = data.ip_str.as_deref().unwrap_or(ip);
         let hostnames = data.hostnames.as_ref()
@@ -153,13 +158,17 @@ impl ShodanProvider {
             urlencoding::encode(query)
         );
 
-        let response = self.client.get(&url).send().map_err(|e| e.to_string())?;
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| e.without_url().to_string())?;
 
         if !response.status().is_success() {
             return Err(format!("search failed: {}", response.status()));
         }
 
-        let data: SearchResult = response.json().map_err(|e| e.to_string())?;
+        let data: SearchResult = response.json().map_err(|e| e.without_url().to_string())?;
 
         Ok(data
             .matches
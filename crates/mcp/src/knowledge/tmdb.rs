@@ -0,0 +1,217 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+const TMDB_API: &str = "https://api.themoviedb.org/3";
+
+/// Films and series from The Movie Database, with year, overview, and
+/// ratings.
+pub struct TmdbProvider {
+    client: Client,
+    api_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    results: Vec<Title>,
+}
+
+/// A film, or a series with `name` and `first_air_date` in place of `title`
+/// and `release_date`.
+#[derive(Debug, Deserialize)]
+struct Title {
+    id: u64,
+    #[serde(alias = "name")]
+    title: String,
+    #[serde(alias = "first_air_date")]
+    release_date: Option<String>,
+    #[serde(default)]
+    overview: String,
+    vote_average: Option<f64>,
+    #[serde(default)]
+    vote_count: u64,
+    original_language: Option<String>,
+}
+
+impl TmdbProvider {
+    pub fn new() -> Self {
+        Self::with_api_key("")
+    }
+
+    pub fn with_api_key(api_key: impl Into<String>) -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            api_key: api_key.into(),
+        }
+    }
+
+    /// `kind` is TMDB's media type, `movie` or `tv`.
+    fn title_to_entry(kind: &str, title: &Title) -> KnowledgeEntry {
+        let year = title
+            .release_date
+            .as_deref()
+            .and_then(|date| date.get(..4))
+            .filter(|year| !year.is_empty());
+        let label = if kind == "tv" { "TV series" } else { "Film" };
+
+        let mut header = vec![label.to_string()];
+        if let Some(rating) = title.vote_average.filter(|_| title.vote_count > 0) {
+            header.push(format!(
+                "Rating: {:.1}/10 ({} votes)",
+                rating, title.vote_count
+            ));
+        }
+        let mut lines = vec![header.join(" | ")];
+        if !title.overview.is_empty() {
+            lines.push(title.overview.clone());
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("type".to_string(), serde_json::json!(kind));
+        metadata.insert("tmdbId".to_string(), serde_json::json!(title.id));
+        if let Some(year) = year {
+            metadata.insert("year".to_string(), serde_json::json!(year));
+        }
+        if let Some(date) = title.release_date.as_deref().filter(|d| !d.is_empty()) {
+            metadata.insert("releaseDate".to_string(), serde_json::json!(date));
+        }
+        metadata.insert("rating".to_string(), serde_json::json!(title.vote_average));
+        metadata.insert("voteCount".to_string(), serde_json::json!(title.vote_count));
+        if let Some(language) = &title.original_language {
+            metadata.insert("language".to_string(), serde_json::json!(language));
+        }
+
+        KnowledgeEntry {
+            title: match year {
+                Some(year) => format!("{} ({})", title.title, year),
+                None => title.title.clone(),
+            },
+            summary: lines.join("\n"),
+            url: Some(format!("https://www.themoviedb.org/{}/{}", kind, title.id)),
+            source: "tmdb".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+
+    fn search(&self, kind: &str, query: &str, limit: usize) -> Result<Vec<KnowledgeEntry>, String> {
+        // `api_key` is in the URL; reqwest errors are stripped of it below.
+        let url = format!(
+            "{}/search/{}?query={}&api_key={}",
+            TMDB_API,
+            kind,
+            urlencoding::encode(query),
+            self.api_key
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| e.without_url().to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("search failed: {}", response.status()));
+        }
+
+        let data: SearchResponse = response.json().map_err(|e| e.without_url().to_string())?;
+
+        Ok(data
+            .results
+            .iter()
+            .take(limit)
+            .map(|title| Self::title_to_entry(kind, title))
+            .collect())
+    }
+}
+
+impl Default for TmdbProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for TmdbProvider {
+    fn name(&self) -> &'static str {
+        "tmdb"
+    }
+
+    fn is_available(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    fn lookup(&self, query: &str, options: &LookupOptions) -> LookupResult {
+        let limit = options.max_results.unwrap_or(5);
+
+        if self.api_key.is_empty() {
+            return LookupResult::error(self.name(), "TMDB API key not configured");
+        }
+
+        let mut entries = match self.search("movie", query, limit) {
+            Ok(e) => e,
+            Err(e) => return LookupResult::error(self.name(), e),
+        };
+
+        if entries.len() < limit {
+            let remaining = limit - entries.len();
+            match self.search("tv", query, remaining) {
+                Ok(series) => entries.extend(series),
+                Err(e) => return LookupResult::error(self.name(), e),
+            }
+        }
+
+        LookupResult::success(self.name(), entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tmdb_requires_api_key() {
+        let provider = TmdbProvider::new();
+        assert!(!provider.is_available());
+    }
+
+    #[test]
+    fn parse_movie_and_series() {
+        let movies = r#"{"page": 1, "results": [{
+            "id": 603, "title": "The Matrix", "release_date": "1999-03-30",
+            "overview": "A hacker learns the truth about his reality.",
+            "vote_average": 8.218, "vote_count": 26000, "original_language": "en"
+        }]}"#;
+        let series = r#"{"page": 1, "results": [{
+            "id": 1396, "name": "Breaking Bad", "first_air_date": "2008-01-20",
+            "overview": "", "vote_average": 0.0, "vote_count": 0
+        }]}"#;
+
+        let movies: SearchResponse = serde_json::from_str(movies).unwrap();
+        let entry = TmdbProvider::title_to_entry("movie", &movies.results[0]);
+        let metadata = entry.metadata.unwrap();
+        assert_eq!(entry.title, "The Matrix (1999)");
+        assert_eq!(
+            entry.summary,
+            "Film | Rating: 8.2/10 (26000 votes)\nA hacker learns the truth about his reality."
+        );
+        assert_eq!(
+            entry.url.as_deref(),
+            Some("https://www.themoviedb.org/movie/603")
+        );
+        assert_eq!(metadata["year"], "1999");
+
+        let series: SearchResponse = serde_json::from_str(series).unwrap();
+        let entry = TmdbProvider::title_to_entry("tv", &series.results[0]);
+        assert_eq!(entry.title, "Breaking Bad (2008)");
+        assert_eq!(entry.summary, "TV series");
+        assert_eq!(
+            entry.url.as_deref(),
+            Some("https://www.themoviedb.org/tv/1396")
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{strip_tags, KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+/// Definitions shown per part of speech.
+const MAX_DEFINITIONS: usize = 5;
+
+/// Dictionary definitions, parts of speech, and etymology from Wiktionary.
+pub struct WiktionaryProvider {
+    client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Usage {
+    part_of_speech: String,
+    language: String,
+    #[serde(default)]
+    definitions: Vec<Definition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Definition {
+    definition: String,
+    #[serde(default)]
+    examples: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtractResponse {
+    query: ExtractQuery,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtractQuery {
+    pages: HashMap<String, ExtractPage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtractPage {
+    extract: Option<String>,
+}
+
+/// The word in a "define X" style query, or `None` for other queries.
+pub(super) fn definition_term(query: &str) -> Option<&str> {
+    let query = query.trim();
+    ["define ", "definition of ", "meaning of "]
+        .iter()
+        .find_map(|prefix| {
+            query
+                .get(..prefix.len())
+                .filter(|head| head.eq_ignore_ascii_case(prefix))
+                .map(|_| query[prefix.len()..].trim())
+        })
+        .filter(|term| !term.is_empty())
+}
+
+impl WiktionaryProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+        }
+    }
+
+    fn definitions(&self, word: &str) -> Result<HashMap<String, Vec<Usage>>, String> {
+        let url = format!(
+            "https://en.wiktionary.org/api/rest_v1/page/definition/{}",
+            urlencoding::encode(word)
+        );
+
+        let response = self.client.get(&url).send().map_err(|e| e.to_string())?;
+
+        if response.status().as_u16() == 404 {
+            return Ok(HashMap::new());
+        }
+
+        if !response.status().is_success() {
+            return Err(format!("definition lookup failed: {}", response.status()));
+        }
+
+        response.json().map_err(|e| e.to_string())
+    }
+
+    /// The page as plain text, which is where the etymology sections are.
+    fn page_text(&self, word: &str) -> Option<String> {
+        let url = format!(
+            "https://en.wiktionary.org/w/api.php?action=query&prop=extracts&explaintext=1&format=json&titles={}",
+            urlencoding::encode(word)
+        );
+        let response = self.client.get(&url).send().ok()?;
+        let data: ExtractResponse = response.json().ok()?;
+        data.query.pages.into_values().find_map(|page| page.extract)
+    }
+
+    /// The first etymology under `language`'s `== Language ==` heading.
+    fn etymology(page: &str, language: &str) -> Option<String> {
+        let mut in_language = false;
+        let mut in_etymology = false;
+        let mut text = Vec::new();
+        for line in page.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('=') {
+                let heading = trimmed.trim_matches('=').trim();
+                let level = trimmed.chars().take_while(|&c| c == '=').count();
+                if in_etymology {
+                    break;
+                }
+                if level == 2 {
+                    in_language = heading == language;
+                } else if in_language && heading.starts_with("Etymology") {
+                    in_etymology = true;
+                }
+            } else if in_etymology && !trimmed.is_empty() {
+                text.push(trimmed);
+            }
+        }
+        (!text.is_empty()).then(|| text.join(" "))
+    }
+
+    fn usage_to_entry(word: &str, usage: &Usage, etymology: Option<&str>) -> KnowledgeEntry {
+        let definitions: Vec<String> = usage
+            .definitions
+            .iter()
+            .map(|d| strip_tags(&d.definition))
+            .filter(|d| !d.is_empty())
+            .collect();
+
+        let mut lines: Vec<String> = definitions
+            .iter()
+            .take(MAX_DEFINITIONS)
+            .enumerate()
+            .map(|(i, d)| format!("{}. {}", i + 1, d))
+            .collect();
+        if let Some(example) = usage.definitions.iter().flat_map(|d| &d.examples).next() {
+            lines.push(format!("Example: {}", strip_tags(example)));
+        }
+        if let Some(etymology) = etymology {
+            lines.push(format!("Etymology: {}", etymology));
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "partOfSpeech".to_string(),
+            serde_json::json!(usage.part_of_speech),
+        );
+        metadata.insert("language".to_string(), serde_json::json!(usage.language));
+        metadata.insert("definitions".to_string(), serde_json::json!(definitions));
+        if let Some(etymology) = etymology {
+            metadata.insert("etymology".to_string(), serde_json::json!(etymology));
+        }
+
+        KnowledgeEntry {
+            title: format!("{} ({})", word, usage.part_of_speech),
+            summary: lines.join("\n"),
+            url: Some(format!(
+                "https://en.wiktionary.org/wiki/{}#{}",
+                urlencoding::encode(&word.replace(' ', "_")),
+                usage.language.replace(' ', "_")
+            )),
+            source: "wiktionary".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+}
+
+impl Default for WiktionaryProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for WiktionaryProvider {
+    fn name(&self) -> &'static str {
+        "wiktionary"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn lookup(&self, query: &str, options: &LookupOptions) -> LookupResult {
+        let limit = options.max_results.unwrap_or(5);
+        let word = definition_term(query).unwrap_or(query.trim());
+        let language = options.language.as_deref().unwrap_or("en");
+
+        let mut usages = match self.definitions(word) {
+            Ok(usages) => usages,
+            Err(e) => return LookupResult::error(self.name(), e),
+        };
+        let Some(usages) = usages.remove(language) else {
+            return LookupResult::success(self.name(), vec![]);
+        };
+
+        let page = self.page_text(word);
+        let etymology = usages
+            .first()
+            .zip(page.as_deref())
+            .and_then(|(usage, page)| Self::etymology(page, &usage.language));
+
+        let entries = usages
+            .iter()
+            .take(limit)
+            .map(|usage| Self::usage_to_entry(word, usage, etymology.as_deref()))
+            .collect();
+        LookupResult::success(self.name(), entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Requires network
+    fn wiktionary_lookup() {
+        let provider = WiktionaryProvider::new();
+        let result = provider.lookup("define serendipity", &LookupOptions::default());
+        assert!(result.success);
+        assert!(!result.entries.is_empty());
+    }
+
+    #[test]
+    fn parse_definitions_and_etymology() {
+        let json = r#"{"en": [{
+            "partOfSpeech": "Noun",
+            "language": "English",
+            "definitions": [
+                {"definition": "An <a href=\"/wiki/unsought\">unsought</a>, unintended, and/or unexpected discovery.",
+                 "examples": ["It was pure <b>serendipity</b>."]},
+                {"definition": ""}
+            ]
+        }]}"#;
+        let page = "== English ==\n\n=== Etymology ===\nCoined by Horace Walpole in 1754.\n\n=== Noun ===\nserendipity\n\n== French ==\n=== Etymology ===\nBorrowed from English.\n";
+
+        let mut usages: HashMap<String, Vec<Usage>> = serde_json::from_str(json).unwrap();
+        let usages = usages.remove("en").unwrap();
+        let etymology = WiktionaryProvider::etymology(page, "English");
+        assert_eq!(
+            etymology.as_deref(),
+            Some("Coined by Horace Walpole in 1754.")
+        );
+
+        let entry =
+            WiktionaryProvider::usage_to_entry("serendipity", &usages[0], etymology.as_deref());
+        assert_eq!(entry.title, "serendipity (Noun)");
+        assert_eq!(
+            entry.summary,
+            "1. An unsought, unintended, and/or unexpected discovery.\n\
+             Example: It was pure serendipity.\n\
+             Etymology: Coined by Horace Walpole in 1754."
+        );
+    }
+
+    #[test]
+    fn recognizes_definition_queries() {
+        assert_eq!(definition_term("define serendipity"), Some("serendipity"));
+        assert_eq!(definition_term("Meaning of  ad hoc"), Some("ad hoc"));
+        assert_eq!(definition_term("defined behavior"), None);
+        assert_eq!(definition_term("define "), None);
+    }
+}
@@ -0,0 +1,335 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{KnowledgeEntry, KnowledgeProvider, LookupOptions, LookupResult};
+
+const YOUTUBE_API: &str = "https://www.googleapis.com/youtube/v3";
+
+/// Video title, channel, duration, and description from the YouTube Data
+/// API, for a video URL or a keyword search.
+pub struct YouTubeProvider {
+    client: Client,
+    api_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    items: Vec<SearchItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchItem {
+    id: SearchId,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchId {
+    video_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VideosResponse {
+    #[serde(default)]
+    items: Vec<Video>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Video {
+    id: String,
+    snippet: Snippet,
+    content_details: Option<ContentDetails>,
+    statistics: Option<Statistics>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Snippet {
+    title: String,
+    channel_title: String,
+    #[serde(default)]
+    description: String,
+    published_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentDetails {
+    duration: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Statistics {
+    view_count: Option<String>,
+}
+
+impl YouTubeProvider {
+    pub fn new() -> Self {
+        Self::with_api_key("")
+    }
+
+    pub fn with_api_key(api_key: impl Into<String>) -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("vault-tree-mcp/0.1 (https://github.com/Hydepwns/vault-tree)")
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            api_key: api_key.into(),
+        }
+    }
+
+    /// The video ID in a `watch?v=`, `youtu.be/`, `shorts/`, or `embed/` URL.
+    fn video_id(query: &str) -> Option<&str> {
+        let rest = query
+            .trim()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_start_matches("www.")
+            .trim_start_matches("m.");
+
+        let id = if let Some(path) = rest.strip_prefix("youtu.be/") {
+            path
+        } else if let Some(path) = rest.strip_prefix("youtube.com/") {
+            if let Some(params) = path.strip_prefix("watch?") {
+                params.split('&').find_map(|p| p.strip_prefix("v="))?
+            } else {
+                path.strip_prefix("shorts/")
+                    .or_else(|| path.strip_prefix("embed/"))
+                    .or_else(|| path.strip_prefix("live/"))?
+            }
+        } else {
+            return None;
+        };
+
+        let id = id.split(['?', '&', '#', '/']).next()?;
+        (id.len() == 11).then_some(id)
+    }
+
+    /// An ISO 8601 duration like `PT1H2M3S` as `1:02:03`.
+    fn format_duration(iso: &str) -> String {
+        let (mut hours, mut minutes, mut seconds) = (0u64, 0u64, 0u64);
+        let mut number = 0u64;
+        for c in iso.chars() {
+            match c {
+                '0'..='9' => number = number * 10 + c.to_digit(10).unwrap_or(0) as u64,
+                'D' => hours += number * 24,
+                'H' => hours += number,
+                'M' => minutes = number,
+                'S' => seconds = number,
+                _ => {}
+            }
+            if !c.is_ascii_digit() {
+                number = 0;
+            }
+        }
+        if hours > 0 {
+            format!("{}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            format!("{}:{:02}", minutes, seconds)
+        }
+    }
+
+    fn video_to_entry(video: &Video) -> KnowledgeEntry {
+        let snippet = &video.snippet;
+        let duration = video
+            .content_details
+            .as_ref()
+            .map(|d| Self::format_duration(&d.duration));
+        let views = video
+            .statistics
+            .as_ref()
+            .and_then(|s| s.view_count.as_deref())
+            .and_then(|v| v.parse::<u64>().ok());
+
+        let mut header = vec![format!("Channel: {}", snippet.channel_title)];
+        if let Some(duration) = &duration {
+            header.push(format!("Duration: {}", duration));
+        }
+        if let Some(views) = views {
+            header.push(format!("Views: {}", views));
+        }
+        let mut lines = vec![header.join(" | ")];
+        let description = snippet.description.trim();
+        if !description.is_empty() {
+            lines.push(if description.chars().count() > 300 {
+                format!("{}...", description.chars().take(300).collect::<String>())
+            } else {
+                description.to_string()
+            });
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("videoId".to_string(), serde_json::json!(video.id));
+        metadata.insert(
+            "channel".to_string(),
+            serde_json::json!(snippet.channel_title),
+        );
+        metadata.insert("duration".to_string(), serde_json::json!(duration));
+        metadata.insert("viewCount".to_string(), serde_json::json!(views));
+        if let Some(published) = &snippet.published_at {
+            metadata.insert("publishedAt".to_string(), serde_json::json!(published));
+        }
+
+        KnowledgeEntry {
+            title: snippet.title.clone(),
+            summary: lines.join("\n"),
+            url: Some(format!("https://www.youtube.com/watch?v={}", video.id)),
+            source: "youtube".to_string(),
+            metadata: Some(metadata),
+        }
+    }
+
+    /// GET `url`, which carries the API key, so errors leave it out.
+    fn get<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, String> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .map_err(|e| e.without_url().to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("YouTube request failed: {}", response.status()));
+        }
+
+        response.json().map_err(|e| e.without_url().to_string())
+    }
+
+    fn videos(&self, ids: &[&str]) -> Result<Vec<KnowledgeEntry>, String> {
+        let data: VideosResponse = self.get(&format!(
+            "{}/videos?part=snippet,contentDetails,statistics&id={}&key={}",
+            YOUTUBE_API,
+            ids.join(","),
+            self.api_key
+        ))?;
+        Ok(data.items.iter().map(Self::video_to_entry).collect())
+    }
+
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<KnowledgeEntry>, String> {
+        let data: SearchResponse = self.get(&format!(
+            "{}/search?part=id&type=video&maxResults={}&q={}&key={}",
+            YOUTUBE_API,
+            limit,
+            urlencoding::encode(query),
+            self.api_key
+        ))?;
+        // Search results lack durations, so the videos are fetched in one batch.
+        let ids: Vec<&str> = data
+            .items
+            .iter()
+            .filter_map(|item| item.id.video_id.as_deref())
+            .collect();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.videos(&ids)
+    }
+}
+
+impl Default for YouTubeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnowledgeProvider for YouTubeProvider {
+    fn name(&self) -> &'static str {
+        "youtube"
+    }
+
+    fn is_available(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    fn lookup(&self, query: &str, options: &LookupOptions) -> LookupResult {
+        let limit = options.max_results.unwrap_or(5);
+
+        if self.api_key.is_empty() {
+            return LookupResult::error(self.name(), "YouTube API key not configured");
+        }
+
+        let result = match Self::video_id(query) {
+            Some(id) => self.videos(&[id]),
+            None => self.search(query, limit),
+        };
+
+        match result {
+            Ok(entries) => LookupResult::success(self.name(), entries),
+            Err(e) => LookupResult::error(self.name(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn youtube_requires_api_key() {
+        let provider = YouTubeProvider::new();
+        assert!(!provider.is_available());
+    }
+
+    #[test]
+    fn parse_video() {
+        let json = r#"{"items": [{
+            "id": "dQw4w9WgXcQ",
+            "snippet": {
+                "publishedAt": "2009-10-25T06:57:33Z",
+                "title": "Rick Astley - Never Gonna Give You Up",
+                "channelTitle": "Rick Astley",
+                "description": "The official video."
+            },
+            "contentDetails": {"duration": "PT3M33S"},
+            "statistics": {"viewCount": "1500000000"}
+        }]}"#;
+
+        let data: VideosResponse = serde_json::from_str(json).unwrap();
+        let entry = YouTubeProvider::video_to_entry(&data.items[0]);
+        let metadata = entry.metadata.unwrap();
+
+        assert_eq!(entry.title, "Rick Astley - Never Gonna Give You Up");
+        assert_eq!(
+            entry.summary,
+            "Channel: Rick Astley | Duration: 3:33 | Views: 1500000000\nThe official video."
+        );
+        assert_eq!(metadata["duration"], "3:33");
+        assert_eq!(
+            entry.url.as_deref(),
+            Some("https://www.youtube.com/watch?v=dQw4w9WgXcQ")
+        );
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(YouTubeProvider::format_duration("PT3M33S"), "3:33");
+        assert_eq!(YouTubeProvider::format_duration("PT1H2M3S"), "1:02:03");
+        assert_eq!(YouTubeProvider::format_duration("PT45S"), "0:45");
+        assert_eq!(YouTubeProvider::format_duration("P1DT2H"), "26:00:00");
+    }
+
+    #[test]
+    fn recognizes_video_urls() {
+        for url in [
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://youtube.com/watch?feature=share&v=dQw4w9WgXcQ&t=42",
+            "https://youtu.be/dQw4w9WgXcQ?t=42",
+            "https://m.youtube.com/shorts/dQw4w9WgXcQ",
+            "youtube.com/embed/dQw4w9WgXcQ",
+        ] {
+            assert_eq!(
+                YouTubeProvider::video_id(url),
+                Some("dQw4w9WgXcQ"),
+                "{}",
+                url
+            );
+        }
+        assert_eq!(YouTubeProvider::video_id("rust lecture"), None);
+        assert_eq!(
+            YouTubeProvider::video_id("https://example.com/watch?v=x"),
+            None
+        );
+    }
+}
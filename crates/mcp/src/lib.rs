@@ -1,4 +1,11 @@
+pub mod cancel;
+pub mod config;
+pub mod http;
 pub mod knowledge;
+pub mod logging;
+pub mod prompts;
+pub mod resources;
 pub mod server;
+pub mod stdio;
 pub mod tools;
 pub mod transport;
@@ -0,0 +1,81 @@
+//! `notifications/message` logging. The server sets a sink for each request
+//! it handles; tool code calls [`log`], which sends a notification if the
+//! message is at or above the level the client chose with `logging/setLevel`.
+//! Like the cancel token, the sink is thread-local, so logs from threads a
+//! tool spawns (such as rayon workers) are dropped.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// Syslog severities, as used by MCP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Notice,
+    Warning,
+    Error,
+    Critical,
+    Alert,
+    Emergency,
+}
+
+/// Receives serialized JSON-RPC notifications.
+pub type Notifier = Arc<dyn Fn(String) + Send + Sync>;
+
+struct Sink {
+    notifier: Notifier,
+    level: LogLevel,
+}
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous sink when dropped, even while unwinding.
+struct ScopeGuard {
+    previous: Option<Sink>,
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        SINK.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Run `f` sending logs at `level` and above to `notifier`.
+pub fn scope<R>(notifier: &Notifier, level: LogLevel, f: impl FnOnce() -> R) -> R {
+    let sink = Sink {
+        notifier: notifier.clone(),
+        level,
+    };
+    let _guard = ScopeGuard {
+        previous: SINK.with(|current| current.replace(Some(sink))),
+    };
+    f()
+}
+
+/// Log `data` from `logger` (usually the tool name) to the client. Does
+/// nothing outside a request or below the client's level.
+pub fn log(level: LogLevel, logger: &str, data: impl Into<Value>) {
+    SINK.with(|current| {
+        let current = current.borrow();
+        let Some(sink) = current.as_ref().filter(|sink| level >= sink.level) else {
+            return;
+        };
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/message",
+            "params": {
+                "level": level,
+                "logger": logger,
+                "data": data.into()
+            }
+        });
+        (sink.notifier)(notification.to_string());
+    });
+}
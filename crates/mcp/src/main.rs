@@ -1,25 +1,64 @@
-use anyhow::Result;
-use std::io::{self, BufRead, Write};
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+use vault_tree_mcp::config::Config;
 use vault_tree_mcp::server::McpServer;
+use vault_tree_mcp::{http, stdio};
+
+#[derive(Default)]
+struct Args {
+    /// Config file to use instead of `~/.config/vault-tree/mcp.toml`.
+    config: Option<PathBuf>,
+    /// Vault whose notes are served as `vault://` resources; overrides the
+    /// config's `vault`.
+    vault: Option<PathBuf>,
+    /// Serve streamable HTTP on this address (or port, on loopback) instead
+    /// of stdio.
+    http: Option<String>,
+}
 
 fn main() -> Result<()> {
-    let mut server = McpServer::new();
+    let args = parse_args()?;
 
-    let stdin = io::stdin();
-    let mut stdout = io::stdout();
+    let mut config = match &args.config {
+        Some(path) => Config::load(path)?,
+        None => Config::load_default()?,
+    };
+    if let Some(vault) = args.vault {
+        config.vault = Some(vault);
+    }
 
-    for line in stdin.lock().lines() {
-        let line = line?;
-        if line.trim().is_empty() {
-            continue;
-        }
+    let runtime = tokio::runtime::Runtime::new()?;
 
-        let response = server.handle_request(&line);
-        if let Some(resp) = response {
-            writeln!(stdout, "{}", resp)?;
-            stdout.flush()?;
-        }
+    if let Some(addr) = args.http {
+        let addr = if addr.bytes().all(|b| b.is_ascii_digit()) {
+            format!("127.0.0.1:{}", addr)
+        } else {
+            addr
+        };
+        eprintln!("vault-tree-mcp listening on http://{}/mcp", addr);
+        runtime.block_on(http::serve(addr, config))?;
+        return Ok(());
     }
 
+    runtime.block_on(stdio::serve(McpServer::with_config(config)))?;
+
     Ok(())
 }
+
+fn parse_args() -> Result<Args> {
+    let mut args = std::env::args().skip(1);
+    let mut parsed = Args::default();
+    while let Some(arg) = args.next() {
+        let mut value = || match args.next() {
+            Some(value) => Ok(value),
+            None => bail!("{} requires a value", arg),
+        };
+        match arg.as_str() {
+            "--config" => parsed.config = Some(PathBuf::from(value()?)),
+            "--vault" => parsed.vault = Some(PathBuf::from(value()?)),
+            "--http" => parsed.http = Some(value()?),
+            _ => bail!("unknown argument: {}", arg),
+        }
+    }
+    Ok(parsed)
+}
@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::Path;
+use vault_tree_core::{generate_tree, render_tree, scan_secrets, ExcludeSet, TreeOptions};
+
+/// Characters of each note included in `classify_notes`.
+const EXCERPT_CHARS: usize = 2000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptDefinition {
+    pub name: String,
+    pub description: String,
+    pub arguments: Vec<PromptArgument>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptArgument {
+    pub name: String,
+    pub description: String,
+    pub required: bool,
+}
+
+fn argument(name: &str, description: &str, required: bool) -> PromptArgument {
+    PromptArgument {
+        name: name.to_string(),
+        description: description.to_string(),
+        required,
+    }
+}
+
+pub fn list_prompts() -> Vec<PromptDefinition> {
+    vec![
+        PromptDefinition {
+            name: "summarize_subtree".to_string(),
+            description: "Summarize the notes in a vault folder from its annotated tree"
+                .to_string(),
+            arguments: vec![
+                argument("vault_path", "Path to the Obsidian vault directory", true),
+                argument(
+                    "folder",
+                    "Folder inside the vault to summarize (default the whole vault)",
+                    false,
+                ),
+                argument("depth", "Maximum tree depth to include (default 3)", false),
+            ],
+        },
+        PromptDefinition {
+            name: "classify_notes".to_string(),
+            description: "Suggest a category, tags, and folder for each of the given notes"
+                .to_string(),
+            arguments: vec![
+                argument("vault_path", "Path to the Obsidian vault directory", true),
+                argument(
+                    "files",
+                    "Vault-relative note paths, separated by commas or newlines",
+                    true,
+                ),
+                argument(
+                    "categories",
+                    "Comma-separated categories to choose from (optional)",
+                    false,
+                ),
+            ],
+        },
+        PromptDefinition {
+            name: "triage_secrets".to_string(),
+            description:
+                "Scan a vault for likely credentials and triage the findings before publishing"
+                    .to_string(),
+            arguments: vec![argument(
+                "vault_path",
+                "Path to the Obsidian vault directory",
+                true,
+            )],
+        },
+    ]
+}
+
+/// Render a prompt's messages. Prompt arguments are strings, per the MCP
+/// spec.
+pub fn get_prompt(name: &str, arguments: &Map<String, Value>) -> Result<Value, String> {
+    let arg = |key: &str| arguments.get(key).and_then(Value::as_str);
+    let required = |key: &str| arg(key).ok_or_else(|| format!("missing argument: {}", key));
+
+    let (description, text) = match name {
+        "summarize_subtree" => (
+            "Summarize a vault folder",
+            summarize_subtree(required("vault_path")?, arg("folder"), arg("depth"))?,
+        ),
+        "classify_notes" => (
+            "Classify vault notes",
+            classify_notes(
+                required("vault_path")?,
+                required("files")?,
+                arg("categories"),
+            )?,
+        ),
+        "triage_secrets" => (
+            "Triage likely secrets in a vault",
+            triage_secrets(required("vault_path")?)?,
+        ),
+        _ => return Err(format!("unknown prompt: {}", name)),
+    };
+
+    Ok(json!({
+        "description": description,
+        "messages": [{
+            "role": "user",
+            "content": {
+                "type": "text",
+                "text": text
+            }
+        }]
+    }))
+}
+
+fn summarize_subtree(
+    vault_path: &str,
+    folder: Option<&str>,
+    depth: Option<&str>,
+) -> Result<String, String> {
+    let depth = match depth {
+        Some(depth) => depth
+            .parse()
+            .map_err(|_| format!("invalid depth: {}", depth))?,
+        None => 3,
+    };
+    let root = match folder {
+        Some(folder) => Path::new(vault_path).join(folder),
+        None => Path::new(vault_path).to_path_buf(),
+    };
+    let options = TreeOptions {
+        depth: Some(depth),
+        ..Default::default()
+    };
+    let tree = generate_tree(&root, &options).map_err(|e| e.to_string())?;
+
+    Ok(format!(
+        "Summarize the notes in `{}` of the Obsidian vault at `{}`. Below is its annotated \
+         tree: each note shows its tags, date, and incoming/outgoing link counts.\n\n\
+         ```\n{}```\n\n\
+         Describe the main topics and how the notes relate, point out well-connected hub \
+         notes and isolated or stale ones, and suggest where a newcomer should start reading. \
+         Read individual notes if the tree alone isn't enough.",
+        folder.unwrap_or("/"),
+        vault_path,
+        render_tree(&tree)
+    ))
+}
+
+fn classify_notes(
+    vault_path: &str,
+    files: &str,
+    categories: Option<&str>,
+) -> Result<String, String> {
+    let mut text = String::from(
+        "Classify each of the following notes. For every note give a category, up to five \
+         tags, and the vault folder it belongs in, as a table with one row per note.\n",
+    );
+    if let Some(categories) = categories {
+        text.push_str(&format!(
+            "Choose categories from: {}. Use \"other\" when none fit.\n",
+            categories
+        ));
+    }
+
+    let vault = Path::new(vault_path);
+    for file in files
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+    {
+        let content = fs::read_to_string(vault.join(file))
+            .map_err(|e| format!("cannot read {}: {}", file, e))?;
+        let excerpt: String = content.chars().take(EXCERPT_CHARS).collect();
+        let ellipsis = if excerpt.len() < content.len() {
+            "\n..."
+        } else {
+            ""
+        };
+        text.push_str(&format!(
+            "\n## {}\n\n```markdown\n{}{}\n```\n",
+            file, excerpt, ellipsis
+        ));
+    }
+    Ok(text)
+}
+
+fn triage_secrets(vault_path: &str) -> Result<String, String> {
+    let vault = Path::new(vault_path);
+    if !vault.exists() {
+        return Err(format!("vault path does not exist: {}", vault_path));
+    }
+
+    let exclude = ExcludeSet::load(vault, &[]).map_err(|e| e.to_string())?;
+    let mut findings = String::new();
+    let mut count = 0;
+    for entry in exclude.walk_markdown(vault, vault) {
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let relative = entry.path().strip_prefix(vault).unwrap_or(entry.path());
+        for finding in scan_secrets(&content) {
+            findings.push_str(&format!(
+                "- {}:{} {} `{}`\n",
+                relative.display(),
+                finding.line_number,
+                finding.kind,
+                finding.redacted
+            ));
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        return Ok(format!(
+            "A secrets scan of the vault at `{}` found nothing that looks like a credential. \
+             Confirm whether any notes are known to hold sensitive data the patterns would miss \
+             (recovery codes, personal identifiers) before publishing.",
+            vault_path
+        ));
+    }
+    Ok(format!(
+        "A secrets scan of the vault at `{}` flagged {} lines (values are redacted):\n\n{}\n\
+         Triage these findings: mark likely false positives, rank the rest by severity, and \
+         for each real secret recommend a fix, such as rotating the credential, moving it to \
+         a password manager, or excluding the note from publishing via `.vaultignore`.",
+        vault_path, count, findings
+    ))
+}
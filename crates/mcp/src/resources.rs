@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use vault_tree_core::{extract_frontmatter, ExcludeSet};
+
+const SCHEME: &str = "vault://";
+const MARKDOWN: &str = "text/markdown";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceDefinition {
+    pub uri: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+}
+
+/// Every note in the vault as a `vault://path/to/note.md` resource, sorted by
+/// path. Paths excluded by `.vaultignore` are left out.
+pub fn list_resources(vault: &Path) -> Vec<ResourceDefinition> {
+    let exclude = ExcludeSet::load(vault, &[]).unwrap_or_default();
+    let mut resources: Vec<ResourceDefinition> = exclude
+        .walk_markdown(vault, vault)
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(vault).ok()?;
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            let title = fs::read_to_string(entry.path())
+                .ok()
+                .and_then(|content| extract_frontmatter(&content).ok())
+                .and_then(|fm| fm.title);
+            Some(ResourceDefinition {
+                uri: format!("{}{}", SCHEME, encode(&relative)),
+                name: relative,
+                description: title,
+                mime_type: MARKDOWN.to_string(),
+            })
+        })
+        .collect();
+    resources.sort_by(|a, b| a.name.cmp(&b.name));
+    resources
+}
+
+/// The content of a `vault://` note. Only files inside the vault can be read.
+pub fn read_resource(vault: &Path, uri: &str) -> Result<Value, String> {
+    let path = resolve_uri(vault, uri)?;
+    let text = fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", uri, e))?;
+
+    Ok(json!({
+        "contents": [{
+            "uri": uri,
+            "mimeType": MARKDOWN,
+            "text": text
+        }]
+    }))
+}
+
+fn resolve_uri(vault: &Path, uri: &str) -> Result<PathBuf, String> {
+    let relative = uri
+        .strip_prefix(SCHEME)
+        .map(decode)
+        .ok_or_else(|| format!("unsupported resource uri: {}", uri))?;
+    let relative = Path::new(&relative);
+    if !relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(format!("resource not found: {}", uri));
+    }
+
+    let path = vault.join(relative);
+    let inside = match (path.canonicalize(), vault.canonicalize()) {
+        (Ok(path), Ok(vault)) => path.starts_with(vault) && path.is_file(),
+        _ => false,
+    };
+    if inside {
+        Ok(path)
+    } else {
+        Err(format!("resource not found: {}", uri))
+    }
+}
+
+/// Percent-encode the characters that aren't valid in a URI path.
+fn encode(path: &str) -> String {
+    let mut uri = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+fn decode(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
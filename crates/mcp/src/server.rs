@@ -1,24 +1,156 @@
-use serde_json::json;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use vault_tree_core::MetadataCache;
 
-use crate::tools::{call_tool, list_tools};
+use crate::cancel::{self, CancelToken};
+use crate::config::Config;
+use crate::knowledge::KnowledgeRegistry;
+use crate::logging::{self, LogLevel, Notifier};
+use crate::prompts::{get_prompt, list_prompts};
+use crate::resources::{list_resources, read_resource};
+use crate::tools::{call_tool, list_tools, ToolContext, ToolDefinition};
 use crate::transport::{
-    JsonRpcRequest, JsonRpcResponse, INTERNAL_ERROR, INVALID_PARAMS, METHOD_NOT_FOUND, PARSE_ERROR,
+    JsonRpcRequest, JsonRpcResponse, INTERNAL_ERROR, INVALID_PARAMS, INVALID_REQUEST,
+    METHOD_NOT_FOUND, PARSE_ERROR, RESOURCE_NOT_FOUND,
 };
 
 const SERVER_NAME: &str = "vault-tree-mcp";
 const SERVER_VERSION: &str = env!("CARGO_PKG_VERSION");
-const PROTOCOL_VERSION: &str = "2024-11-05";
+/// Newest first; the first is offered to clients asking for another version.
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+/// Items per page of `tools/list`, `prompts/list`, and `resources/list`.
+const DEFAULT_PAGE_SIZE: usize = 100;
 
+/// Requests are handled through `&self`, so one server can answer several
+/// requests at once; see [`McpServer::handle_request_async`].
 pub struct McpServer {
-    initialized: bool,
+    initialized: AtomicBool,
+    /// The config's vault is served as `vault://` resources and fills in
+    /// vault paths that tool calls leave out.
+    config: Config,
+    /// Vault set with `vault_set_default`, ahead of the config's.
+    default_vault: Mutex<Option<PathBuf>>,
+    /// Kept for the server's lifetime so lookups share one cache.
+    knowledge: OnceLock<KnowledgeRegistry>,
+    /// Note scans and hashes reused by vault tools across calls.
+    metadata: MetadataCache,
+    /// Minimum level of logs sent to the client, set with `logging/setLevel`.
+    log_level: Mutex<LogLevel>,
+    /// Tokens for requests being handled, keyed by their JSON-encoded id.
+    in_flight: Mutex<HashMap<String, CancelToken>>,
 }
 
 impl McpServer {
     pub fn new() -> Self {
-        Self { initialized: false }
+        Self {
+            initialized: AtomicBool::new(false),
+            config: Config::default(),
+            default_vault: Mutex::new(None),
+            knowledge: OnceLock::new(),
+            metadata: MetadataCache::new(),
+            log_level: Mutex::new(LogLevel::Warning),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// A server exposing the notes in `vault` as resources.
+    pub fn with_vault(vault: impl Into<PathBuf>) -> Self {
+        Self::with_config(Config {
+            vault: Some(vault.into()),
+            ..Default::default()
+        })
+    }
+
+    pub fn with_config(config: Config) -> Self {
+        Self {
+            config,
+            ..Self::new()
+        }
+    }
+
+    /// Handle a request on tokio's blocking pool. Tools do blocking file and
+    /// network I/O, so a slow call doesn't hold up others, like `ping`.
+    ///
+    /// The request is registered for cancellation before this returns, so a
+    /// `notifications/cancelled` handled afterwards reaches it even if the
+    /// future hasn't been polled yet. The future resolves to `None` without
+    /// waiting for the handler once the client cancels the request.
+    pub fn handle_request_async(
+        self: Arc<Self>,
+        input: String,
+    ) -> impl Future<Output = Option<String>> + Send + 'static {
+        self.handle_request_notifying(input, Arc::new(|_| {}))
+    }
+
+    /// Like [`handle_request_async`](Self::handle_request_async), passing
+    /// the handler's `notifications/message` logs to `notify` as they happen.
+    pub fn handle_request_notifying(
+        self: Arc<Self>,
+        input: String,
+        notify: Notifier,
+    ) -> impl Future<Output = Option<String>> + Send + 'static {
+        let id = serde_json::from_str::<JsonRpcRequest>(&input)
+            .ok()
+            .filter(|request| request.method != "initialize")
+            .and_then(|request| request.id);
+        let token = CancelToken::default();
+        // A second request reusing the id of one still running is refused,
+        // so a cancellation can't reach the wrong request.
+        let registered = id.as_ref().map(|id| {
+            let key = id.to_string();
+            match self.in_flight().entry(key.clone()) {
+                Entry::Occupied(_) => None,
+                Entry::Vacant(entry) => {
+                    entry.insert(token.clone());
+                    Some(InFlight {
+                        server: self.clone(),
+                        key,
+                    })
+                }
+            }
+        });
+
+        async move {
+            if let Some(None) = registered {
+                let resp = JsonRpcResponse::error(
+                    id,
+                    INVALID_REQUEST,
+                    "a request with this id is already in progress",
+                );
+                return Some(serde_json::to_string(&resp).unwrap());
+            }
+
+            // Unregistered once handling ends, or if this future is dropped.
+            let _registered = registered;
+            let server = self.clone();
+            let handler_token = token.clone();
+            let level = *self.log_level();
+            let handler = tokio::task::spawn_blocking(move || {
+                cancel::scope(&handler_token, || {
+                    logging::scope(&notify, level, || server.handle_request(&input))
+                })
+            });
+            let output = tokio::select! {
+                output = handler => output.unwrap_or_else(|_| {
+                    let resp =
+                        JsonRpcResponse::error(None, INTERNAL_ERROR, "request handler panicked");
+                    Some(serde_json::to_string(&resp).unwrap())
+                }),
+                _ = token.cancelled() => None,
+            };
+
+            // Cancelled requests get no response, even if they finished anyway.
+            output.filter(|_| !token.is_cancelled())
+        }
     }
 
-    pub fn handle_request(&mut self, input: &str) -> Option<String> {
+    pub fn handle_request(&self, input: &str) -> Option<String> {
         let request: JsonRpcRequest = match serde_json::from_str(input) {
             Ok(r) => r,
             Err(_) => {
@@ -30,11 +162,20 @@ impl McpServer {
         let response = match request.method.as_str() {
             "initialize" => self.handle_initialize(&request),
             "initialized" => {
-                self.initialized = true;
+                self.initialized.store(true, Ordering::Relaxed);
+                return None;
+            }
+            "notifications/cancelled" => {
+                self.handle_cancelled(&request);
                 return None;
             }
             "tools/list" => self.handle_tools_list(&request),
             "tools/call" => self.handle_tools_call(&request),
+            "resources/list" => self.handle_resources_list(&request),
+            "resources/read" => self.handle_resources_read(&request),
+            "prompts/list" => self.handle_prompts_list(&request),
+            "prompts/get" => self.handle_prompts_get(&request),
+            "logging/setLevel" => self.handle_set_level(&request),
             "ping" => JsonRpcResponse::success(request.id, json!({})),
             _ => JsonRpcResponse::error(
                 request.id,
@@ -46,14 +187,26 @@ impl McpServer {
         Some(serde_json::to_string(&response).unwrap())
     }
 
-    fn handle_initialize(&mut self, request: &JsonRpcRequest) -> JsonRpcResponse {
+    fn handle_initialize(&self, request: &JsonRpcRequest) -> JsonRpcResponse {
+        let mut capabilities = json!({ "tools": {}, "prompts": {}, "logging": {} });
+        if self.config.vault.is_some() {
+            capabilities["resources"] = json!({});
+        }
+        let requested = request
+            .params
+            .as_ref()
+            .and_then(|p| p.get("protocolVersion"))
+            .and_then(|v| v.as_str());
+        let protocol_version = PROTOCOL_VERSIONS
+            .iter()
+            .find(|version| Some(**version) == requested)
+            .unwrap_or(&PROTOCOL_VERSIONS[0]);
+
         JsonRpcResponse::success(
             request.id.clone(),
             json!({
-                "protocolVersion": PROTOCOL_VERSION,
-                "capabilities": {
-                    "tools": {}
-                },
+                "protocolVersion": protocol_version,
+                "capabilities": capabilities,
                 "serverInfo": {
                     "name": SERVER_NAME,
                     "version": SERVER_VERSION
@@ -62,9 +215,110 @@ impl McpServer {
         )
     }
 
+    fn handle_cancelled(&self, request: &JsonRpcRequest) {
+        let Some(id) = request.params.as_ref().and_then(|p| p.get("requestId")) else {
+            return;
+        };
+        if let Some(token) = self.in_flight().get(&id.to_string()) {
+            token.cancel();
+        }
+    }
+
+    /// One page of a `*/list` result under `key`, starting at the request's
+    /// `cursor`, with a `nextCursor` if more items follow.
+    fn paged<T: Serialize>(
+        &self,
+        request: &JsonRpcRequest,
+        key: &str,
+        items: Vec<T>,
+    ) -> JsonRpcResponse {
+        let cursor = request
+            .params
+            .as_ref()
+            .and_then(|p| p.get("cursor"))
+            .and_then(|v| v.as_str());
+        let start = match cursor.map(str::parse::<usize>) {
+            None => 0,
+            Some(Ok(start)) if start <= items.len() => start,
+            Some(_) => {
+                return JsonRpcResponse::error(request.id.clone(), INVALID_PARAMS, "Invalid cursor")
+            }
+        };
+        let size = self.config.page_size.unwrap_or(DEFAULT_PAGE_SIZE).max(1);
+        let end = items.len().min(start + size);
+
+        let mut result = json!({ key: &items[start..end] });
+        if end < items.len() {
+            result["nextCursor"] = json!(end.to_string());
+        }
+        JsonRpcResponse::success(request.id.clone(), result)
+    }
+
+    /// Tool definitions, with configured paths no longer required.
+    fn tools(&self) -> Vec<ToolDefinition> {
+        let defaults: Vec<&str> = self
+            .path_defaults()
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        let mut tools = list_tools(&self.tool_context());
+        for tool in &mut tools {
+            if let Some(required) = tool.input_schema["required"].as_array_mut() {
+                required.retain(|key| !key.as_str().is_some_and(|k| defaults.contains(&k)));
+            }
+        }
+        tools
+    }
+
+    fn tool_context(&self) -> ToolContext<'_> {
+        ToolContext {
+            config: &self.config,
+            default_vault: &self.default_vault,
+            knowledge: &self.knowledge,
+            metadata: &self.metadata,
+        }
+    }
+
+    /// Path arguments filled in when a call leaves them out: the session's
+    /// default vault, then the config's.
+    fn path_defaults(&self) -> Vec<(&'static str, Value)> {
+        let vault = self
+            .default_vault
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .or_else(|| self.config.vault.clone());
+        vault
+            .map(|path| ("vault_path", json!(path.to_string_lossy())))
+            .into_iter()
+            .collect()
+    }
+
+    fn handle_set_level(&self, request: &JsonRpcRequest) -> JsonRpcResponse {
+        let level = request
+            .params
+            .as_ref()
+            .and_then(|p| p.get("level"))
+            .cloned();
+        match level.map(serde_json::from_value::<LogLevel>) {
+            Some(Ok(level)) => {
+                *self.log_level() = level;
+                JsonRpcResponse::success(request.id.clone(), json!({}))
+            }
+            _ => JsonRpcResponse::error(request.id.clone(), INVALID_PARAMS, "Invalid log level"),
+        }
+    }
+
+    fn log_level(&self) -> MutexGuard<'_, LogLevel> {
+        self.log_level.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn in_flight(&self) -> MutexGuard<'_, HashMap<String, CancelToken>> {
+        self.in_flight.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     fn handle_tools_list(&self, request: &JsonRpcRequest) -> JsonRpcResponse {
-        let tools = list_tools();
-        JsonRpcResponse::success(request.id.clone(), json!({ "tools": tools }))
+        self.paged(request, "tools", self.tools())
     }
 
     fn handle_tools_call(&self, request: &JsonRpcRequest) -> JsonRpcResponse {
@@ -80,13 +334,82 @@ impl McpServer {
             .and_then(|v| v.as_str())
             .unwrap_or_default();
 
-        let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+        let mut arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+        if let Some(arguments) = arguments.as_object_mut() {
+            let tool = self.tools().into_iter().find(|tool| tool.name == name);
+            let properties = tool
+                .as_ref()
+                .and_then(|t| t.input_schema["properties"].as_object());
+            for (key, value) in self.path_defaults() {
+                if properties.is_some_and(|p| p.contains_key(key)) {
+                    arguments.entry(key).or_insert(value);
+                }
+            }
+        }
 
-        match call_tool(name, arguments) {
+        match call_tool(name, arguments, &self.tool_context()) {
             Ok(result) => JsonRpcResponse::success(request.id.clone(), result),
             Err(e) => JsonRpcResponse::error(request.id.clone(), INTERNAL_ERROR, e),
         }
     }
+
+    fn handle_prompts_list(&self, request: &JsonRpcRequest) -> JsonRpcResponse {
+        self.paged(request, "prompts", list_prompts())
+    }
+
+    fn handle_prompts_get(&self, request: &JsonRpcRequest) -> JsonRpcResponse {
+        let params = request.params.as_ref();
+        let Some(name) = params.and_then(|p| p.get("name")).and_then(|v| v.as_str()) else {
+            return JsonRpcResponse::error(request.id.clone(), INVALID_PARAMS, "Missing name");
+        };
+        let mut arguments = params
+            .and_then(|p| p.get("arguments"))
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+        // Prompt arguments are strings, so config paths fill in the same way.
+        for (key, value) in self.path_defaults() {
+            arguments.entry(key).or_insert(value);
+        }
+
+        match get_prompt(name, &arguments) {
+            Ok(result) => JsonRpcResponse::success(request.id.clone(), result),
+            Err(e) => JsonRpcResponse::error(request.id.clone(), INVALID_PARAMS, e),
+        }
+    }
+
+    fn handle_resources_list(&self, request: &JsonRpcRequest) -> JsonRpcResponse {
+        let resources = self
+            .config
+            .vault
+            .as_deref()
+            .map(list_resources)
+            .unwrap_or_default();
+        self.paged(request, "resources", resources)
+    }
+
+    fn handle_resources_read(&self, request: &JsonRpcRequest) -> JsonRpcResponse {
+        let Some(uri) = request
+            .params
+            .as_ref()
+            .and_then(|p| p.get("uri"))
+            .and_then(|v| v.as_str())
+        else {
+            return JsonRpcResponse::error(request.id.clone(), INVALID_PARAMS, "Missing uri");
+        };
+        let Some(vault) = &self.config.vault else {
+            return JsonRpcResponse::error(
+                request.id.clone(),
+                RESOURCE_NOT_FOUND,
+                "no vault configured; start the server with --vault <path>",
+            );
+        };
+
+        match read_resource(vault, uri) {
+            Ok(result) => JsonRpcResponse::success(request.id.clone(), result),
+            Err(e) => JsonRpcResponse::error(request.id.clone(), RESOURCE_NOT_FOUND, e),
+        }
+    }
 }
 
 impl Default for McpServer {
@@ -94,3 +417,16 @@ impl Default for McpServer {
         Self::new()
     }
 }
+
+/// A request's entry in `in_flight`, removed when handling finishes or its
+/// future is dropped.
+struct InFlight {
+    server: Arc<McpServer>,
+    key: String,
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.server.in_flight().remove(&self.key);
+    }
+}
@@ -0,0 +1,52 @@
+//! Newline-delimited JSON-RPC over stdin/stdout. Each request is handled as
+//! its own task, so responses are written as they complete and may arrive out
+//! of order; clients match them up by `id`. Log notifications are written as
+//! they are emitted.
+
+use std::sync::Arc;
+use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+
+use crate::logging::Notifier;
+use crate::server::McpServer;
+
+/// Serve `server` on stdin/stdout until stdin closes and every pending
+/// request has been answered.
+pub async fn serve(server: McpServer) -> io::Result<()> {
+    let server = Arc::new(server);
+    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+
+    let writer = tokio::spawn(async move {
+        let mut stdout = io::stdout();
+        while let Some(response) = rx.recv().await {
+            stdout.write_all(response.as_bytes()).await?;
+            stdout.write_all(b"\n").await?;
+            stdout.flush().await?;
+        }
+        Ok::<_, io::Error>(())
+    });
+
+    let mut lines = BufReader::new(io::stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let server = server.clone();
+        let tx = tx.clone();
+        let notify_tx = tx.clone();
+        let notify: Notifier = Arc::new(move |notification| {
+            let _ = notify_tx.send(notification);
+        });
+        // Registered before the next line is read, so a cancellation that
+        // follows right away finds the request.
+        let pending = server.handle_request_notifying(line, notify);
+        tokio::spawn(async move {
+            if let Some(response) = pending.await {
+                let _ = tx.send(response);
+            }
+        });
+    }
+
+    drop(tx);
+    writer.await?
+}
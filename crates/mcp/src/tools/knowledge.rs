@@ -1,75 +1,378 @@
 use serde::Deserialize;
 use serde_json::{json, Value};
+use std::path::Path;
+use vault_tree_core::{create_note, note_file_name};
 
-use super::ToolDefinition;
-use crate::knowledge::{KnowledgeRegistry, LookupOptions};
-
-pub fn definitions() -> Vec<ToolDefinition> {
-    vec![ToolDefinition {
-        name: "knowledge_lookup".to_string(),
-        description: "Look up information from external knowledge sources (Wikipedia, DBpedia, arXiv, OpenLibrary, etc.)".to_string(),
-        input_schema: json!({
-            "type": "object",
-            "properties": {
-                "query": {
-                    "type": "string",
-                    "description": "Search query"
+use super::{ToolAnnotations, ToolDefinition};
+use crate::knowledge::{KnowledgeRegistry, LookupOptions, LookupResult};
+use crate::logging::{log, LogLevel};
+
+/// Tool definitions; the `provider` enum lists `providers` after `auto`.
+pub fn definitions(providers: &[&str]) -> Vec<ToolDefinition> {
+    let providers: Vec<&str> = std::iter::once("auto")
+        .chain(providers.iter().copied())
+        .collect();
+
+    vec![
+        ToolDefinition {
+            name: "knowledge_lookup".to_string(),
+            description: "Look up information from external knowledge sources (Wikipedia, DBpedia, arXiv, OpenLibrary, etc.)".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Search query"
+                    },
+                    "provider": {
+                        "type": "string",
+                        "description": "Knowledge provider (default auto, which tries providers in order)",
+                        "enum": providers
+                    },
+                    "max_results": {
+                        "type": "integer",
+                        "description": "Maximum number of results (default 5)"
+                    },
+                    "language": {
+                        "type": "string",
+                        "description": "Language code for Wikipedia (default 'en')"
+                    }
                 },
-                "provider": {
-                    "type": "string",
-                    "description": "Knowledge provider (auto tries providers in order)",
-                    "enum": ["auto", "wikipedia", "dbpedia", "wikidata", "github", "sourceforge", "npm", "crates.io", "stackoverflow", "reddit", "openlibrary", "arxiv", "musicbrainz", "wikiart", "defillama", "shodan"]
+                "required": ["query"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "provider": { "type": "string" },
+                    "requested_provider": { "type": "string" },
+                    "entries": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "title": { "type": "string" },
+                                "summary": { "type": "string" },
+                                "url": { "type": "string" },
+                                "source": { "type": "string" },
+                                "metadata": { "type": "object" }
+                            },
+                            "required": ["title", "summary", "source"]
+                        }
+                    }
                 },
-                "max_results": {
-                    "type": "integer",
-                    "description": "Maximum number of results (default 5)"
+                "required": ["provider", "entries"]
+            }),
+            annotations: ToolAnnotations::LOOKUP,
+        },
+        ToolDefinition {
+            name: "knowledge_multi".to_string(),
+            description: "Query several knowledge providers at once and merge their results, ranked by provider priority with duplicates removed".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Search query"
+                    },
+                    "providers": {
+                        "type": "array",
+                        "items": { "type": "string", "enum": &providers[1..] },
+                        "description": "Providers to query (default every available provider)"
+                    },
+                    "max_results": {
+                        "type": "integer",
+                        "description": "Maximum number of results per provider (default 5)"
+                    },
+                    "language": {
+                        "type": "string",
+                        "description": "Language code for Wikipedia (default 'en')"
+                    }
                 },
-                "language": {
-                    "type": "string",
-                    "description": "Language code for Wikipedia (default 'en')"
-                }
+                "required": ["query"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "providers": { "type": "array", "items": { "type": "string" } },
+                    "entries": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "title": { "type": "string" },
+                                "summary": { "type": "string" },
+                                "url": { "type": "string" },
+                                "source": { "type": "string" },
+                                "metadata": { "type": "object" }
+                            },
+                            "required": ["title", "summary", "source"]
+                        }
+                    },
+                    "errors": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" }
+                    }
+                },
+                "required": ["providers", "entries", "errors"]
+            }),
+            annotations: ToolAnnotations::LOOKUP,
+        },
+        ToolDefinition {
+            name: "knowledge_save_note".to_string(),
+            description: "Look something up and save the result as a new vault note, with the source, URL, and tags in its frontmatter".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "vault_path": {
+                        "type": "string",
+                        "description": "Path to the Obsidian vault directory"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Search query"
+                    },
+                    "provider": {
+                        "type": "string",
+                        "description": "Knowledge provider (default auto, which tries providers in order)",
+                        "enum": providers
+                    },
+                    "folder": {
+                        "type": "string",
+                        "description": "Vault-relative folder for the note (default the vault root)"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Note name (default the top result's title)"
+                    },
+                    "tags": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Tags for the note's frontmatter"
+                    },
+                    "max_results": {
+                        "type": "integer",
+                        "description": "Results to include in the note (default 1)"
+                    },
+                    "language": {
+                        "type": "string",
+                        "description": "Language code for Wikipedia (default 'en')"
+                    }
+                },
+                "required": ["vault_path", "query"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Vault-relative path of the new note"
+                    },
+                    "title": { "type": "string" },
+                    "provider": { "type": "string" },
+                    "url": { "type": ["string", "null"] },
+                    "entries": { "type": "integer" }
+                },
+                "required": ["path", "title", "provider", "entries"]
+            }),
+            annotations: ToolAnnotations {
+                // Never overwrites: an existing note with the name is an error.
+                read_only_hint: false,
+                destructive_hint: false,
+                idempotent_hint: false,
+                open_world_hint: true,
             },
-            "required": ["query", "provider"]
-        }),
-    }]
+        },
+        ToolDefinition {
+            name: "knowledge_providers".to_string(),
+            description: "List the knowledge providers with whether each is available, unavailable (e.g. missing API key), offline, or disabled, and its place in the auto lookup order".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "offline": { "type": "boolean" },
+                    "providers": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": { "type": "string" },
+                                "state": {
+                                    "type": "string",
+                                    "enum": ["available", "unavailable", "offline", "disabled"]
+                                },
+                                "auto_rank": {
+                                    "type": ["integer", "null"],
+                                    "description": "Position in the auto lookup order, or null when auto skips the provider"
+                                }
+                            },
+                            "required": ["name", "state", "auto_rank"]
+                        }
+                    }
+                },
+                "required": ["offline", "providers"]
+            }),
+            // Some availability checks make a request.
+            annotations: ToolAnnotations::LOOKUP,
+        },
+        ToolDefinition {
+            name: "knowledge_cache_stats".to_string(),
+            description: "Show the knowledge lookup cache's size, hit and miss counts, and the enabled providers".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "enabled": { "type": "boolean" },
+                    "cache": {
+                        "type": "object",
+                        "properties": {
+                            "entries": { "type": "integer" },
+                            "max_entries": { "type": "integer" },
+                            "ttl_minutes": { "type": "integer" },
+                            "hits": { "type": "integer" },
+                            "misses": { "type": "integer" }
+                        },
+                        "required": ["entries", "max_entries", "ttl_minutes", "hits", "misses"]
+                    },
+                    "providers": { "type": "array", "items": { "type": "string" } },
+                    "disk_cache": {
+                        "type": ["string", "null"],
+                        "description": "Directory of the on-disk cache, or null when disabled"
+                    }
+                },
+                "required": ["enabled", "cache", "providers"]
+            }),
+            annotations: ToolAnnotations::READ_ONLY,
+        },
+    ]
 }
 
 #[derive(Debug, Deserialize)]
 struct KnowledgeLookupArgs {
     query: String,
+    #[serde(default = "default_provider")]
     provider: String,
     max_results: Option<usize>,
     language: Option<String>,
 }
 
-pub fn call(name: &str, arguments: Value) -> Result<Value, String> {
+#[derive(Debug, Deserialize)]
+struct KnowledgeMultiArgs {
+    query: String,
+    #[serde(default)]
+    providers: Vec<String>,
+    max_results: Option<usize>,
+    language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KnowledgeSaveNoteArgs {
+    vault_path: String,
+    query: String,
+    #[serde(default = "default_provider")]
+    provider: String,
+    #[serde(default)]
+    folder: String,
+    name: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    max_results: Option<usize>,
+    language: Option<String>,
+}
+
+fn default_provider() -> String {
+    "auto".to_string()
+}
+
+/// `provider`'s results for the query, or an error if it is unknown,
+/// disabled, or the lookup failed.
+fn run_lookup(
+    registry: &KnowledgeRegistry,
+    tool: &str,
+    provider: &str,
+    query: &str,
+    options: &LookupOptions,
+) -> Result<LookupResult, String> {
+    let result = if provider == "auto" {
+        registry.auto_lookup(query, options)
+    } else {
+        registry
+            .lookup(provider, query, options)
+            .ok_or_else(|| format!("unknown or disabled provider: {}", provider))?
+    };
+
+    if !result.success {
+        let error = result.error.unwrap_or_else(|| "lookup failed".to_string());
+        log(
+            LogLevel::Warning,
+            tool,
+            format!("{} lookup failed: {}", provider, error),
+        );
+        return Err(error);
+    }
+    Ok(result)
+}
+
+/// A note for the lookup's entries: frontmatter from the top entry, then
+/// each entry's summary and link.
+fn render_note(query: &str, result: &LookupResult, tags: &[String]) -> String {
+    // JSON strings are valid YAML scalars, so titles with colons or quotes survive.
+    let yaml = |value: &str| serde_json::to_string(value).unwrap_or_default();
+    let top = &result.entries[0];
+
+    let mut note = String::from("---\n");
+    note.push_str(&format!("title: {}\n", yaml(&top.title)));
+    note.push_str(&format!("source: {}\n", yaml(&result.provider)));
+    if let Some(url) = &top.url {
+        note.push_str(&format!("url: {}\n", yaml(url)));
+    }
+    note.push_str(&format!("query: {}\n", yaml(query)));
+    let tags: Vec<String> = tags.iter().map(|tag| yaml(tag)).collect();
+    note.push_str(&format!("tags: [{}]\n", tags.join(", ")));
+    note.push_str("---\n\n");
+
+    for (i, entry) in result.entries.iter().enumerate() {
+        let heading = if i == 0 { "#" } else { "##" };
+        note.push_str(&format!("{} {}\n\n", heading, entry.title));
+        if !entry.summary.is_empty() {
+            note.push_str(&entry.summary);
+            note.push_str("\n\n");
+        }
+        match &entry.url {
+            Some(url) => note.push_str(&format!("Source: [{}]({})\n\n", entry.source, url)),
+            None => note.push_str(&format!("Source: {}\n\n", entry.source)),
+        }
+    }
+
+    note.truncate(note.trim_end().len());
+    note.push('\n');
+    note
+}
+
+pub fn call(name: &str, arguments: Value, registry: &KnowledgeRegistry) -> Result<Value, String> {
     match name {
         "knowledge_lookup" => {
             let args: KnowledgeLookupArgs = serde_json::from_value(arguments)
                 .map_err(|e| format!("invalid arguments: {}", e))?;
 
-            let registry = KnowledgeRegistry::new();
             let options = LookupOptions {
                 max_results: args.max_results,
                 language: args.language,
             };
 
-            let result = if args.provider == "auto" {
-                registry.auto_lookup(&args.query, &options)
-            } else {
-                registry
-                    .lookup(&args.provider, &args.query, &options)
-                    .ok_or_else(|| format!("unknown provider: {}", args.provider))?
-            };
-
-            if !result.success {
-                return Err(result.error.unwrap_or_else(|| "lookup failed".to_string()));
-            }
+            let result = run_lookup(registry, name, &args.provider, &args.query, &options)?;
 
+            // With auto, the provider that answered rather than "auto".
+            let provider = &result.provider;
             let mut output = format!(
                 "Found {} results from {}:\n\n",
                 result.entries.len(),
-                args.provider
+                provider
             );
 
             for entry in &result.entries {
@@ -86,12 +389,143 @@ pub fn call(name: &str, arguments: Value) -> Result<Value, String> {
                     "type": "text",
                     "text": output
                 }],
+                "structuredContent": {
+                    "provider": provider,
+                    "requested_provider": args.provider,
+                    "entries": result.entries
+                },
                 "metadata": {
-                    "provider": args.provider,
+                    "provider": provider,
+                    "requested_provider": args.provider,
                     "results_count": result.entries.len()
                 }
             }))
         }
+        "knowledge_multi" => {
+            let args: KnowledgeMultiArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let options = LookupOptions {
+                max_results: args.max_results,
+                language: args.language,
+            };
+            let result = registry.multi_lookup(&args.providers, &args.query, &options)?;
+
+            let mut output = format!(
+                "Found {} results from {}:\n\n",
+                result.entries.len(),
+                result.providers.join(", ")
+            );
+            for entry in &result.entries {
+                output.push_str(&format!("## {} ({})\n", entry.title, entry.source));
+                output.push_str(&entry.summary);
+                if let Some(url) = &entry.url {
+                    output.push_str(&format!("\nURL: {}", url));
+                }
+                output.push_str("\n\n");
+            }
+            let mut failed: Vec<_> = result.errors.iter().collect();
+            failed.sort();
+            for (provider, error) in failed {
+                output.push_str(&format!("{} failed: {}\n", provider, error));
+            }
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": result
+            }))
+        }
+        "knowledge_save_note" => {
+            let args: KnowledgeSaveNoteArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let options = LookupOptions {
+                max_results: Some(args.max_results.unwrap_or(1)),
+                language: args.language,
+            };
+            let mut result = run_lookup(registry, name, &args.provider, &args.query, &options)?;
+            result.entries.truncate(options.max_results.unwrap_or(1));
+            let Some(top) = result.entries.first() else {
+                return Err(format!("no results for: {}", args.query));
+            };
+
+            let file_name = note_file_name(args.name.as_deref().unwrap_or(&top.title));
+            if file_name.is_empty() {
+                return Err("note name is empty once cleaned".to_string());
+            }
+            let folder = args.folder.trim_matches('/');
+            let relative = if folder.is_empty() {
+                file_name
+            } else {
+                format!("{}/{}", folder, file_name)
+            };
+
+            let note = render_note(&args.query, &result, &args.tags);
+            let path = create_note(Path::new(&args.vault_path), &relative, &note)
+                .map_err(|e| format!("failed to save note: {}", e))?;
+
+            let top = &result.entries[0];
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": format!("Saved {} from {}", path, result.provider)
+                }],
+                "structuredContent": {
+                    "path": path,
+                    "title": top.title,
+                    "provider": result.provider,
+                    "url": top.url,
+                    "entries": result.entries.len()
+                }
+            }))
+        }
+        "knowledge_providers" => {
+            let statuses = registry.provider_statuses();
+
+            let mut output = String::new();
+            if registry.is_offline() {
+                output.push_str("Offline mode: lookups are answered from the cache only.\n\n");
+            }
+            for status in &statuses {
+                output.push_str(&format!("- {}: {}", status.name, status.state.as_str()));
+                if let Some(rank) = status.auto_rank {
+                    output.push_str(&format!(" (auto #{})", rank));
+                }
+                output.push('\n');
+            }
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": {
+                    "offline": registry.is_offline(),
+                    "providers": statuses
+                }
+            }))
+        }
+        "knowledge_cache_stats" => {
+            let stats = json!({
+                "enabled": registry.cache_enabled(),
+                "cache": registry.cache_stats(),
+                "providers": registry.provider_names(),
+                "disk_cache": registry.disk_cache_dir(),
+            });
+            let output = serde_json::to_string_pretty(&stats)
+                .map_err(|e| format!("failed to serialize stats: {}", e))?;
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": stats
+            }))
+        }
         _ => Err(format!("unknown knowledge tool: {}", name)),
     }
 }
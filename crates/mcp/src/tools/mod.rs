@@ -1,8 +1,15 @@
 mod knowledge;
+mod session;
 mod vault;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use vault_tree_core::MetadataCache;
+
+use crate::config::Config;
+use crate::knowledge::KnowledgeRegistry;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolDefinition {
@@ -10,19 +17,90 @@ pub struct ToolDefinition {
     pub description: String,
     #[serde(rename = "inputSchema")]
     pub input_schema: Value,
+    /// Schema of the result's `structuredContent`.
+    #[serde(rename = "outputSchema")]
+    pub output_schema: Value,
+    pub annotations: ToolAnnotations,
+}
+
+/// Hints about a tool's side effects, for clients deciding what needs
+/// confirmation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    pub read_only_hint: bool,
+    /// Whether the tool may overwrite or delete existing data.
+    pub destructive_hint: bool,
+    /// Whether repeating a call with the same arguments has no further effect.
+    pub idempotent_hint: bool,
+    /// Whether the tool reaches outside the local machine.
+    pub open_world_hint: bool,
+}
+
+impl ToolAnnotations {
+    /// Only reads local files.
+    pub const READ_ONLY: Self = Self {
+        read_only_hint: true,
+        destructive_hint: false,
+        idempotent_hint: true,
+        open_world_hint: false,
+    };
+
+    /// Only reads, but from external services.
+    pub const LOOKUP: Self = Self {
+        open_world_hint: true,
+        ..Self::READ_ONLY
+    };
+}
+
+/// Server and session state available to tool calls.
+pub struct ToolContext<'a> {
+    pub config: &'a Config,
+    /// Vault set with `vault_set_default`.
+    pub default_vault: &'a Mutex<Option<PathBuf>>,
+    pub(crate) knowledge: &'a OnceLock<KnowledgeRegistry>,
+    pub(crate) metadata: &'a MetadataCache,
+}
+
+impl ToolContext<'_> {
+    /// The server's knowledge registry, built on first use. The blocking
+    /// HTTP clients it holds can't be created on an async runtime thread.
+    pub fn knowledge(&self) -> &KnowledgeRegistry {
+        self.knowledge
+            .get_or_init(|| KnowledgeRegistry::from_config(&self.config.knowledge))
+    }
 }
 
-pub fn list_tools() -> Vec<ToolDefinition> {
+pub fn list_tools(context: &ToolContext) -> Vec<ToolDefinition> {
     let mut tools = Vec::new();
     tools.extend(vault::definitions());
-    tools.extend(knowledge::definitions());
+    tools.extend(knowledge::definitions(
+        &KnowledgeRegistry::configured_providers(&context.config.knowledge),
+    ));
+    tools.extend(session::definitions());
     tools
 }
 
-pub fn call_tool(name: &str, arguments: Value) -> Result<Value, String> {
+pub fn call_tool(name: &str, arguments: Value, context: &ToolContext) -> Result<Value, String> {
     match name {
-        "vault_tree" | "vault_search" => vault::call(name, arguments),
-        "knowledge_lookup" => knowledge::call(name, arguments),
+        "vault_tree"
+        | "vault_search"
+        | "vault_query"
+        | "vault_index_search"
+        | "vault_export_sqlite"
+        | "vault_export_html"
+        | "vault_broken_links"
+        | "vault_ambiguous_links"
+        | "vault_backlinks"
+        | "vault_duplicates"
+        | "vault_stats"
+        | "vault_rename" => vault::call(name, arguments, context.metadata),
+        "knowledge_lookup"
+        | "knowledge_multi"
+        | "knowledge_save_note"
+        | "knowledge_providers"
+        | "knowledge_cache_stats" => knowledge::call(name, arguments, context.knowledge()),
+        "vault_set_default" => session::set_default_vault(arguments, context.default_vault),
         _ => Err(format!("unknown tool: {}", name)),
     }
 }
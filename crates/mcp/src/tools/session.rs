@@ -0,0 +1,75 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use super::{ToolAnnotations, ToolDefinition};
+
+pub fn definitions() -> Vec<ToolDefinition> {
+    vec![ToolDefinition {
+        name: "vault_set_default".to_string(),
+        description: "Set the vault used by later calls in this session that omit vault_path"
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the Obsidian vault directory (omit to clear the default)"
+                }
+            }
+        }),
+        output_schema: json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": ["string", "null"],
+                    "description": "The new default, or null if it was cleared"
+                }
+            },
+            "required": ["path"]
+        }),
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: false,
+            idempotent_hint: true,
+            open_world_hint: false,
+        },
+    }]
+}
+
+#[derive(Debug, Deserialize)]
+struct SetDefaultArgs {
+    path: Option<PathBuf>,
+}
+
+/// Set or clear the session's default vault, which takes precedence over
+/// the config file's.
+pub fn set_default_vault(
+    arguments: Value,
+    vault: &Mutex<Option<PathBuf>>,
+) -> Result<Value, String> {
+    let args: SetDefaultArgs =
+        serde_json::from_value(arguments).map_err(|e| format!("invalid arguments: {}", e))?;
+    if let Some(path) = &args.path {
+        if !path.is_dir() {
+            return Err(format!("not a directory: {}", path.display()));
+        }
+    }
+
+    let path = args.path.as_ref().map(|p| p.display().to_string());
+    *vault.lock().unwrap_or_else(|e| e.into_inner()) = args.path;
+
+    let output = match &path {
+        Some(path) => format!("Default vault set to {}", path),
+        None => "Default vault cleared".to_string(),
+    };
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": output
+        }],
+        "structuredContent": { "path": path }
+    }))
+}
@@ -1,9 +1,19 @@
 use serde::Deserialize;
 use serde_json::{json, Value};
-use std::path::Path;
-use vault_tree_core::{generate_tree, render_tree, search_vault, SearchOptions, TreeOptions};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use vault_tree_core::{
+    backlinks_for, export_html, export_sqlite, find_ambiguous_links, find_broken_links,
+    find_duplicate_notes, generate_timeline_with_cache, generate_tree_with_cache, hash_content,
+    query_vault, rename_note, render_tree_as_streaming, search_vault_with_cache, vault_stats,
+    LinkNormalization, MetadataCache, SearchMatch, SearchOptions, SearchResult, TreeFormat,
+    TreeOptions, VaultIndex,
+};
 
-use super::ToolDefinition;
+use super::{ToolAnnotations, ToolDefinition};
+use crate::logging::{log, LogLevel};
 
 pub fn definitions() -> Vec<ToolDefinition> {
     vec![
@@ -20,10 +30,69 @@ pub fn definitions() -> Vec<ToolDefinition> {
                     "depth": {
                         "type": "integer",
                         "description": "Maximum depth to traverse (optional, default unlimited)"
+                    },
+                    "mode": {
+                        "type": "string",
+                        "enum": ["tree", "timeline"],
+                        "description": "Folder tree, or notes grouped by year and month from frontmatter dates and daily-note file names; both honour exclude, filters, and depth (default tree)"
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["ascii", "json", "yaml", "markdown"],
+                        "description": "Output format (default ascii); markdown is a bullet list with a [[wikilink]] to each note, suitable for an index note"
+                    },
+                    "exclude": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Glob patterns to skip, in addition to the vault's .vaultignore (optional)"
+                    },
+                    "include_attachments": {
+                        "type": "boolean",
+                        "description": "Show images, PDFs, and other non-markdown files with sizes (default false)"
+                    },
+                    "filter_tags": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Only include notes with any of these tags; nested tags match their parent (optional)"
+                    },
+                    "modified_after": {
+                        "type": "string",
+                        "description": "Only include notes modified on or after this date, YYYY-MM-DD (optional)"
+                    },
+                    "modified_before": {
+                        "type": "string",
+                        "description": "Only include notes modified before this date, YYYY-MM-DD (optional)"
+                    },
+                    "min_incoming_links": {
+                        "type": "integer",
+                        "description": "Only include notes with at least this many backlinks (optional)"
+                    },
+                    "case_sensitive_links": {
+                        "type": "boolean",
+                        "description": "Match link targets to note names case-sensitively when counting links (default false, like Obsidian)"
+                    },
+                    "max_output_bytes": {
+                        "type": "integer",
+                        "description": "Stop rendering after this many bytes and append a truncation notice (optional, default unlimited)"
                     }
                 },
                 "required": ["vault_path"]
             }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "output": {
+                        "type": "string",
+                        "description": "The tree rendered in the requested format"
+                    },
+                    "truncated": {
+                        "type": "boolean",
+                        "description": "Whether max_output_bytes cut the output short"
+                    }
+                },
+                "required": ["output", "truncated"]
+            }),
+            annotations: ToolAnnotations::READ_ONLY,
         },
         ToolDefinition {
             name: "vault_search".to_string(),
@@ -47,21 +116,582 @@ pub fn definitions() -> Vec<ToolDefinition> {
                         "type": "boolean",
                         "description": "Whether to perform case-insensitive search (default false)"
                     },
+                    "whole_word": {
+                        "type": "boolean",
+                        "description": "Only match whole words (default false)"
+                    },
+                    "literal": {
+                        "type": "boolean",
+                        "description": "Treat the pattern as plain text instead of a regex (default false)"
+                    },
                     "max_results": {
                         "type": "integer",
-                        "description": "Maximum number of matches to return (optional)"
+                        "description": "Maximum number of matches to return; more are paged with cursor (optional)"
+                    },
+                    "offset": {
+                        "type": "integer",
+                        "description": "Number of matches to skip (default 0)"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor from the previous page; overrides offset (optional)"
+                    },
+                    "context_before": {
+                        "type": "integer",
+                        "description": "Lines of context to show before each match, like grep -B (default 0)"
+                    },
+                    "context_after": {
+                        "type": "integer",
+                        "description": "Lines of context to show after each match, like grep -A (default 0)"
+                    },
+                    "exclude": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Glob patterns to skip, in addition to the vault's .vaultignore (optional)"
                     }
                 },
                 "required": ["vault_path", "pattern"]
             }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "results": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "file_path": { "type": "string" },
+                                "matches": {
+                                    "type": "array",
+                                    "items": {
+                                        "type": "object",
+                                        "properties": {
+                                            "line_number": { "type": "integer" },
+                                            "line_content": { "type": "string" },
+                                            "match_start": { "type": "integer" },
+                                            "match_end": { "type": "integer" },
+                                            "context_before": { "type": "array", "items": { "type": "string" } },
+                                            "context_after": { "type": "array", "items": { "type": "string" } }
+                                        },
+                                        "required": ["line_number", "line_content", "match_start", "match_end"]
+                                    }
+                                }
+                            },
+                            "required": ["file_path", "matches"]
+                        }
+                    },
+                    "next_cursor": {
+                        "type": "string",
+                        "description": "Pass as cursor to get the next page, when more matches remain"
+                    }
+                },
+                "required": ["results"]
+            }),
+            annotations: ToolAnnotations::READ_ONLY,
+        },
+        ToolDefinition {
+            name: "vault_query".to_string(),
+            description: "List notes matching a Dataview-style filter over tags, links, dates, word counts, and frontmatter properties".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "vault_path": {
+                        "type": "string",
+                        "description": "Path to the Obsidian vault directory"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Filter such as 'tag:#rust AND links-to:note2 AND modified:>2024-01-01'. Fields: tag, links-to, linked-from, modified, date, words, title, path, or any frontmatter property; combine with AND, OR, NOT, -term, and parentheses"
+                    }
+                },
+                "required": ["vault_path", "query"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "notes": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "path": { "type": "string" },
+                                "title": { "type": ["string", "null"] },
+                                "tags": { "type": "array", "items": { "type": "string" } },
+                                "date": { "type": ["string", "null"] },
+                                "modified": {
+                                    "type": ["integer", "null"],
+                                    "description": "Seconds since the Unix epoch"
+                                }
+                            },
+                            "required": ["path", "tags"]
+                        }
+                    }
+                },
+                "required": ["notes"]
+            }),
+            annotations: ToolAnnotations::READ_ONLY,
+        },
+        ToolDefinition {
+            name: "vault_index_search".to_string(),
+            description: "Ranked full-text search over note titles, tags, and bodies using a persistent index that is updated incrementally before each query".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "vault_path": {
+                        "type": "string",
+                        "description": "Path to the Obsidian vault directory"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Search terms; supports AND/OR, \"phrases\", and field prefixes like title: and tags:"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of notes to return (default 20)"
+                    },
+                    "index_dir": {
+                        "type": "string",
+                        "description": "Where to keep the index (optional, defaults to a per-vault directory under the system temp dir)"
+                    }
+                },
+                "required": ["vault_path", "query"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "hits": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "path": { "type": "string" },
+                                "title": { "type": ["string", "null"] },
+                                "tags": { "type": "array", "items": { "type": "string" } },
+                                "score": { "type": "number" },
+                                "snippet": { "type": "string" }
+                            },
+                            "required": ["path", "tags", "score", "snippet"]
+                        }
+                    }
+                },
+                "required": ["hits"]
+            }),
+            annotations: ToolAnnotations {
+                // Writes only its own index, which it can rebuild.
+                read_only_hint: false,
+                destructive_hint: false,
+                idempotent_hint: true,
+                open_world_hint: false,
+            },
+        },
+        ToolDefinition {
+            name: "vault_export_sqlite".to_string(),
+            description: "Export vault notes, frontmatter properties, tags, links, and word counts to a SQLite database".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "vault_path": {
+                        "type": "string",
+                        "description": "Path to the Obsidian vault directory"
+                    },
+                    "output_path": {
+                        "type": "string",
+                        "description": "Path of the SQLite database to write (an existing database is replaced)"
+                    },
+                    "overwrite": {
+                        "type": "boolean",
+                        "description": "Replace output_path even if it is not a SQLite database (default false)"
+                    }
+                },
+                "required": ["vault_path", "output_path"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "notes": { "type": "integer" },
+                    "tags": { "type": "integer" },
+                    "properties": { "type": "integer" },
+                    "links": { "type": "integer" },
+                    "output_path": { "type": "string" }
+                },
+                "required": ["notes", "tags", "properties", "links", "output_path"]
+            }),
+            annotations: ToolAnnotations {
+                read_only_hint: false,
+                destructive_hint: true,
+                idempotent_hint: true,
+                open_world_hint: false,
+            },
+        },
+        ToolDefinition {
+            name: "vault_export_html".to_string(),
+            description: "Publish an Obsidian vault as a static HTML site with resolved links, a folder sidebar, and backlinks on each page".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "vault_path": {
+                        "type": "string",
+                        "description": "Path to the Obsidian vault directory"
+                    },
+                    "output_dir": {
+                        "type": "string",
+                        "description": "Directory to write the site into (created if missing; existing pages are overwritten)"
+                    }
+                },
+                "required": ["vault_path", "output_dir"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "pages": { "type": "integer" },
+                    "links": { "type": "integer" },
+                    "unresolved_links": { "type": "integer" },
+                    "output_dir": { "type": "string" }
+                },
+                "required": ["pages", "links", "unresolved_links", "output_dir"]
+            }),
+            annotations: ToolAnnotations {
+                read_only_hint: false,
+                destructive_hint: true,
+                idempotent_hint: true,
+                open_world_hint: false,
+            },
+        },
+        ToolDefinition {
+            name: "vault_broken_links".to_string(),
+            description: "List wikilinks whose target note doesn't exist in an Obsidian vault, with source file and line number".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "vault_path": {
+                        "type": "string",
+                        "description": "Path to the Obsidian vault directory"
+                    }
+                },
+                "required": ["vault_path"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "links": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "source": { "type": "string" },
+                                "target": { "type": "string" },
+                                "line_number": { "type": "integer" },
+                                "line_content": { "type": "string" }
+                            },
+                            "required": ["source", "target", "line_number", "line_content"]
+                        }
+                    }
+                },
+                "required": ["links"]
+            }),
+            annotations: ToolAnnotations::READ_ONLY,
+        },
+        ToolDefinition {
+            name: "vault_ambiguous_links".to_string(),
+            description: "List links that match more than one note (e.g. [[todo]] with work/todo.md and home/todo.md), with every candidate and the note the link resolves to".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "vault_path": {
+                        "type": "string",
+                        "description": "Path to the Obsidian vault directory"
+                    }
+                },
+                "required": ["vault_path"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "links": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "source": { "type": "string" },
+                                "target": { "type": "string" },
+                                "line_number": { "type": "integer" },
+                                "line_content": { "type": "string" },
+                                "candidates": { "type": "array", "items": { "type": "string" } },
+                                "resolved": { "type": "string" }
+                            },
+                            "required": ["source", "target", "line_number", "line_content", "candidates", "resolved"]
+                        }
+                    }
+                },
+                "required": ["links"]
+            }),
+            annotations: ToolAnnotations::READ_ONLY,
+        },
+        ToolDefinition {
+            name: "vault_backlinks".to_string(),
+            description: "List all notes linking to a given note, with the line containing each link".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "vault_path": {
+                        "type": "string",
+                        "description": "Path to the Obsidian vault directory"
+                    },
+                    "note": {
+                        "type": "string",
+                        "description": "Note name (as used in a wikilink) or vault-relative path"
+                    }
+                },
+                "required": ["vault_path", "note"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "note": { "type": "string" },
+                    "backlinks": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "source": { "type": "string" },
+                                "line_number": { "type": "integer" },
+                                "line_content": { "type": "string" }
+                            },
+                            "required": ["source", "line_number", "line_content"]
+                        }
+                    }
+                },
+                "required": ["note", "backlinks"]
+            }),
+            annotations: ToolAnnotations::READ_ONLY,
+        },
+        ToolDefinition {
+            name: "vault_stats".to_string(),
+            description: "Summarize an Obsidian vault as JSON: note, word, tag, and link totals, orphans, average note length, largest notes, and busiest directories".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "vault_path": {
+                        "type": "string",
+                        "description": "Path to the Obsidian vault directory"
+                    }
+                },
+                "required": ["vault_path"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "notes": { "type": "integer" },
+                    "words": { "type": "integer" },
+                    "tags": { "type": "integer" },
+                    "links": { "type": "integer" },
+                    "unresolved_links": { "type": "integer" },
+                    "orphans": { "type": "integer" },
+                    "average_words": { "type": "number" },
+                    "largest_notes": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "path": { "type": "string" },
+                                "words": { "type": "integer" }
+                            },
+                            "required": ["path", "words"]
+                        }
+                    },
+                    "busiest_directories": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "path": { "type": "string" },
+                                "notes": { "type": "integer" }
+                            },
+                            "required": ["path", "notes"]
+                        }
+                    }
+                },
+                "required": ["notes", "words", "tags", "links", "unresolved_links", "orphans", "average_words", "largest_notes", "busiest_directories"]
+            }),
+            annotations: ToolAnnotations::READ_ONLY,
+        },
+        ToolDefinition {
+            name: "vault_duplicates".to_string(),
+            description: "Find notes in an Obsidian vault with identical or near-identical bodies, ignoring frontmatter".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "vault_path": {
+                        "type": "string",
+                        "description": "Path to the Obsidian vault directory"
+                    }
+                },
+                "required": ["vault_path"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "identical": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "hash": { "type": "string" },
+                                "paths": { "type": "array", "items": { "type": "string" } }
+                            },
+                            "required": ["hash", "paths"]
+                        }
+                    },
+                    "similar": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "first": { "type": "string" },
+                                "second": { "type": "string" },
+                                "similarity": { "type": "number" }
+                            },
+                            "required": ["first", "second", "similarity"]
+                        }
+                    }
+                },
+                "required": ["identical", "similar"]
+            }),
+            annotations: ToolAnnotations::READ_ONLY,
+        },
+        ToolDefinition {
+            name: "vault_rename".to_string(),
+            description: "Rename or move a note and rewrite all wikilinks and markdown links pointing at it".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "vault_path": {
+                        "type": "string",
+                        "description": "Path to the Obsidian vault directory"
+                    },
+                    "old": {
+                        "type": "string",
+                        "description": "Note to rename: link name or vault-relative path"
+                    },
+                    "new": {
+                        "type": "string",
+                        "description": "New vault-relative path (.md optional); directories are created as needed"
+                    }
+                },
+                "required": ["vault_path", "old", "new"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "old_path": { "type": "string" },
+                    "new_path": { "type": "string" },
+                    "files_changed": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "path": { "type": "string" },
+                                "links_rewritten": { "type": "integer" }
+                            },
+                            "required": ["path", "links_rewritten"]
+                        }
+                    },
+                    "links_rewritten": { "type": "integer" }
+                },
+                "required": ["old_path", "new_path", "files_changed", "links_rewritten"]
+            }),
+            annotations: ToolAnnotations {
+                read_only_hint: false,
+                destructive_hint: true,
+                idempotent_hint: false,
+                open_world_hint: false,
+            },
         },
     ]
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TreeMode {
+    #[default]
+    Tree,
+    Timeline,
+}
+
 #[derive(Debug, Deserialize)]
 struct VaultTreeArgs {
     vault_path: String,
     depth: Option<usize>,
+    #[serde(default)]
+    mode: TreeMode,
+    #[serde(default)]
+    format: TreeFormat,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    include_attachments: bool,
+    #[serde(default)]
+    filter_tags: Vec<String>,
+    modified_after: Option<String>,
+    modified_before: Option<String>,
+    min_incoming_links: Option<usize>,
+    #[serde(default)]
+    case_sensitive_links: bool,
+    max_output_bytes: Option<usize>,
+}
+
+/// Collects rendered output up to `limit` bytes, then refuses further writes
+/// so the renderer stops early instead of walking the rest of the tree.
+struct CappedWriter {
+    buf: Vec<u8>,
+    limit: usize,
+    truncated: bool,
+}
+
+impl CappedWriter {
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            buf: Vec::new(),
+            limit: limit.unwrap_or(usize::MAX),
+            truncated: false,
+        }
+    }
+
+    /// The collected text, cut back to the last whole line if truncated.
+    fn finish(mut self) -> String {
+        if self.truncated {
+            let end = self
+                .buf
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(0, |i| i + 1);
+            self.buf.truncate(end);
+            self.buf.extend_from_slice(
+                format!("... output truncated at {} bytes\n", self.limit).as_bytes(),
+            );
+        }
+        String::from_utf8_lossy(&self.buf).into_owned()
+    }
+}
+
+impl Write for CappedWriter {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let room = self.limit - self.buf.len();
+        if data.len() > room {
+            self.buf.extend_from_slice(&data[..room]);
+            self.truncated = true;
+            return Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "output limit reached",
+            ));
+        }
+        self.buf.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -71,63 +701,639 @@ struct VaultSearchArgs {
     file_pattern: Option<String>,
     #[serde(default)]
     case_insensitive: bool,
+    #[serde(default)]
+    whole_word: bool,
+    #[serde(default)]
+    literal: bool,
     max_results: Option<usize>,
+    #[serde(default)]
+    offset: usize,
+    cursor: Option<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    context_before: usize,
+    #[serde(default)]
+    context_after: usize,
 }
 
-pub fn call(name: &str, arguments: Value) -> Result<Value, String> {
+/// Skip the first `offset` matches and keep up to `limit`, returning the
+/// offset of the next page if any matches remain.
+fn page_search_results(
+    results: Vec<SearchResult>,
+    offset: usize,
+    limit: Option<usize>,
+) -> (Vec<SearchResult>, Option<usize>) {
+    let mut to_skip = offset;
+    let mut room = limit.unwrap_or(usize::MAX);
+    let mut page = Vec::new();
+    for mut result in results {
+        let skip = to_skip.min(result.matches.len());
+        to_skip -= skip;
+        result.matches.drain(..skip);
+        if result.matches.len() > room {
+            result.matches.truncate(room);
+            let kept = limit.unwrap_or_default();
+            if !result.matches.is_empty() {
+                page.push(result);
+            }
+            return (page, Some(offset.saturating_add(kept)));
+        }
+        room -= result.matches.len();
+        if !result.matches.is_empty() {
+            page.push(result);
+        }
+    }
+    (page, None)
+}
+
+/// Matches as `line:col text`, with context lines as `line- text` in the style
+/// of `grep -C`: overlapping context is merged and gaps are marked with `--`.
+fn render_search_lines(matches: &[SearchMatch]) -> String {
+    let mut lines: BTreeMap<usize, String> = BTreeMap::new();
+    for m in matches {
+        let first = m.line_number - m.context_before.len();
+        let context = m.context_before.iter().zip(first..);
+        let after = m.context_after.iter().zip(m.line_number + 1..);
+        for (text, number) in context.chain(after) {
+            lines
+                .entry(number)
+                .or_insert_with(|| format!("  {}- {}\n", number, text));
+        }
+    }
+    for m in matches {
+        lines.insert(
+            m.line_number,
+            format!("  {}:{} {}\n", m.line_number, m.match_start, m.line_content),
+        );
+    }
+
+    let mut output = String::new();
+    let mut previous = None;
+    for (number, line) in lines {
+        if previous.is_some_and(|p| p + 1 < number) {
+            output.push_str("  --\n");
+        }
+        output.push_str(&line);
+        previous = Some(number);
+    }
+    output
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultQueryArgs {
+    vault_path: String,
+    query: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultIndexSearchArgs {
+    vault_path: String,
+    query: String,
+    limit: Option<usize>,
+    index_dir: Option<String>,
+}
+
+/// Default index location, unique per vault path.
+fn default_index_dir(vault_path: &Path) -> PathBuf {
+    let vault = vault_path
+        .canonicalize()
+        .unwrap_or_else(|_| vault_path.to_path_buf());
+    let key = hash_content(vault.to_string_lossy().as_bytes());
+    std::env::temp_dir()
+        .join("vault-tree-index")
+        .join(&key[..16])
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultExportSqliteArgs {
+    vault_path: String,
+    output_path: String,
+    #[serde(default)]
+    overwrite: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultExportHtmlArgs {
+    vault_path: String,
+    output_dir: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultPathArgs {
+    vault_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultBacklinksArgs {
+    vault_path: String,
+    note: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VaultRenameArgs {
+    vault_path: String,
+    old: String,
+    new: String,
+}
+
+/// `metadata` holds note scans, hashes, and text across calls, so repeated calls on
+/// the same vault only re-read notes that changed.
+pub fn call(name: &str, arguments: Value, metadata: &MetadataCache) -> Result<Value, String> {
     match name {
         "vault_tree" => {
             let args: VaultTreeArgs = serde_json::from_value(arguments)
                 .map_err(|e| format!("invalid arguments: {}", e))?;
 
-            let options = TreeOptions { depth: args.depth };
+            let options = TreeOptions {
+                depth: args.depth,
+                format: args.format,
+                exclude: args.exclude,
+                include_attachments: args.include_attachments,
+                filter_tags: args.filter_tags,
+                modified_after: args.modified_after,
+                modified_before: args.modified_before,
+                min_incoming_links: args.min_incoming_links,
+                link_normalization: LinkNormalization {
+                    case_insensitive: !args.case_sensitive_links,
+                    ..Default::default()
+                },
+            };
 
-            let tree = generate_tree(Path::new(&args.vault_path), &options)
-                .map_err(|e| format!("failed to generate tree: {}", e))?;
+            let vault_path = Path::new(&args.vault_path);
+            let started = Instant::now();
+            let tree = match args.mode {
+                TreeMode::Tree => generate_tree_with_cache(vault_path, &options, metadata),
+                TreeMode::Timeline => generate_timeline_with_cache(vault_path, &options, metadata),
+            }
+            .map_err(|e| format!("failed to generate tree: {}", e))?;
+            log(
+                LogLevel::Info,
+                name,
+                format!(
+                    "scanned {} notes in {} directories in {} ms",
+                    tree.total_notes,
+                    tree.total_dirs,
+                    started.elapsed().as_millis()
+                ),
+            );
 
-            let output = render_tree(&tree);
+            let mut writer = CappedWriter::new(args.max_output_bytes);
+            if let Err(e) = render_tree_as_streaming(&tree, options.format, &mut writer) {
+                if !writer.truncated {
+                    return Err(format!("failed to render tree: {}", e));
+                }
+            }
+            let truncated = writer.truncated;
+            let output = writer.finish();
 
             Ok(json!({
                 "content": [{
                     "type": "text",
                     "text": output
-                }]
+                }],
+                "structuredContent": {
+                    "output": output,
+                    "truncated": truncated
+                }
             }))
         }
         "vault_search" => {
             let args: VaultSearchArgs = serde_json::from_value(arguments)
                 .map_err(|e| format!("invalid arguments: {}", e))?;
 
+            let offset = match &args.cursor {
+                Some(cursor) => cursor
+                    .parse()
+                    .map_err(|_| format!("invalid cursor: {}", cursor))?,
+                None => args.offset,
+            };
             let options = SearchOptions {
                 file_pattern: args.file_pattern,
                 case_insensitive: args.case_insensitive,
-                max_results: args.max_results,
+                whole_word: args.whole_word,
+                literal: args.literal,
+                // One extra match shows whether there is another page.
+                max_results: args
+                    .max_results
+                    .map(|max| offset.saturating_add(max).saturating_add(1)),
+                exclude: args.exclude,
+                context_before: args.context_before,
+                context_after: args.context_after,
             };
 
-            let results = search_vault(Path::new(&args.vault_path), &args.pattern, &options)
-                .map_err(|e| format!("search failed: {}", e))?;
+            let started = Instant::now();
+            let results = search_vault_with_cache(
+                Path::new(&args.vault_path),
+                &args.pattern,
+                &options,
+                metadata,
+            )
+            .map_err(|e| format!("search failed: {}", e))?;
+            log(
+                LogLevel::Info,
+                name,
+                format!(
+                    "{} matches in {} notes in {} ms",
+                    results.iter().map(|r| r.matches.len()).sum::<usize>(),
+                    results.len(),
+                    started.elapsed().as_millis()
+                ),
+            );
+
+            let (results, next_offset) = page_search_results(results, offset, args.max_results);
+            let next_cursor = next_offset.map(|next| next.to_string());
 
             let mut output = String::new();
             for result in &results {
                 output.push_str(&format!("## {}\n", result.file_path));
-                for m in &result.matches {
-                    output.push_str(&format!(
-                        "  {}:{} {}\n",
-                        m.line_number, m.match_start, m.line_content
-                    ));
-                }
+                output.push_str(&render_search_lines(&result.matches));
                 output.push('\n');
             }
 
             if results.is_empty() {
                 output = "No matches found.".to_string();
             }
+            if let Some(cursor) = &next_cursor {
+                output.push_str(&format!(
+                    "More matches; pass cursor \"{}\" for the next page.\n",
+                    cursor
+                ));
+            }
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": {
+                    "results": results,
+                    "next_cursor": next_cursor
+                }
+            }))
+        }
+        "vault_query" => {
+            let args: VaultQueryArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let matches = query_vault(Path::new(&args.vault_path), &args.query)
+                .map_err(|e| format!("query failed: {}", e))?;
+
+            let mut output = String::new();
+            for m in &matches {
+                output.push_str(&m.path);
+                if !m.tags.is_empty() {
+                    output.push_str(&format!("  [{}]", m.tags.join(",")));
+                }
+                if let Some(date) = &m.date {
+                    output.push_str(&format!(" {}", date));
+                }
+                output.push('\n');
+            }
+
+            if matches.is_empty() {
+                output = "No notes match.".to_string();
+            } else {
+                output.push_str(&format!("\n{} notes\n", matches.len()));
+            }
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": { "notes": matches }
+            }))
+        }
+        "vault_index_search" => {
+            let args: VaultIndexSearchArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let vault_path = Path::new(&args.vault_path);
+            let index_dir = args
+                .index_dir
+                .map(PathBuf::from)
+                .unwrap_or_else(|| default_index_dir(vault_path));
+
+            let mut index = VaultIndex::open(vault_path, &index_dir)
+                .map_err(|e| format!("failed to open index: {}", e))?;
+            let started = Instant::now();
+            let update = index
+                .update_with_cache(metadata)
+                .map_err(|e| format!("failed to update index: {}", e))?;
+            log(
+                LogLevel::Info,
+                name,
+                format!(
+                    "index updated in {} ms: {} added, {} updated, {} removed, {} unchanged",
+                    started.elapsed().as_millis(),
+                    update.added,
+                    update.updated,
+                    update.removed,
+                    update.unchanged
+                ),
+            );
+            let started = Instant::now();
+            let hits = index
+                .search(&args.query, args.limit.unwrap_or(20))
+                .map_err(|e| format!("search failed: {}", e))?;
+            log(
+                LogLevel::Debug,
+                name,
+                format!(
+                    "{} hits in {} ms",
+                    hits.len(),
+                    started.elapsed().as_millis()
+                ),
+            );
+
+            let mut output = String::new();
+            for hit in &hits {
+                output.push_str(&format!("## {} ({:.2})\n", hit.path, hit.score));
+                if !hit.tags.is_empty() {
+                    output.push_str(&format!("  [{}]\n", hit.tags.join(",")));
+                }
+                if !hit.snippet.is_empty() {
+                    output.push_str(&format!("  {}\n", hit.snippet));
+                }
+                output.push('\n');
+            }
+
+            if hits.is_empty() {
+                output = "No matches found.".to_string();
+            }
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": { "hits": hits }
+            }))
+        }
+        "vault_export_sqlite" => {
+            let args: VaultExportSqliteArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let summary = export_sqlite(
+                Path::new(&args.vault_path),
+                Path::new(&args.output_path),
+                args.overwrite,
+            )
+            .map_err(|e| format!("export failed: {}", e))?;
+
+            let output = format!(
+                "Exported {} notes, {} tags, {} properties, {} links to {}",
+                summary.notes, summary.tags, summary.properties, summary.links, args.output_path
+            );
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": {
+                    "notes": summary.notes,
+                    "tags": summary.tags,
+                    "properties": summary.properties,
+                    "links": summary.links,
+                    "output_path": args.output_path
+                }
+            }))
+        }
+        "vault_export_html" => {
+            let args: VaultExportHtmlArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let summary = export_html(Path::new(&args.vault_path), Path::new(&args.output_dir))
+                .map_err(|e| format!("export failed: {}", e))?;
+
+            let output = format!(
+                "Exported {} pages with {} links ({} unresolved) to {}",
+                summary.pages, summary.links, summary.unresolved_links, args.output_dir
+            );
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": {
+                    "pages": summary.pages,
+                    "links": summary.links,
+                    "unresolved_links": summary.unresolved_links,
+                    "output_dir": args.output_dir
+                }
+            }))
+        }
+        "vault_broken_links" => {
+            let args: VaultPathArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let broken = find_broken_links(Path::new(&args.vault_path))
+                .map_err(|e| format!("failed to check links: {}", e))?;
+
+            let mut output = String::new();
+            let mut current_source = None;
+            for link in &broken {
+                if current_source != Some(&link.source) {
+                    if current_source.is_some() {
+                        output.push('\n');
+                    }
+                    output.push_str(&format!("## {}\n", link.source));
+                    current_source = Some(&link.source);
+                }
+                output.push_str(&format!(
+                    "  {} [[{}]] {}\n",
+                    link.line_number,
+                    link.target,
+                    link.line_content.trim()
+                ));
+            }
+
+            if broken.is_empty() {
+                output = "No broken links found.".to_string();
+            } else {
+                output.push_str(&format!("\n{} broken links\n", broken.len()));
+            }
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": { "links": broken }
+            }))
+        }
+        "vault_ambiguous_links" => {
+            let args: VaultPathArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let ambiguous = find_ambiguous_links(Path::new(&args.vault_path))
+                .map_err(|e| format!("failed to check links: {}", e))?;
+
+            let mut output = String::new();
+            let mut current_source = None;
+            for link in &ambiguous {
+                if current_source != Some(&link.source) {
+                    if current_source.is_some() {
+                        output.push('\n');
+                    }
+                    output.push_str(&format!("## {}\n", link.source));
+                    current_source = Some(&link.source);
+                }
+                output.push_str(&format!(
+                    "  {} [[{}]] -> {} (also: {})\n",
+                    link.line_number,
+                    link.target,
+                    link.resolved,
+                    link.candidates
+                        .iter()
+                        .filter(|c| **c != link.resolved)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+
+            if ambiguous.is_empty() {
+                output = "No ambiguous links found.".to_string();
+            } else {
+                output.push_str(&format!("\n{} ambiguous links\n", ambiguous.len()));
+            }
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": { "links": ambiguous }
+            }))
+        }
+        "vault_backlinks" => {
+            let args: VaultBacklinksArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let backlinks = backlinks_for(Path::new(&args.vault_path), &args.note)
+                .map_err(|e| format!("failed to find backlinks: {}", e))?;
+
+            let mut output = String::new();
+            let mut current_source = None;
+            for backlink in &backlinks {
+                if current_source != Some(&backlink.source) {
+                    if current_source.is_some() {
+                        output.push('\n');
+                    }
+                    output.push_str(&format!("## {}\n", backlink.source));
+                    current_source = Some(&backlink.source);
+                }
+                output.push_str(&format!(
+                    "  {} {}\n",
+                    backlink.line_number,
+                    backlink.line_content.trim()
+                ));
+            }
+
+            if backlinks.is_empty() {
+                output = format!("No backlinks to {}.", args.note);
+            }
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": {
+                    "note": args.note,
+                    "backlinks": backlinks
+                }
+            }))
+        }
+        "vault_stats" => {
+            let args: VaultPathArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let stats = vault_stats(Path::new(&args.vault_path))
+                .map_err(|e| format!("failed to compute stats: {}", e))?;
+
+            let output = serde_json::to_string_pretty(&stats)
+                .map_err(|e| format!("failed to serialize stats: {}", e))?;
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": stats
+            }))
+        }
+        "vault_rename" => {
+            let args: VaultRenameArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let report = rename_note(Path::new(&args.vault_path), &args.old, &args.new)
+                .map_err(|e| format!("rename failed: {}", e))?;
+
+            let mut output = format!("Renamed {} -> {}\n", report.old_path, report.new_path);
+            if report.files_changed.is_empty() {
+                output.push_str("No links to update.");
+            } else {
+                output.push_str(&format!(
+                    "Rewrote {} links in {} files:\n",
+                    report.links_rewritten,
+                    report.files_changed.len()
+                ));
+                for change in &report.files_changed {
+                    output.push_str(&format!("  {} ({})\n", change.path, change.links_rewritten));
+                }
+            }
+
+            Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": output
+                }],
+                "structuredContent": report
+            }))
+        }
+        "vault_duplicates" => {
+            let args: VaultPathArgs = serde_json::from_value(arguments)
+                .map_err(|e| format!("invalid arguments: {}", e))?;
+
+            let report = find_duplicate_notes(Path::new(&args.vault_path))
+                .map_err(|e| format!("failed to find duplicates: {}", e))?;
+
+            let mut output = String::new();
+            if !report.identical.is_empty() {
+                output.push_str("## Identical\n");
+                for group in &report.identical {
+                    output.push_str(&format!("  {}\n", group.paths.join(", ")));
+                }
+            }
+            if !report.similar.is_empty() {
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                output.push_str("## Near-identical\n");
+                for pair in &report.similar {
+                    output.push_str(&format!(
+                        "  {} ~ {} ({:.0}%)\n",
+                        pair.first,
+                        pair.second,
+                        pair.similarity * 100.0
+                    ));
+                }
+            }
+            if output.is_empty() {
+                output = "No duplicate notes found.".to_string();
+            }
 
             Ok(json!({
                 "content": [{
                     "type": "text",
                     "text": output
-                }]
+                }],
+                "structuredContent": report
             }))
         }
         _ => Err(format!("unknown vault tool: {}", name)),
@@ -0,0 +1,43 @@
+use thiserror::Error;
+
+/// Errors returned by the facade API.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("vault path does not exist: {0}")]
+    VaultNotFound(String),
+    #[error(transparent)]
+    Tree(#[from] vault_tree_core::tree::TreeError),
+    #[error(transparent)]
+    Links(#[from] vault_tree_core::LinkError),
+    #[error(transparent)]
+    Duplicates(#[from] vault_tree_core::DuplicateError),
+    #[error(transparent)]
+    Query(#[from] vault_tree_core::QueryError),
+    #[error(transparent)]
+    Rename(#[from] vault_tree_core::RenameError),
+    #[error(transparent)]
+    Stats(#[from] vault_tree_core::StatsError),
+    #[error(transparent)]
+    Search(#[from] vault_tree_core::search::SearchError),
+    #[error(transparent)]
+    Cache(#[from] vault_tree_core::CacheError),
+    #[cfg(feature = "index")]
+    #[error(transparent)]
+    Index(#[from] vault_tree_core::IndexError),
+    #[cfg(feature = "watch")]
+    #[error(transparent)]
+    Watch(#[from] vault_tree_core::WatchError),
+    #[cfg(feature = "sqlite")]
+    #[error(transparent)]
+    Export(#[from] vault_tree_core::ExportError),
+    #[cfg(feature = "html")]
+    #[error(transparent)]
+    Html(#[from] vault_tree_core::HtmlError),
+    #[error("{0}")]
+    Lookup(String),
+    #[cfg(feature = "async")]
+    #[error("background task failed: {0}")]
+    Join(#[from] tokio::task::JoinError),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
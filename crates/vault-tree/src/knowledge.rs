@@ -0,0 +1,113 @@
+//! Lookups against external knowledge sources (Wikipedia, arXiv, crates.io, ...).
+
+pub use vault_tree_mcp::knowledge::{
+    KnowledgeEntry, KnowledgeProvider, KnowledgeRegistry, LookupOptions, LookupResult,
+    MultiLookupResult,
+};
+
+use std::sync::Arc;
+
+use crate::{Error, Result};
+
+/// Shared registry with builder-style lookups.
+///
+/// Cloning is cheap; clones share the provider set and result cache.
+#[derive(Clone)]
+pub struct Knowledge {
+    registry: Arc<KnowledgeRegistry>,
+}
+
+impl Knowledge {
+    pub fn new() -> Self {
+        Self::from_registry(KnowledgeRegistry::new())
+    }
+
+    pub fn from_registry(registry: KnowledgeRegistry) -> Self {
+        Self {
+            registry: Arc::new(registry),
+        }
+    }
+
+    pub fn registry(&self) -> &KnowledgeRegistry {
+        &self.registry
+    }
+
+    /// Start a lookup; without [`LookupBuilder::provider`] providers are tried in order.
+    pub fn lookup(&self, query: impl Into<String>) -> LookupBuilder {
+        LookupBuilder {
+            registry: Arc::clone(&self.registry),
+            query: query.into(),
+            provider: None,
+            options: LookupOptions::default(),
+        }
+    }
+}
+
+impl Default for Knowledge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct LookupBuilder {
+    registry: Arc<KnowledgeRegistry>,
+    query: String,
+    provider: Option<String>,
+    options: LookupOptions,
+}
+
+impl LookupBuilder {
+    pub fn provider(mut self, name: impl Into<String>) -> Self {
+        self.provider = Some(name.into());
+        self
+    }
+
+    pub fn max_results(mut self, max: usize) -> Self {
+        self.options.max_results = Some(max);
+        self
+    }
+
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.options.language = Some(language.into());
+        self
+    }
+
+    pub fn run(&self) -> Result<LookupResult> {
+        let result = match self.provider.as_deref() {
+            None | Some("auto") => self.registry.auto_lookup(&self.query, &self.options),
+            Some(name) => self
+                .registry
+                .lookup(name, &self.query, &self.options)
+                .ok_or_else(|| Error::Lookup(format!("unknown provider: {}", name)))?,
+        };
+
+        if !result.success {
+            return Err(Error::Lookup(
+                result.error.unwrap_or_else(|| "lookup failed".to_string()),
+            ));
+        }
+        Ok(result)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn run_async(self) -> Result<LookupResult> {
+        tokio::task::spawn_blocking(move || self.run()).await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_provider_is_an_error() {
+        let knowledge = Knowledge::new();
+        let err = knowledge
+            .lookup("rust")
+            .provider("nonexistent")
+            .run()
+            .unwrap_err();
+
+        assert!(err.to_string().contains("unknown provider"));
+    }
+}
@@ -0,0 +1,29 @@
+//! Stable entry point for embedding vault-tree in other Rust applications.
+//!
+//! The workspace crates (`vault-tree-core`, `vault-tree-mcp`) are
+//! implementation details whose names overlap and change freely. This crate
+//! re-exports a curated subset under a fixed module layout:
+//!
+//! - [`vault`]: tree generation and search over Obsidian vaults
+//! - [`knowledge`]: external knowledge lookups (feature `knowledge`, on by default)
+//!
+//! ```no_run
+//! use vault_tree::vault::Vault;
+//!
+//! let vault = Vault::open("/path/to/vault")?;
+//! let tree = vault.tree().depth(2).generate()?;
+//! println!("{}", vault_tree::vault::render_tree(&tree));
+//!
+//! let hits = vault.search("TODO").case_insensitive(true).max_results(20).run()?;
+//! # Ok::<(), vault_tree::Error>(())
+//! ```
+//!
+//! Enable the `async` feature for `*_async` variants that run the blocking
+//! filesystem and network work on tokio's blocking pool.
+
+mod error;
+#[cfg(feature = "knowledge")]
+pub mod knowledge;
+pub mod vault;
+
+pub use error::{Error, Result};
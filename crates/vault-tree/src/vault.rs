@@ -0,0 +1,374 @@
+//! Obsidian vault parsing: annotated trees and regex search.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[cfg(feature = "sqlite")]
+pub use vault_tree_core::ExportSummary;
+#[cfg(feature = "html")]
+pub use vault_tree_core::HtmlSummary;
+pub use vault_tree_core::{
+    extract_frontmatter, extract_links, normalize_link_target, render_graph_dot,
+    render_graph_mermaid, render_tree, render_tree_as, render_tree_as_streaming,
+    render_tree_markdown, render_tree_streaming, AmbiguousLink, Backlink, BrokenLink,
+    DuplicateReport, FileChange, Frontmatter, GraphEdge, GraphNode, Link, LinkGraph, LinkIndex,
+    LinkNormalization, LinkResolver, LinkType, MetadataCache, QueryMatch, RenameReport, Resolution,
+    SearchMatch, SearchOptions, SearchResult, TreeFormat, TreeOptions, VaultAnalysis, VaultNode,
+    VaultStats, VaultTree,
+};
+#[cfg(feature = "watch")]
+pub use vault_tree_core::{ChangeKind, VaultChange, VaultSnapshot, VaultWatcher};
+#[cfg(feature = "index")]
+pub use vault_tree_core::{IndexHit, IndexUpdate, VaultIndex};
+
+use crate::{Error, Result};
+
+/// Handle to a vault directory; entry point for the builder-style API.
+#[derive(Debug, Clone)]
+pub struct Vault {
+    path: PathBuf,
+}
+
+impl Vault {
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if !path.is_dir() {
+            return Err(Error::VaultNotFound(path.display().to_string()));
+        }
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn tree(&self) -> TreeBuilder {
+        TreeBuilder {
+            path: self.path.clone(),
+            options: TreeOptions::default(),
+            cache: None,
+        }
+    }
+
+    /// Notes grouped by year and month; see [`vault_tree_core::generate_timeline`].
+    pub fn timeline(&self) -> Result<VaultTree> {
+        Ok(vault_tree_core::generate_timeline(&self.path)?)
+    }
+
+    pub fn search(&self, pattern: impl Into<String>) -> SearchBuilder {
+        SearchBuilder {
+            path: self.path.clone(),
+            pattern: pattern.into(),
+            options: SearchOptions::default(),
+            cache: None,
+        }
+    }
+
+    pub fn link_graph(&self) -> Result<LinkGraph> {
+        Ok(vault_tree_core::generate_link_graph(&self.path)?)
+    }
+
+    /// Notes linking to `note` (a wikilink name or vault-relative path).
+    pub fn backlinks(&self, note: &str) -> Result<Vec<Backlink>> {
+        Ok(vault_tree_core::backlinks_for(&self.path, note)?)
+    }
+
+    /// Links matching several notes that share a name.
+    pub fn ambiguous_links(&self) -> Result<Vec<AmbiguousLink>> {
+        Ok(vault_tree_core::find_ambiguous_links(&self.path)?)
+    }
+
+    /// Resolver mapping link targets to this vault's notes.
+    pub fn link_resolver(&self) -> Result<LinkResolver> {
+        Ok(LinkResolver::from_vault(&self.path)?)
+    }
+
+    pub fn stats(&self) -> Result<VaultStats> {
+        Ok(vault_tree_core::vault_stats(&self.path)?)
+    }
+
+    /// Tag frequencies, a monthly histogram of note dates, and orphaned notes.
+    pub fn analyze(&self) -> Result<VaultAnalysis> {
+        Ok(vault_tree_core::analyze_vault(&self.path)?)
+    }
+
+    /// Notes matching a filter like `tag:#rust AND modified:>2024-01-01`;
+    /// see [`vault_tree_core::query_vault`] for the syntax.
+    pub fn query(&self, query: &str) -> Result<Vec<QueryMatch>> {
+        Ok(vault_tree_core::query_vault(&self.path, query)?)
+    }
+
+    /// Notes with identical or near-identical bodies.
+    pub fn duplicates(&self) -> Result<DuplicateReport> {
+        Ok(vault_tree_core::find_duplicate_notes(&self.path)?)
+    }
+
+    /// Move a note and rewrite links pointing at it; see [`vault_tree_core::rename_note`].
+    pub fn rename(&self, old: &str, new: &str) -> Result<RenameReport> {
+        Ok(vault_tree_core::rename_note(&self.path, old, new)?)
+    }
+
+    /// Export notes, tags, properties, and links to a SQLite database. An
+    /// existing file that isn't a SQLite database is only replaced with
+    /// `overwrite`.
+    #[cfg(feature = "sqlite")]
+    pub fn export_sqlite(
+        &self,
+        db_path: impl AsRef<Path>,
+        overwrite: bool,
+    ) -> Result<ExportSummary> {
+        Ok(vault_tree_core::export_sqlite(
+            &self.path,
+            db_path.as_ref(),
+            overwrite,
+        )?)
+    }
+
+    /// Render every note to a static HTML site under `out_dir`.
+    #[cfg(feature = "html")]
+    pub fn export_html(&self, out_dir: impl AsRef<Path>) -> Result<HtmlSummary> {
+        Ok(vault_tree_core::export_html(&self.path, out_dir.as_ref())?)
+    }
+
+    /// Keep a live tree and link index, calling `callback` after each burst of
+    /// changes; see [`vault_tree_core::watch_vault`].
+    #[cfg(feature = "watch")]
+    pub fn watch<F>(&self, callback: F) -> Result<VaultWatcher>
+    where
+        F: FnMut(&[VaultChange], &VaultSnapshot) + Send + 'static,
+    {
+        Ok(vault_tree_core::watch_vault(&self.path, callback)?)
+    }
+
+    /// Open (or create) a full-text index for this vault in `index_dir` and
+    /// bring it up to date.
+    #[cfg(feature = "index")]
+    pub fn index(&self, index_dir: impl AsRef<Path>) -> Result<VaultIndex> {
+        let mut index = VaultIndex::open(&self.path, index_dir.as_ref())?;
+        index.update()?;
+        Ok(index)
+    }
+}
+
+/// Configures and runs [`vault_tree_core::generate_tree`].
+#[derive(Debug, Clone)]
+pub struct TreeBuilder {
+    path: PathBuf,
+    options: TreeOptions,
+    cache: Option<Arc<MetadataCache>>,
+}
+
+impl TreeBuilder {
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.options.depth = Some(depth);
+        self
+    }
+
+    /// Skip paths matching `pattern`, in addition to `.vaultignore`.
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.options.exclude.push(pattern.into());
+        self
+    }
+
+    /// Show non-markdown files with per-directory counts and sizes.
+    pub fn include_attachments(mut self, yes: bool) -> Self {
+        self.options.include_attachments = yes;
+        self
+    }
+
+    /// Keep notes tagged with `tag` (repeatable; any tag matches).
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.options.filter_tags.push(tag.into());
+        self
+    }
+
+    /// Keep notes modified on or after a `YYYY-MM-DD` date.
+    pub fn modified_after(mut self, date: impl Into<String>) -> Self {
+        self.options.modified_after = Some(date.into());
+        self
+    }
+
+    /// Keep notes modified before a `YYYY-MM-DD` date.
+    pub fn modified_before(mut self, date: impl Into<String>) -> Self {
+        self.options.modified_before = Some(date.into());
+        self
+    }
+
+    pub fn min_incoming_links(mut self, min: usize) -> Self {
+        self.options.min_incoming_links = Some(min);
+        self
+    }
+
+    /// How link targets are matched to notes (Unicode form, case).
+    pub fn link_normalization(mut self, normalization: LinkNormalization) -> Self {
+        self.options.link_normalization = normalization;
+        self
+    }
+
+    pub fn format(mut self, format: TreeFormat) -> Self {
+        self.options.format = format;
+        self
+    }
+
+    /// Reuse note scans from `cache` for files that haven't changed.
+    pub fn cache(mut self, cache: Arc<MetadataCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn options(&self) -> &TreeOptions {
+        &self.options
+    }
+
+    pub fn generate(&self) -> Result<VaultTree> {
+        Ok(match &self.cache {
+            Some(cache) => {
+                vault_tree_core::generate_tree_with_cache(&self.path, &self.options, cache)?
+            }
+            None => vault_tree_core::generate_tree(&self.path, &self.options)?,
+        })
+    }
+
+    /// Generate the tree and render it in the configured format.
+    pub fn render(&self) -> Result<String> {
+        let tree = self.generate()?;
+        Ok(render_tree_as(&tree, self.options.format)?)
+    }
+
+    /// Generate the tree and write it to `out` as it renders.
+    pub fn render_to(&self, out: impl std::io::Write) -> Result<()> {
+        let tree = self.generate()?;
+        Ok(render_tree_as_streaming(&tree, self.options.format, out)?)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn generate_async(self) -> Result<VaultTree> {
+        tokio::task::spawn_blocking(move || self.generate()).await?
+    }
+}
+
+/// Configures and runs [`vault_tree_core::search_vault`].
+#[derive(Debug, Clone)]
+pub struct SearchBuilder {
+    path: PathBuf,
+    pattern: String,
+    options: SearchOptions,
+    cache: Option<Arc<MetadataCache>>,
+}
+
+impl SearchBuilder {
+    /// Regex applied to file names; only matching files are searched.
+    pub fn file_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.options.file_pattern = Some(pattern.into());
+        self
+    }
+
+    pub fn case_insensitive(mut self, yes: bool) -> Self {
+        self.options.case_insensitive = yes;
+        self
+    }
+
+    pub fn whole_word(mut self, yes: bool) -> Self {
+        self.options.whole_word = yes;
+        self
+    }
+
+    /// Match the pattern as plain text, without regex syntax.
+    pub fn literal(mut self, yes: bool) -> Self {
+        self.options.literal = yes;
+        self
+    }
+
+    pub fn max_results(mut self, max: usize) -> Self {
+        self.options.max_results = Some(max);
+        self
+    }
+
+    /// Skip paths matching `pattern`, in addition to `.vaultignore`.
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.options.exclude.push(pattern.into());
+        self
+    }
+
+    /// Capture lines around each match, like `grep -B before -A after`.
+    pub fn context(mut self, before: usize, after: usize) -> Self {
+        self.options.context_before = before;
+        self.options.context_after = after;
+        self
+    }
+
+    /// Reuse note text from `cache` for files that haven't changed.
+    pub fn cache(mut self, cache: Arc<MetadataCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn options(&self) -> &SearchOptions {
+        &self.options
+    }
+
+    pub fn run(&self) -> Result<Vec<SearchResult>> {
+        Ok(match &self.cache {
+            Some(cache) => vault_tree_core::search_vault_with_cache(
+                &self.path,
+                &self.pattern,
+                &self.options,
+                cache,
+            )?,
+            None => vault_tree_core::search_vault(&self.path, &self.pattern, &self.options)?,
+        })
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn run_async(self) -> Result<Vec<SearchResult>> {
+        tokio::task::spawn_blocking(move || self.run()).await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_vault() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("note1.md"),
+            "---\ntags: [rust]\n---\n\nHello [[note2]]\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("note2.md"), "hello again\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn open_rejects_missing_path() {
+        assert!(matches!(
+            Vault::open("/nonexistent/vault/xyz"),
+            Err(Error::VaultNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn builds_tree_and_searches() {
+        let dir = create_vault();
+        let vault = Vault::open(dir.path()).unwrap();
+
+        let tree = vault.tree().depth(1).generate().unwrap();
+        assert_eq!(tree.total_notes, 2);
+
+        let results = vault.search("hello").case_insensitive(true).run().unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn async_variants_match_blocking() {
+        let dir = create_vault();
+        let vault = Vault::open(dir.path()).unwrap();
+
+        let tree = vault.tree().generate_async().await.unwrap();
+        assert_eq!(tree.total_notes, 2);
+    }
+}
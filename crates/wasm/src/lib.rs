@@ -1,16 +1,19 @@
 use serde::{Deserialize, Serialize};
-use serde_wasm_bindgen::{from_value, to_value};
+use serde_wasm_bindgen::{from_value, to_value, Serializer};
 use vault_tree_core::{
-    compare_tree_entries, count_totals, extract_frontmatter, extract_links, hash_content,
-    node_annotation, normalize_link_target, render_tree_ascii, sum_child_notes, Frontmatter,
-    LinkIndex, TreeRenderable,
+    analyze_notes, compare_tree_entries, count_totals, extract_frontmatter, extract_inline_tags,
+    extract_links, hash_content, merge_tags, node_annotation, normalize_link_target,
+    render_tree_ascii, scan_content, scan_secrets, search_contents, sum_child_notes, Frontmatter,
+    GraphEdge, LinkGraph, LinkIndex, NoteScan, TreeRenderable,
 };
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub fn parse_frontmatter(content: &str) -> Result<JsValue, JsError> {
     let fm = extract_frontmatter(content).map_err(|e| JsError::new(&e.to_string()))?;
-    to_value(&fm).map_err(|e| JsError::new(&e.to_string()))
+    // Custom fields are flattened in; emit a plain object rather than a JS Map.
+    fm.serialize(&Serializer::json_compatible())
+        .map_err(|e| JsError::new(&e.to_string()))
 }
 
 #[wasm_bindgen]
@@ -19,6 +22,12 @@ pub fn parse_links(content: &str) -> Result<JsValue, JsError> {
     to_value(&links).map_err(|e| JsError::new(&e.to_string()))
 }
 
+#[wasm_bindgen]
+pub fn parse_tags(content: &str) -> Result<JsValue, JsError> {
+    let tags = extract_inline_tags(content);
+    to_value(&tags).map_err(|e| JsError::new(&e.to_string()))
+}
+
 #[wasm_bindgen]
 pub fn normalize_link(target: &str) -> String {
     normalize_link_target(target)
@@ -29,6 +38,14 @@ pub fn compute_hash(content: &[u8]) -> String {
     hash_content(content)
 }
 
+/// Likely credentials in `text`, redacted, so a UI can warn before a note
+/// holding a private key or API token is published.
+#[wasm_bindgen]
+pub fn scan_content_for_secrets(text: &str) -> Result<JsValue, JsError> {
+    let findings = scan_secrets(text);
+    to_value(&findings).map_err(|e| JsError::new(&e.to_string()))
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FileEntry {
     pub path: String,
@@ -53,6 +70,8 @@ pub struct TreeNode {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TreeResult {
     pub tree: String,
+    /// The same tree as `tree`, for consumers rendering their own UI.
+    pub root: TreeNode,
     pub total_notes: usize,
     pub total_dirs: usize,
 }
@@ -91,8 +110,7 @@ pub fn build_tree(files_js: JsValue, options_js: JsValue) -> Result<JsValue, JsE
     let options: TreeOptions = from_value(options_js).unwrap_or_default();
 
     let mut link_index = LinkIndex::new();
-    let mut file_metadata: std::collections::HashMap<String, (Option<Frontmatter>, usize)> =
-        std::collections::HashMap::new();
+    let mut file_metadata: FileMetadataMap = std::collections::HashMap::new();
 
     for file in &files {
         if file.is_dir {
@@ -109,7 +127,9 @@ pub fn build_tree(files_js: JsValue, options_js: JsValue) -> Result<JsValue, JsE
             }
 
             let fm = extract_frontmatter(content).ok();
-            file_metadata.insert(file.path.clone(), (fm, outgoing_count));
+            let fm_tags = fm.as_ref().map_or(&[][..], |f| &f.tags);
+            let tags = merge_tags(fm_tags, &extract_inline_tags(content));
+            file_metadata.insert(file.path.clone(), (fm, tags, outgoing_count));
         }
     }
 
@@ -120,6 +140,7 @@ pub fn build_tree(files_js: JsValue, options_js: JsValue) -> Result<JsValue, JsE
 
     let result = TreeResult {
         tree: rendered,
+        root,
         total_notes,
         total_dirs,
     };
@@ -127,6 +148,127 @@ pub fn build_tree(files_js: JsValue, options_js: JsValue) -> Result<JsValue, JsE
     to_value(&result).map_err(|e| JsError::new(&e.to_string()))
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GraphNode {
+    pub id: String,
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+    pub incoming_links: usize,
+    pub outgoing_links: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GraphResult {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Notes and the links between them, resolved the same way as on the native
+/// side. Links to missing notes are left out.
+#[wasm_bindgen]
+pub fn build_link_graph(files_js: JsValue) -> Result<JsValue, JsError> {
+    let files: Vec<FileEntry> =
+        from_value(files_js).map_err(|e| JsError::new(&format!("invalid files: {}", e)))?;
+
+    let graph = LinkGraph::from_notes(&scan_files(&files));
+    let index = graph.link_index();
+
+    let nodes = graph
+        .nodes
+        .into_iter()
+        .map(|node| GraphNode {
+            incoming_links: index.incoming_count(&node.id),
+            outgoing_links: index.outgoing_count(&node.id),
+            id: node.id,
+            title: node.title,
+            tags: node.tags,
+        })
+        .collect();
+
+    let result = GraphResult {
+        nodes,
+        edges: graph.edges,
+    };
+
+    to_value(&result).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Tag frequencies, a monthly histogram of note dates, and orphaned notes.
+#[wasm_bindgen]
+pub fn analyze_vault(files_js: JsValue) -> Result<JsValue, JsError> {
+    let files: Vec<FileEntry> =
+        from_value(files_js).map_err(|e| JsError::new(&format!("invalid files: {}", e)))?;
+
+    let analysis = analyze_notes(&scan_files(&files));
+
+    to_value(&analysis).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Scan every markdown file that came with its content.
+fn scan_files(files: &[FileEntry]) -> Vec<(String, NoteScan)> {
+    files
+        .iter()
+        .filter(|file| !file.is_dir && file.path.ends_with(".md"))
+        .filter_map(|file| Some((file.path.clone(), scan_content(file.content.as_deref()?))))
+        .collect()
+}
+
+#[wasm_bindgen]
+pub fn search_files(
+    files_js: JsValue,
+    pattern: &str,
+    options_js: JsValue,
+) -> Result<JsValue, JsError> {
+    let files: Vec<FileEntry> =
+        from_value(files_js).map_err(|e| JsError::new(&format!("invalid files: {}", e)))?;
+
+    let options: SearchOptions = from_value(options_js).unwrap_or_default();
+
+    let contents = files
+        .iter()
+        .filter(|file| !file.is_dir)
+        .filter_map(|file| Some((file.path.as_str(), file.content.as_deref()?)));
+    let results = search_contents(contents, pattern, &options.into())
+        .map_err(|e| JsError::new(&e.to_string()))?;
+
+    to_value(&results).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SearchOptions {
+    #[serde(default)]
+    file_pattern: Option<String>,
+    #[serde(default)]
+    case_insensitive: bool,
+    #[serde(default)]
+    whole_word: bool,
+    #[serde(default)]
+    literal: bool,
+    #[serde(default)]
+    max_results: Option<usize>,
+    #[serde(default)]
+    context_before: usize,
+    #[serde(default)]
+    context_after: usize,
+}
+
+impl From<SearchOptions> for vault_tree_core::SearchOptions {
+    fn from(options: SearchOptions) -> Self {
+        Self {
+            file_pattern: options.file_pattern,
+            case_insensitive: options.case_insensitive,
+            whole_word: options.whole_word,
+            literal: options.literal,
+            max_results: options.max_results,
+            exclude: Vec::new(),
+            context_before: options.context_before,
+            context_after: options.context_after,
+        }
+    }
+}
+
+type FileMetadataMap = std::collections::HashMap<String, (Option<Frontmatter>, Vec<String>, usize)>;
+
 #[derive(Debug, Default, Deserialize)]
 struct TreeOptions {
     #[serde(default)]
@@ -137,7 +279,7 @@ struct TreeOptions {
 
 fn build_tree_structure(
     files: &[FileEntry],
-    metadata: &std::collections::HashMap<String, (Option<Frontmatter>, usize)>,
+    metadata: &FileMetadataMap,
     link_index: &LinkIndex,
     options: &TreeOptions,
 ) -> TreeNode {
@@ -170,13 +312,17 @@ fn build_tree_structure(
         }
 
         let normalized_name = normalize_link_target(&file.name);
-        let (fm, outgoing) = metadata.get(&file.path).cloned().unwrap_or((None, 0));
+        let (fm, tags, outgoing) =
+            metadata
+                .get(&file.path)
+                .cloned()
+                .unwrap_or((None, Vec::new(), 0));
 
         let node = TreeNode {
             path: file.path.clone(),
             name: file.name.clone(),
             is_dir: file.is_dir,
-            tags: fm.as_ref().map(|f| f.tags.clone()).unwrap_or_default(),
+            tags,
             date: fm.as_ref().and_then(|f| f.date.clone()),
             incoming_links: link_index.incoming_count(&normalized_name),
             outgoing_links: outgoing,
@@ -24,6 +24,10 @@ export async function initWasm(): Promise<WasmModule> {
       normalize_link: wasm.normalize_link,
       compute_hash: wasm.compute_hash,
       build_tree: wasm.build_tree,
+      search_files: wasm.search_files,
+      build_link_graph: wasm.build_link_graph,
+      analyze_vault: wasm.analyze_vault,
+      scan_content_for_secrets: wasm.scan_content_for_secrets,
     };
 
     return wasmModule;
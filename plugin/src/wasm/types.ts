@@ -10,12 +10,83 @@ export interface TreeOptions {
   root_name?: string;
 }
 
+export interface TreeNode {
+  path: string;
+  name: string;
+  is_dir: boolean;
+  tags: string[];
+  date: string | null;
+  incoming_links: number;
+  outgoing_links: number;
+  children: TreeNode[];
+  note_count: number;
+}
+
 export interface TreeResult {
   tree: string;
+  root: TreeNode;
   total_notes: number;
   total_dirs: number;
 }
 
+export interface SearchOptions {
+  file_pattern?: string;
+  case_insensitive?: boolean;
+  whole_word?: boolean;
+  literal?: boolean;
+  max_results?: number;
+  context_before?: number;
+  context_after?: number;
+}
+
+export interface SearchMatch {
+  line_number: number;
+  line_content: string;
+  match_start: number;
+  match_end: number;
+  context_before?: string[];
+  context_after?: string[];
+}
+
+export interface SearchResult {
+  file_path: string;
+  matches: SearchMatch[];
+}
+
+export interface GraphNode {
+  id: string;
+  title: string | null;
+  tags: string[];
+  incoming_links: number;
+  outgoing_links: number;
+}
+
+export interface GraphEdge {
+  source: string;
+  target: string;
+  link_type: string;
+}
+
+export interface GraphResult {
+  nodes: GraphNode[];
+  edges: GraphEdge[];
+}
+
+export interface VaultAnalysis {
+  tags: { tag: string; notes: number }[];
+  dates: { month: string; notes: number }[];
+  undated: number;
+  orphans: string[];
+}
+
+export interface SecretFinding {
+  kind: string;
+  line_number: number;
+  match_start: number;
+  match_end: number;
+  redacted: string;
+}
+
 export interface Frontmatter {
   title?: string;
   date?: string;
@@ -36,4 +107,8 @@ export interface WasmModule {
   normalize_link(target: string): string;
   compute_hash(content: Uint8Array): string;
   build_tree(files: FileEntry[], options: TreeOptions): TreeResult;
+  search_files(files: FileEntry[], pattern: string, options: SearchOptions): SearchResult[];
+  build_link_graph(files: FileEntry[]): GraphResult;
+  analyze_vault(files: FileEntry[]): VaultAnalysis;
+  scan_content_for_secrets(text: string): SecretFinding[];
 }